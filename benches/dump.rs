@@ -0,0 +1,37 @@
+//! Compares `dump_tree` with `dump_tree_parallel` on a tree of 50 000 keys. Run with `cargo bench --features rayon`
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use mininip::datas::{tree::Tree, Identifier, Value};
+use mininip::dump::{dump_tree, dump_tree_parallel};
+
+/// Builds a tree of `sections` sections holding `keys` keys each, with values of various types
+fn large_tree(sections: usize, keys: usize) -> Tree {
+    let mut tree = Tree::new();
+    for i in 0..sections {
+        let section = format!("section{}", i);
+        for j in 0..keys {
+            let value = match j % 4 {
+                0 => Value::Int(j as i64),
+                1 => Value::Float(j as f64 / 3.0),
+                2 => Value::Str(format!("value; number {}", j)),
+                _ => Value::Bool(j % 8 == 3),
+            };
+            tree.insert(Identifier::new(Some(section.clone()), format!("key{}", j)), value);
+        }
+    }
+
+    tree
+}
+
+fn dump(c: &mut Criterion) {
+    let tree = large_tree(500, 100);
+    assert_eq!(dump_tree_parallel(&tree), dump_tree(&tree));
+
+    let mut group = c.benchmark_group("dump 50 000 keys");
+    group.bench_function("dump_tree", |b| b.iter(|| dump_tree(&tree)));
+    group.bench_function("dump_tree_parallel", |b| b.iter(|| dump_tree_parallel(&tree)));
+    group.finish();
+}
+
+criterion_group!(benches, dump);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+//! Compares `parse_content` on a large ASCII content, read by byte scanning, with the same content whose comments are not ASCII, read by the Unicode path. Run with `cargo bench --bench parse`, and with `--save-baseline` and `--baseline` to compare two versions of the crate
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use mininip::parse::parse_content;
+
+/// Builds a content of `sections` sections holding `keys` keys each, with values of various types and a comment ending each line
+fn large_content(sections: usize, keys: usize, comment: &str) -> String {
+    let mut content = String::new();
+    for i in 0..sections {
+        content.push_str(&format!("[section{}] ; {}\n", i, comment));
+        for j in 0..keys {
+            let value = match j % 4 {
+                0 => format!("{}", j),
+                1 => format!("{}", j as f64 / 3.0),
+                2 => format!("'value; number {}'", j),
+                _ => String::from(if j % 8 == 3 { "yes" } else { "no" }),
+            };
+            content.push_str(&format!("key{} = {} ; {}\n", j, value, comment));
+        }
+    }
+
+    content
+}
+
+fn parse(c: &mut Criterion) {
+    let ascii = large_content(500, 100, "a comment");
+    let unicode = large_content(500, 100, "a comment \u{2014} not ASCII");
+    assert_eq!(parse_content(&ascii).unwrap(), parse_content(&unicode).unwrap());
+
+    let mut group = c.benchmark_group("parse 50 000 keys");
+    group.bench_function("ASCII", |b| b.iter(|| parse_content(&ascii).unwrap()));
+    group.bench_function("Unicode comments", |b| b.iter(|| parse_content(&unicode).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
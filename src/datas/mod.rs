@@ -1,6 +1,15 @@
 //! The basic datas structures like [`Identifier`](datas/struct.Identifier.html "Identifier") and [`Value`](datas/enum.Value.html "Value")
 
-use std::fmt::{self, Display, Formatter};
+use crate::prelude::*;
+use core::fmt::{self, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::mem;
+use alloc::borrow::Cow;
+use core::cmp::Ordering;
+use core::convert::{Infallible, TryFrom};
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io;
 use crate::{parse, dump};
 use crate::errors::{Error, error_kinds::*};
 
@@ -9,11 +18,30 @@ use crate::errors::{Error, error_kinds::*};
 /// The following types are available
 /// - `Raw`: the raw content of the file, not formatted. The only computation is that the escaped characters are unescaped (see [parse_str](../parse/fn.parse_str.html "parse::parse_str") to learn more about escaped characters)
 /// - `Str`: a quoted string written inside non-escaped quotes like that `"Hello world!"` or that `'Hello world!'`
-/// - `Int`: a 64 bytes-sized integer
+/// - `Int`: a 64 bytes-sized integer, written in decimal or with one of the prefixes `0x`, `0o` or `0b`
 /// - `Float`: a 64 bytes-sized floating-point number
-/// - `Bool`: a boolean (currently either `on` or `off`)
+/// - `Bool`: a boolean (either `true` or `false` once dumped)
+/// - `Array`: a list of values separated by a separator such as `,`. It is only parsed if asked (see [`ParserOptions::array_separator`](../parse/struct.ParserOptions.html#structfield.array_separator "parse::ParserOptions::array_separator"))
+/// - `Color`: a color written in hexadecimal as `#RGB`, `#RRGGBB` or `#RRGGBBAA` with its red, green and blue components and its alpha component if any. It is only parsed if asked (see [`ParserOptions::parse_colors`](../parse/struct.ParserOptions.html#structfield.parse_colors "parse::ParserOptions::parse_colors"))
+/// - `Null`: a key explicitly set to no value, unlike an empty string, written as a bare key without any separator or as a null token such as `null`. It is only parsed if asked (see [`ParserOptions::bare_keys`](../parse/struct.ParserOptions.html#structfield.bare_keys "parse::ParserOptions::bare_keys") and [`ParserOptions::null_token`](../parse/struct.ParserOptions.html#structfield.null_token "parse::ParserOptions::null_token"))
 /// 
 /// Each type is represented as an enum variant
+/// 
+/// Two values are equal if they are of the same variant with the same content, so `Int(1)` is not equal to `Float(1.0)` nor `Raw(1)` to `Str(1)`. As a `Float` may be `NaN`, `Value` does not implement `Eq`, but it implements `Hash` and [`ValueKey`](struct.ValueKey.html "datas::ValueKey") makes it the key of a `HashMap`
+/// 
+/// A `Value` may be built from a Rust primitive with `From`: the integers up to `i64` (and up to `u32` for the unsigned ones) give an `Int`, the floats a `Float`, `bool` a `Bool` and the strings a `Str`. The other way around, `TryFrom<Value>` extracts the primitive and gives the value back as the error if its variant does not match or if it does not fit in the type asked
+/// 
+/// # Example
+/// ```
+/// use mininip::datas::Value;
+/// use std::convert::TryFrom;
+/// 
+/// assert_eq!(Value::from(42), Value::Int(42));
+/// assert_eq!(Value::from("text"), Value::Str(String::from("text")));
+/// 
+/// assert_eq!(u8::try_from(Value::Int(255)), Ok(255));
+/// assert_eq!(u8::try_from(Value::Int(256)), Err(Value::Int(256)));
+/// ```
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Raw(String),
@@ -21,6 +49,9 @@ pub enum Value {
     Int(i64),
     Float(f64),
     Bool(bool),
+    Array(Vec<Value>),
+    Color(u8, u8, u8, Option<u8>),
+    Null,
 }
 
 impl Display for Value {
@@ -30,49 +61,282 @@ impl Display for Value {
             Value::Str(string)   => string.fmt(formatter),
             Value::Int(number)   => number.fmt(formatter),
             Value::Float(number) => number.fmt(formatter),
-            Value::Bool(true)    => "on".fmt(formatter),
-            Value::Bool(false)   => "off".fmt(formatter),
+            Value::Bool(true)    => "true".fmt(formatter),
+            Value::Bool(false)   => "false".fmt(formatter),
+            Value::Array(values) => {
+                for (n, i) in values.iter().enumerate() {
+                    if n != 0 {
+                        formatter.write_str(", ")?;
+                    }
+                    i.fmt(formatter)?;
+                }
+
+                Ok(())
+            },
+            Value::Color(..)     => format_color(self).fmt(formatter),
+            Value::Null          => Ok(()),
         }
     }
 }
 
+/// Hashes a `Value` consistently with its equality: a `Float` is hashed by the bit pattern of its number, except `-0.0` which is hashed like `0.0` since they are equal. A `NaN` is never equal to any value so its bit pattern does not matter
+/// 
+/// As `Value` does not implement `Eq`, it can not be the key of a `HashMap` or a `HashSet` itself. [`ValueKey`](struct.ValueKey.html "datas::ValueKey") can
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+
+        match self {
+            Value::Raw(string)       => string.hash(state),
+            Value::Str(string)       => string.hash(state),
+            Value::Int(number)       => number.hash(state),
+            Value::Float(number)     => float_bits(*number).hash(state),
+            Value::Bool(boolean)     => boolean.hash(state),
+            Value::Array(values)     => values.hash(state),
+            Value::Color(r, g, b, a) => (r, g, b, a).hash(state),
+            Value::Null              => {},
+        }
+    }
+}
+
+/// Returns the bit pattern of `number`, the same for `0.0` and `-0.0`
+fn float_bits(number: f64) -> u64 {
+    if number == 0.0 {
+        0.0f64.to_bits()
+    } else {
+        number.to_bits()
+    }
+}
+
+/// A [`Value`](enum.Value.html "datas::Value") usable as the key of a `HashMap` or a `HashSet`, to deduplicate values for instance
+/// 
+/// Two `ValueKey`s are equal if their values are equal, except the `Float`s which are compared by the bit pattern of their number so that equality is reflexive: a `NaN` is equal to a `NaN` with the same bit pattern. `0.0` and `-0.0` are still equal
+/// 
+/// # Example
+/// ```
+/// use mininip::datas::{Value, ValueKey};
+/// use std::collections::HashSet;
+/// 
+/// let mut values = HashSet::new();
+/// values.insert(ValueKey(Value::Float(f64::NAN)));
+/// values.insert(ValueKey(Value::Float(f64::NAN)));
+/// values.insert(ValueKey(Value::Int(1)));
+/// values.insert(ValueKey(Value::Float(1.0)));
+/// 
+/// assert_eq!(values.len(), 3);
+/// assert!(values.contains(&ValueKey(Value::Int(1))));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ValueKey(pub Value);
+
+impl ValueKey {
+    /// Consumes `self` and returns its value
+    pub fn into_inner(self) -> Value {
+        self.0
+    }
+}
+
+impl From<Value> for ValueKey {
+    fn from(value: Value) -> ValueKey {
+        ValueKey(value)
+    }
+}
+
+impl PartialEq for ValueKey {
+    fn eq(&self, other: &ValueKey) -> bool {
+        same_values(&self.0, &other.0)
+    }
+}
+
+impl Eq for ValueKey {}
+
+impl Hash for ValueKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+/// Returns `true` if `a` and `b` are equal, the `Float`s being compared by the bit pattern of their number
+pub(crate) fn same_values(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Float(a), Value::Float(b)) => float_bits(*a) == float_bits(*b),
+        (Value::Array(a), Value::Array(b)) => a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_values(a, b)),
+        (a, b)                             => a == b,
+    }
+}
+
+/// Builds a `Value` from a string typed by a user, not formatted as INI (nothing is unescaped)
+/// 
+/// The type is inferred with the following rules, in this order
+/// - `true` and `false` are a `Bool`
+/// - an optional sign `+` or `-` followed by decimal digits is an `Int` (if it fits in an `i64`)
+/// - a decimal number with a fractional part and or an exponent, such as `1.0`, `.5` or `1e3`, is a `Float`. `1.0` is never an `Int`
+/// - anything else is a `Str`, including the numbers with a leading zero such as `007` or `00.5`, the numbers written with a prefix such as `0x10` and the special floats such as `inf` or `NaN`
+/// 
+/// This conversion never fails
+/// 
+/// # Example
+/// ```
+/// use mininip::datas::Value;
+/// 
+/// assert_eq!("42".parse::<Value>().unwrap(), Value::Int(42));
+/// assert_eq!("1.0".parse::<Value>().unwrap(), Value::Float(1.0));
+/// assert_eq!("true".parse::<Value>().unwrap(), Value::Bool(true));
+/// assert_eq!("007".parse::<Value>().unwrap(), Value::Str(String::from("007")));
+/// ```
+impl FromStr for Value {
+    type Err = Infallible;
+
+    fn from_str(content: &str) -> Result<Value, Infallible> {
+        match content {
+            "true"  => return Ok(Value::Bool(true)),
+            "false" => return Ok(Value::Bool(false)),
+            _       => {},
+        }
+
+        if is_plain_number(content) {
+            if let Ok(value) = content.parse::<i64>() {
+                return Ok(Value::Int(value));
+            } else if let Ok(value) = content.parse::<f64>() {
+                if content.contains(&['.', 'e', 'E'][..]) {
+                    return Ok(Value::Float(value));
+                }
+            }
+        }
+
+        Ok(Value::Str(String::from(content)))
+    }
+}
+
 impl Default for Value {
     fn default() -> Self {
         Value::Raw(String::new())
     }
 }
 
+/// Implements `From<$type>` for `Value` as a `Value::$variant` and `TryFrom<Value>` for `$type`, which gives the value back as the error if it is of another variant or does not fit in `$type`
+macro_rules! convert_values {
+    ($variant:ident: $($type:ty),*) => {
+        $(
+            impl From<$type> for Value {
+                fn from(value: $type) -> Value {
+                    Value::$variant(value.into())
+                }
+            }
+
+            impl TryFrom<Value> for $type {
+                type Error = Value;
+
+                fn try_from(value: Value) -> Result<$type, Value> {
+                    match value {
+                        Value::$variant(content) => <$type>::try_from(content).map_err(|_| Value::$variant(content)),
+                        value                    => Err(value),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+convert_values!(Int: i64, i32, i16, i8, u32, u16, u8);
+convert_values!(Bool: bool);
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Value {
+        Value::Float(value)
+    }
+}
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Value {
+        Value::Float(value.into())
+    }
+}
+
+/// Gives `value` back as the error if it is not a `Value::Float`. An integer is not converted
+impl TryFrom<Value> for f64 {
+    type Error = Value;
+
+    fn try_from(value: Value) -> Result<f64, Value> {
+        match value {
+            Value::Float(value) => Ok(value),
+            value               => Err(value),
+        }
+    }
+}
+
+/// Builds a `Value::Str`
+impl From<String> for Value {
+    fn from(value: String) -> Value {
+        Value::Str(value)
+    }
+}
+
+/// Builds a `Value::Str`
+impl From<&str> for Value {
+    fn from(value: &str) -> Value {
+        Value::Str(String::from(value))
+    }
+}
+
+/// Gives `value` back as the error if it is neither a `Value::Str` nor a `Value::Raw`
+impl TryFrom<Value> for String {
+    type Error = Value;
+
+    fn try_from(value: Value) -> Result<String, Value> {
+        match value {
+            Value::Str(value) | Value::Raw(value) => Ok(value),
+            value                                 => Err(value),
+        }
+    }
+}
+
 impl Value {
     /// Builds a new [`Value`](enum.Value.html "datas::Value") from `content`, an INI-formatted string
     /// 
     /// # Return value
     /// `Ok(value)` with `value` as the new object
     /// 
-    /// `Err(error)` when an error occurs while parsing `content` with `error` as the error code
+    /// `Err(error)` when an error occurs while parsing `content` with `error` as the error code, such as an [`IntegerOverflow`](../errors/error_kinds/struct.IntegerOverflow.html "errors::error_kinds::IntegerOverflow") if `content` is an integer which does not fit in an `i64`
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::Value;
+    /// use mininip::errors::Error;
+    /// 
+    /// assert_eq!(Value::parse("9223372036854775807").unwrap(), Value::Int(i64::MAX));
+    /// match Value::parse("99999999999999999999") {
+    ///     Err(Error::IntegerOverflow(err)) => assert_eq!(err.literal(), "99999999999999999999"),
+    ///     other                            => panic!("Wrong return value: {:?}", other),
+    /// }
+    /// ```
     pub fn parse(content: &str) -> Result<Value, Error> {
         let effective = content.trim();
 
-        if effective.starts_with("'") || effective.starts_with("\"") {
-            let quote = &effective[..1];
+        if let Some(string) = unquote(content)? {
+            Ok(Value::Str(string))
+        }
 
-            if !effective.ends_with(quote) {
-                let err = ExpectedToken::new(String::from(content), content.len(), String::from(quote));
-                Err(Error::from(err))
-            } else {
-                Ok(Value::Str(parse::parse_str(&effective[1..effective.len() - 1])?))
-            }
+        else if let Some(boolean) = parse_bool_keyword(effective) {
+            Ok(Value::Bool(boolean))
         }
 
-        else if effective == "on" || effective == "enabled" || effective == "y" || effective == "yes" {
-            Ok(Value::Bool(true))
-        } else if effective == "off" || effective == "disabled" || effective == "n" || effective == "no" {
-            Ok(Value::Bool(false))
+        else if let Some(value) = parse_prefixed_int(effective) {
+            match value {
+                Some(value) => Ok(Value::Int(value)),
+                None        => Err(Error::from(IntegerOverflow::new(String::from(content), String::from(effective)))),
+            }
         }
 
         else if let Ok(value) = effective.parse::<i64>() {
             Ok(Value::Int(value))
         }
 
+        // The float parser would read it as a rounded number
+        else if is_decimal_int(effective) {
+            Err(Error::from(IntegerOverflow::new(String::from(content), String::from(effective))))
+        }
+
         else if let Ok(value) = effective.parse::<f64>() {
             Ok(Value::Float(value))
         }
@@ -82,10 +346,120 @@ impl Value {
         }
     }
 
+    /// Builds a new `Value::Raw` from `content`, an INI-formatted string, without inferring its type
+    /// 
+    /// The quotes around `content` are removed and its escape sequences are resolved like with [`Value::parse`](enum.Value.html#method.parse "datas::Value::parse") but `content` is never read as an `Int`, a `Float` or a `Bool`
+    /// 
+    /// # Return value
+    /// `Ok(value)` with `value` as the new object
+    /// 
+    /// `Err(error)` when an error occurs while parsing `content` with `error` as the error code
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::Value;
+    /// 
+    /// assert_eq!(Value::parse_untyped("0x2A").unwrap(), Value::Raw(String::from("0x2A")));
+    /// assert_eq!(Value::parse_untyped("' yes '").unwrap(), Value::Raw(String::from(" yes ")));
+    /// ```
+    pub fn parse_untyped(content: &str) -> Result<Value, Error> {
+        match unquote(content)? {
+            Some(string) => Ok(Value::Raw(string)),
+            None         => Ok(Value::Raw(parse::parse_str(content.trim())?)),
+        }
+    }
+
+    /// Builds a new `Value::Color` from `content`, a color written in hexadecimal as `#RGB`, `#RRGGBB` or `#RRGGBBAA`
+    /// 
+    /// # Return value
+    /// A `Value::Color` with the components of `content`, or a `Value::Str` with the text of `content` if it is not a valid color
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::Value;
+    /// 
+    /// assert_eq!(Value::parse_color("#1a2b3c"), Value::Color(0x1a, 0x2b, 0x3c, None));
+    /// assert_eq!(Value::parse_color("#fff8"), Value::Str(String::from("#fff8")));
+    /// assert_eq!(Value::parse_color("#F00"), Value::Color(0xff, 0, 0, None));
+    /// assert_eq!(Value::parse_color("#00000080"), Value::Color(0, 0, 0, Some(0x80)));
+    /// ```
+    pub fn parse_color(content: &str) -> Value {
+        let effective = content.trim();
+        let digits = match effective.strip_prefix('#') {
+            Some(digits) if digits.bytes().all(|c| c.is_ascii_hexdigit()) => digits,
+            _                                                             => return Value::Str(String::from(effective)),
+        };
+
+        let component = |n: usize, width: usize| {
+            let value = u8::from_str_radix(&digits[n * width..(n + 1) * width], 16).expect("The digits are hexadecimal");
+            // A single digit `F` stands for `FF`
+            if width == 1 { value * 0x11 } else { value }
+        };
+        match digits.len() {
+            3 => Value::Color(component(0, 1), component(1, 1), component(2, 1), None),
+            6 => Value::Color(component(0, 2), component(1, 2), component(2, 2), None),
+            8 => Value::Color(component(0, 2), component(1, 2), component(2, 2), Some(component(3, 2))),
+            _ => Value::Str(String::from(effective)),
+        }
+    }
+
+    /// Builds a new [`Value`](enum.Value.html "datas::Value") from `content`, an INI-formatted string which may be a list of values separated by `separator`
+    /// 
+    /// `content` is split on each occurrence of `separator` which is neither escaped (like `\,`) nor inside quotes. Each element is then trimmed and parsed as with [`Value::parse`](enum.Value.html#method.parse "datas::Value::parse") except an empty element which is an empty `Value::Str`
+    /// 
+    /// # Return value
+    /// `Ok(value)` with `value` as a `Value::Array` if `content` contains a separator or as the single value it contains otherwise
+    /// 
+    /// `Err(error)` when an error occurs while parsing an element with `error` as the error code
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::Value;
+    /// 
+    /// let value = Value::parse_array("a.com, 'b.com', 8080, a\\, b", ',').unwrap();
+    /// 
+    /// assert_eq!(value, Value::Array(vec![
+    ///     Value::Raw(String::from("a.com")),
+    ///     Value::Str(String::from("b.com")),
+    ///     Value::Int(8080),
+    ///     Value::Raw(String::from("a, b")),
+    /// ]));
+    /// ```
+    pub fn parse_array(content: &str, separator: char) -> Result<Value, Error> {
+        Value::parse_array_with(content, separator, false, Value::parse)
+    }
+
+    /// Like [`parse_array`](enum.Value.html#method.parse_array "datas::Value::parse_array") but with `parse` parsing each element, such as [`Value::parse_untyped`](enum.Value.html#method.parse_untyped "datas::Value::parse_untyped"). An empty element is then `parse("''")`
+    /// 
+    /// If `trailing` is `true`, a separator ending `content` is ignored (see [`ParserOptions::allow_trailing_comma`](../parse/struct.ParserOptions.html#structfield.allow_trailing_comma "parse::ParserOptions::allow_trailing_comma"))
+    pub(crate) fn parse_array_with<F: Fn(&str) -> Result<Value, Error>>(content: &str, separator: char, trailing: bool, parse: F) -> Result<Value, Error> {
+        let mut elements = parse::split_unescaped(content, separator);
+        let trailing = trailing && elements.len() > 1 && elements.last().is_some_and(|i| i.trim().is_empty());
+        if trailing {
+            elements.pop();
+        } else if elements.len() == 1 {
+            return parse(&elements[0]);
+        }
+
+        let mut values = Vec::with_capacity(elements.len());
+        for i in elements {
+            let value = if i.trim().is_empty() {
+                parse("''")?
+            } else {
+                parse(&i)?
+            };
+            values.push(value);
+        }
+
+        Ok(Value::Array(values))
+    }
+
     /// Formats `self` to be dumped in an INI file
     /// 
     /// It means that `format!("{}={}", ident, value.dump())` with `ident` as a valid key and `value` a [`Value`](enum.Value.html "Value") can be properly registered and then, parsed as INI
     /// 
+    /// This is the text written by [`Dumper`](../dump/struct.Dumper.html "dump::Dumper") and [`dump_tree`](../dump/fn.dump_tree.html "dump::dump_tree") for a value whose text was not retained while parsing
+    /// 
     /// # Return value
     /// A `String` containing the value of `self` once formatted
     /// 
@@ -95,11 +469,13 @@ impl Value {
     /// # Note
     /// `self` is backed up in a way preserving its type
     /// 
-    /// - `Raw` is backed up as is, once escaped
+    /// - `Raw` is backed up as is, once escaped. If it starts or ends with a whitespace, it is quoted like a `Str` so this whitespace is kept
     /// - `Str` is backed up with two quotes `'` or `"` around its value once escaped
     /// - `Int` is backed up as is
-    /// - `Float` is backed up as is
+    /// - `Float` is backed up with the shortest text giving back the same number once parsed. It always has a fractional part or an exponent so it is not read back as an `Int`. The infinities and NaN are backed up as `inf`, `-inf` and `nan`
     /// - `Bool` is backed up as two different values: `true` and `false`
+    /// - `Array` is backed up as its elements separated by `,` (see [`dump_with_separator`](enum.Value.html#method.dump_with_separator "datas::Value::dump_with_separator"))
+    /// - `Color` is backed up as `#rrggbb`, followed by its alpha component if any. It is only read back as a `Color` if asked (see [`ParserOptions::parse_colors`](../parse/struct.ParserOptions.html#structfield.parse_colors "parse::ParserOptions::parse_colors"))
     /// 
     /// # Examples
     /// ```
@@ -112,16 +488,372 @@ impl Value {
     /// ```
     pub fn dump(&self) -> String {
         match self {
-            Value::Raw(string)   => format!("{}", dump::dump_str(&string)),
-            Value::Str(string)   => format!("'{}'", dump::dump_str(&string)),
+            Value::Raw(string)   => if string.starts_with(char::is_whitespace) || string.ends_with(char::is_whitespace) {
+                format!("'{}'", dump::dump_str(string))
+            } else {
+                dump::dump_str(string)
+            },
+            Value::Str(string)   => format!("'{}'", dump::dump_str(string)),
             Value::Int(number)   => format!("{}", number),
-            Value::Float(number) => format!("{}", number),
-            Value::Bool(true)    => String::from("on"),
-            Value::Bool(false)   => String::from("off"),
+            Value::Float(number) => format_float(*number),
+            Value::Bool(true)    => String::from("true"),
+            Value::Bool(false)   => String::from("false"),
+            Value::Array(_)      => self.dump_with_separator(','),
+            Value::Color(..)     => format_color(self),
+            Value::Null          => String::new(),
+        }
+    }
+
+    /// Writes `self` formatted by [`dump`](enum.Value.html#method.dump "datas::Value::dump") into `writer`
+    /// 
+    /// # Return value
+    /// An `io::Result<()>` which indicates an error of `writer`
+    /// 
+    /// # Note
+    /// Requires the `std` feature
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::Value;
+    /// 
+    /// let mut line = Vec::from("answer=");
+    /// Value::Int(42).write_dump(&mut line).unwrap();
+    /// 
+    /// assert_eq!(line, b"answer=42");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_dump<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(self.dump().as_bytes())
+    }
+
+    /// Formats `self` to be dumped in an INI file like [`dump`](enum.Value.html#method.dump "datas::Value::dump") but with the elements of an `Array` separated by `separator`. The separators inside an element are escaped
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::Value;
+    /// 
+    /// let value = Value::Array(vec![
+    ///     Value::Raw(String::from("a|b")),
+    ///     Value::Int(2),
+    ///     Value::Str(String::new()),
+    /// ]);
+    /// 
+    /// assert_eq!(value.dump_with_separator('|'), "a\\|b|2|''");
+    /// ```
+    pub fn dump_with_separator(&self, separator: char) -> String {
+        let values = match self {
+            Value::Array(values) => values,
+            _                    => return self.dump(),
+        };
+
+        let mut result = String::new();
+        for (n, i) in values.iter().enumerate() {
+            if n != 0 {
+                result.push(separator);
+            }
+
+            match i {
+                Value::Raw(string) => {
+                    for c in dump::dump_str(string).chars() {
+                        if c == separator {
+                            result.push('\\');
+                        }
+                        result.push(c);
+                    }
+                },
+                _                  => result.push_str(&i.dump_with_separator(separator)),
+            }
+        }
+
+        result
+    }
+
+    /// Returns the source text of `self`, without any quote nor INI escaping
+    /// 
+    /// This is the text of `Value::Raw` read by a parser without type inference (see [`ParserOptions::infer_types`](../parse/struct.ParserOptions.html#structfield.infer_types "parse::ParserOptions::infer_types")) from the [`dump`](enum.Value.html#method.dump "datas::Value::dump") of `self`. It is the same canonical text as [`as_raw_str`](enum.Value.html#method.as_raw_str "datas::Value::as_raw_str") and [`Tree::get_raw`](tree/struct.Tree.html#method.get_raw "datas::tree::Tree::get_raw"): a `Bool` is either `true` or `false` and the elements of an `Array` are separated by `, `
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::Value;
+    /// 
+    /// assert_eq!(Value::Int(42).raw(), "42");
+    /// assert_eq!(Value::Bool(true).raw(), "true");
+    /// assert_eq!(Value::Str(String::from("42")).raw(), "42");
+    /// assert_eq!(Value::parse_untyped(&Value::Float(0.5).dump()).unwrap(), Value::Raw(String::from("0.5")));
+    /// ```
+    pub fn raw(&self) -> Cow<'_, str> {
+        self.as_raw_str()
+    }
+
+    /// Returns the canonical text of `self`, without any INI escaping
+    /// 
+    /// For an `Int`, a `Float` or a `Bool`, this is the text which gives back `self` once parsed with [`FromStr`](enum.Value.html#impl-FromStr "datas::Value"). A `Float` always has a fractional part or an exponent and a `Bool` is either `true` or `false`, as written by [`dump`](enum.Value.html#method.dump "datas::Value::dump"). The elements of an `Array` are separated by `, `
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::Value;
+    /// 
+    /// assert_eq!(Value::Float(1.0).as_raw_str(), "1.0");
+    /// assert_eq!(Value::Bool(false).as_raw_str(), "false");
+    /// assert_eq!(Value::Str(String::from("a = b")).as_raw_str(), "a = b");
+    /// ```
+    pub fn as_raw_str(&self) -> Cow<'_, str> {
+        match self {
+            Value::Raw(string)   => Cow::Borrowed(string),
+            Value::Str(string)   => Cow::Borrowed(string),
+            Value::Int(number)   => Cow::Owned(format!("{}", number)),
+            Value::Float(number) => Cow::Owned(format_float(*number)),
+            Value::Bool(true)    => Cow::Borrowed("true"),
+            Value::Bool(false)   => Cow::Borrowed("false"),
+            Value::Array(values) => {
+                let values: Vec<Cow<str>> = values.iter().map(Value::as_raw_str).collect();
+                Cow::Owned(values.join(", "))
+            },
+            Value::Color(..)     => Cow::Owned(format_color(self)),
+            Value::Null          => Cow::Borrowed(""),
+        }
+    }
+
+    /// Returns the name of the type of `self`, which is `"raw"`, `"str"`, `"int"`, `"float"`, `"bool"`, `"array"`, `"color"` or `"null"`
+    /// 
+    /// These names are the ones used by mininip itself, for instance by a [`TypeError`](../errors/struct.TypeError.html "errors::TypeError") or in a type annotation (see [`ParserOptions::type_annotations`](../parse/struct.ParserOptions.html#structfield.type_annotations "parse::ParserOptions::type_annotations"))
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::Value;
+    /// 
+    /// let value = Value::Float(0.5);
+    /// assert_eq!(format!("expected int, found {}", value.type_name()), "expected int, found float");
+    /// ```
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Raw(_)    => "raw",
+            Value::Str(_)    => "str",
+            Value::Int(_)    => "int",
+            Value::Float(_)  => "float",
+            Value::Bool(_)   => "bool",
+            Value::Array(_)  => "array",
+            Value::Color(..) => "color",
+            Value::Null      => "null",
+        }
+    }
+
+    /// Returns `true` if `self` is a `Value::Null`, which is distinct from an empty string
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::Value;
+    /// 
+    /// assert!(Value::Null.is_null());
+    /// assert!(!Value::Str(String::new()).is_null());
+    /// ```
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Returns the number held by `self` if it is an `Int` or a `Float`, an `Int` being converted to the nearest `f64`
+    /// 
+    /// A `Raw` or a `Str` is never converted, even if its text is a number
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::Value;
+    /// 
+    /// assert_eq!(Value::Int(8080).as_f64(), Some(8080.0));
+    /// assert_eq!(Value::Float(0.5).as_f64(), Some(0.5));
+    /// assert_eq!(Value::Str(String::from("1")).as_f64(), None);
+    /// ```
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(number)   => Some(*number as f64),
+            Value::Float(number) => Some(*number),
+            _                    => None,
+        }
+    }
+
+    /// Compares the numbers held by `self` and `other` whatever their variant, so that `Int(1)` and `Float(1.0)` are equal
+    /// 
+    /// Two `Int`s are compared exactly while an `Int` and a `Float` are compared as `f64`s (see [`as_f64`](enum.Value.html#method.as_f64 "datas::Value::as_f64")). A `Raw` or a `Str` is never converted, even if its text is a number
+    /// 
+    /// # Parameters
+    /// `bools_as_numbers`: compares a `Bool` as the number `0` for `false` and `1` for `true` if `true`. Otherwise, a `Bool` is not a number
+    /// 
+    /// # Return value
+    /// `Some(ordering)` with `ordering` as the ordering of the numbers of `self` and `other`
+    /// 
+    /// `None` if one of them is not a number or is `NaN`
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::Value;
+    /// use std::cmp::Ordering;
+    /// 
+    /// let port = Value::Int(8080);
+    /// assert_eq!(port.partial_cmp_numeric(&Value::Int(1024), false), Some(Ordering::Greater));
+    /// assert_eq!(Value::Int(1).partial_cmp_numeric(&Value::Float(1.0), false), Some(Ordering::Equal));
+    /// assert_eq!(Value::Bool(true).partial_cmp_numeric(&Value::Int(1), false), None);
+    /// assert_eq!(Value::Bool(true).partial_cmp_numeric(&Value::Int(1), true), Some(Ordering::Equal));
+    /// ```
+    pub fn partial_cmp_numeric(&self, other: &Value, bools_as_numbers: bool) -> Option<Ordering> {
+        let as_number = |value: &Value| match value {
+            Value::Bool(boolean) if bools_as_numbers => Some(Value::Int(*boolean as i64)),
+            Value::Int(_) | Value::Float(_)          => Some(value.clone()),
+            _                                        => None,
+        };
+
+        match (as_number(self)?, as_number(other)?) {
+            (Value::Int(a), Value::Int(b)) => Some(a.cmp(&b)),
+            (a, b)                         => a.as_f64()?.partial_cmp(&b.as_f64()?),
         }
     }
 }
 
+/// Returns the content of `content` without its quotes and unescaped if it is quoted, `None` otherwise
+fn unquote(content: &str) -> Result<Option<String>, Error> {
+    let effective = content.trim();
+    if !effective.starts_with('\'') && !effective.starts_with('"') {
+        return Ok(None);
+    }
+
+    let quote = &effective[..1];
+    if effective.len() == 1 || !effective.ends_with(quote) {
+        let err = ExpectedToken::new(String::from(content), content.len(), String::from(quote));
+        Err(Error::from(err))
+    } else {
+        Ok(Some(parse::parse_quoted_str(&effective[1..effective.len() - 1])?))
+    }
+}
+
+/// Returns the text of the string read by [`Value::parse`](enum.Value.html#method.parse "datas::Value::parse") from `content` and whether it is quoted if this text is written as is in `content`, without any escape sequence, or `None` otherwise, for instance if `content` is a number
+pub(crate) fn plain_text(content: &str) -> Option<(&str, bool)> {
+    let effective = content.trim();
+    let plain = |text: &str, forbidden: &[char]| text.chars().all(|c| {
+        c.is_ascii() && (c == '\t' || !c.is_control()) && !forbidden.contains(&c)
+    });
+
+    if let Some(quote) = effective.chars().next().filter(|&c| c == '\'' || c == '"') {
+        let text = effective.strip_prefix(quote)?.strip_suffix(quote)?;
+        return if plain(text, &parse::QUOTED_FORBIDDEN) { Some((text, true)) } else { None };
+    }
+
+    let number = parse_prefixed_int(effective).is_some() || is_decimal_int(effective) || effective.parse::<f64>().is_ok();
+    if number || parse_bool_keyword(effective).is_some() || !plain(effective, &parse::FORBIDDEN) {
+        return None;
+    }
+    Some((effective, false))
+}
+
+/// Formats `color`, a `Value::Color`, as `#rrggbb` or `#rrggbbaa`
+fn format_color(color: &Value) -> String {
+    match color {
+        Value::Color(r, g, b, None)    => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        Value::Color(r, g, b, Some(a)) => format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a),
+        _                              => unreachable!("Only a color is formatted as a color"),
+    }
+}
+
+/// Formats `number` with the shortest text giving back `number` once parsed, with a fractional part or an exponent. The very small and very big numbers use an exponent
+fn format_float(number: f64) -> String {
+    if number.is_nan() {
+        return String::from("nan");
+    } else if number.is_infinite() {
+        return String::from(if number > 0.0 { "inf" } else { "-inf" });
+    }
+
+    let magnitude = number.abs();
+    if magnitude != 0.0 && !(1e-5..1e16).contains(&magnitude) {
+        return format!("{:e}", number);
+    }
+
+    // The standard formatting is already the shortest one giving back the same number
+    let mut string = format!("{}", number);
+    if !string.contains('.') {
+        string.push_str(".0");
+    }
+    string
+}
+
+/// Returns the boolean written `content` as described in the README, such as `on` or `no`, or `None` if `content` is not a boolean
+pub(crate) fn parse_bool_keyword(content: &str) -> Option<bool> {
+    match content {
+        "true" | "on" | "enabled" | "y" | "yes"   => Some(true),
+        "false" | "off" | "disabled" | "n" | "no" => Some(false),
+        _                                         => None,
+    }
+}
+
+/// Returns `true` if `content` is made of an optional sign, decimal digits without any leading zero, and optionally a fractional part and an exponent, which are the numbers accepted by the `FromStr` implementation of `Value`
+fn is_plain_number(content: &str) -> bool {
+    let unsigned = content.strip_prefix(&['+', '-'][..]).unwrap_or(content);
+    let mantissa = match unsigned.find(&['e', 'E'][..]) {
+        Some(index) => {
+            let exponent = &unsigned[index + 1..];
+            let exponent = exponent.strip_prefix(&['+', '-'][..]).unwrap_or(exponent);
+            if exponent.is_empty() || !exponent.bytes().all(|c| c.is_ascii_digit()) {
+                return false;
+            }
+            &unsigned[..index]
+        },
+        None        => unsigned,
+    };
+
+    let (integer, fraction) = match mantissa.find('.') {
+        Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+        None        => (mantissa, ""),
+    };
+
+    let digits = |part: &str| part.bytes().all(|c| c.is_ascii_digit());
+    if !digits(integer) || !digits(fraction) || integer.len() + fraction.len() == 0 {
+        return false;
+    }
+
+    // A leading zero is only allowed alone, as in `0` or `0.5`
+    !(integer.len() > 1 && integer.starts_with('0'))
+}
+
+/// Parses an integer written with one of the prefixes `0x` (hexadecimal), `0o` (octal) or `0b` (binary), optionally signed and with underscores `_` between its digits
+/// 
+/// # Return value
+/// `Some(Some(value))` with `value` as the integer parsed
+/// 
+/// `Some(None)` if `content` is such an integer but does not fit in an `i64`
+/// 
+/// `None` if `content` is not such an integer
+fn parse_prefixed_int(content: &str) -> Option<Option<i64>> {
+    let (sign, unsigned) = match content.as_bytes().first() {
+        Some(b'-') => ("-", &content[1..]),
+        Some(b'+') => ("", &content[1..]),
+        _          => ("", content),
+    };
+
+    let radix = match unsigned.get(..2) {
+        Some("0x") | Some("0X") => 16,
+        Some("0o") | Some("0O") => 8,
+        Some("0b") | Some("0B") => 2,
+        _                       => return None,
+    };
+
+    let digits = &unsigned[2..];
+    if !digits.chars().all(|c| c == '_' || c.is_digit(radix)) {
+        return None;
+    }
+
+    let mut number = String::with_capacity(digits.len() + 1);
+    number.push_str(sign);
+    number.extend(digits.chars().filter(|&c| c != '_'));
+    if number.len() == sign.len() {
+        return None;
+    }
+
+    Some(i64::from_str_radix(&number, radix).ok())
+}
+
+/// Returns `true` if `content` is an integer written in decimal, optionally signed
+fn is_decimal_int(content: &str) -> bool {
+    let unsigned = content.strip_prefix(&['+', '-'][..]).unwrap_or(content);
+    !unsigned.is_empty() && unsigned.bytes().all(|c| c.is_ascii_digit())
+}
+
 
 /// The identifier of a variable, which is its identity. Of course, this type is `Hash` because it may be used as a key in a `HashMap`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -134,10 +866,10 @@ impl Identifier {
     /// Creates an identifier with a valid section name and a valid name
     /// 
     /// # Panics
-    /// Panics if either `section` or `name` is an invalid identifier according to [`Identifier::is_valid`](struct.Identifier.html#method.is_valid "datas::Identifier::is_valid")
+    /// Panics if either `section` or `name` is an invalid identifier according to [`Identifier::is_valid`](struct.Identifier.html#method.is_valid "datas::Identifier::is_valid"). A section name may also contain escaped dots, as the name of a nested section declared like `[version\\.2]` (see [`ParserOptions::nested_sections`](../parse/struct.ParserOptions.html#structfield.nested_sections "parse::ParserOptions::nested_sections"))
     pub fn new(section: Option<String>, name: String) -> Identifier {
         if let Some(section) = &section {
-            assert!(Identifier::is_valid(section));
+            assert!(Identifier::is_valid_section(section));
         }
         assert!(Identifier::is_valid(&name));
 
@@ -147,6 +879,58 @@ impl Identifier {
         }
     }
 
+    /// Creates an identifier, checking the validity of its section name and its name
+    /// 
+    /// # Return value
+    /// `Ok(ident)` with `ident` as the identifier built
+    /// 
+    /// `Err(error)` with `error` as an [`InvalidIdentifier`](../errors/error_kinds/struct.InvalidIdentifier.html "errors::error_kinds::InvalidIdentifier") error if either `section` or `name` is an invalid identifier according to [`Identifier::is_valid`](struct.Identifier.html#method.is_valid "datas::Identifier::is_valid")
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::Identifier;
+    /// use mininip::errors::Error;
+    /// 
+    /// let ident = Identifier::try_new(Some("server"), "port").unwrap();
+    /// assert_eq!(ident, Identifier::new(Some(String::from("server")), String::from("port")));
+    /// 
+    /// match Identifier::try_new(None::<String>, "1st key") {
+    ///     Err(Error::InvalidIdentifier(err)) => assert_eq!(format!("{}", err), "Invalid identifier 1st key in 1st key"),
+    ///     _                                  => panic!("1st key is not a valid name"),
+    /// }
+    /// ```
+    pub fn try_new<S: Into<String>, N: Into<String>>(section: Option<S>, name: N) -> Result<Identifier, Error> {
+        let section = section.map(Into::into);
+        let name = name.into();
+
+        let invalid = match &section {
+            Some(section) if !Identifier::is_valid_section(section) => Some(section),
+            _ if !Identifier::is_valid(&name)                       => Some(&name),
+            _                                                       => None,
+        };
+        if let Some(i) = invalid {
+            return Err(Error::from(InvalidIdentifier::new(i.clone(), i.clone())));
+        }
+
+        Ok(Identifier::new_unchecked(section, name))
+    }
+
+    /// Creates an identifier without checking the validity of its section name and its name, for the callers which already know they are valid
+    /// 
+    /// # Note
+    /// An invalid identifier is not unsafe to use but it may not be read back once dumped. The validity is still checked in debug builds
+    // `Option::is_none_or` would need Rust 1.82
+    #[allow(clippy::unnecessary_map_or)]
+    pub fn new_unchecked(section: Option<String>, name: String) -> Identifier {
+        debug_assert!(section.as_deref().map_or(true, Identifier::is_valid_section), "Invalid section name");
+        debug_assert!(Identifier::is_valid(&name), "Invalid name");
+
+        Identifier {
+            section,
+            name,
+        }
+    }
+
     /// Returns `true` if the given string is a valid identifier and `false` otherwise
     /// 
     /// A valid identifier is defined as a string of latin alphanumeric characters and any of `_`, `~`, `-`, `.`, `:`, `$` and space starting with a latin alphabetic one or any of `.`, `$` or `:`. All of these characters must be ASCII
@@ -193,6 +977,15 @@ impl Identifier {
         true
     }
 
+    /// Returns `true` if the given string is a valid section name, which is a valid identifier according to [`Identifier::is_valid`](struct.Identifier.html#method.is_valid "datas::Identifier::is_valid") whose dots may be escaped as those of a nested section
+    pub(crate) fn is_valid_section(section: &str) -> bool {
+        if section.contains('\\') {
+            Identifier::is_valid(&section.replace("\\.", "."))
+        } else {
+            Identifier::is_valid(section)
+        }
+    }
+
     /// Returns the name of the variable as a reference
     pub fn name(&self) -> &str {
         &self.name
@@ -200,10 +993,7 @@ impl Identifier {
 
     /// Returns the section of the variable which may be a named section as `Some(name)` or the "global scope" wich is `None`
     pub fn section(&self) -> Option<&str> {
-        match &self.section {
-            Some(val) => Some(&val),
-            None      => None,
-        }
+        self.section.as_deref()
     }
 
     /// Change the name of the variable
@@ -229,10 +1019,40 @@ impl Identifier {
     }
 }
 
+/// Identifiers are ordered by section, the global section coming first, then by name. The names are compared by the byte order of their UTF-8 encoding, which is the order of their Unicode code points, without case folding so that `Port` comes before `host`. This order does not depend on the platform nor on the order of insertion
+/// 
+/// # Example
+/// ```
+/// use mininip::datas::Identifier;
+/// 
+/// let mut idents = vec![
+///     Identifier::new(Some(String::from("server")), String::from("host")),
+///     Identifier::new(Some(String::from("client")), String::from("port")),
+///     Identifier::new(None, String::from("name")),
+///     Identifier::new(Some(String::from("server")), String::from("Port")),
+/// ];
+/// idents.sort();
+/// 
+/// let names: Vec<String> = idents.iter().map(|i| format!("{}", i)).collect();
+/// assert_eq!(names, ["name", "client.port", "server.Port", "server.host"]);
+/// ```
+impl Ord for Identifier {
+    fn cmp(&self, other: &Identifier) -> Ordering {
+        self.section().cmp(&other.section())
+                      .then_with(|| self.name.as_bytes().cmp(other.name.as_bytes()))
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Identifier) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Display for Identifier {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         if let Some(section) = &self.section {
-            formatter.write_str(&section)?;
+            formatter.write_str(section)?;
             formatter.write_str(".")?;
         }
 
@@ -240,6 +1060,58 @@ impl Display for Identifier {
     }
 }
 
+/// Builds an `Identifier` from its `Display` form, either `name` for a key of the global section or `section.name`
+/// 
+/// The string is split on its last `.` (unless it is its first character, as in `.name` which is a global key) and both parts must be valid according to [`Identifier::is_valid`](struct.Identifier.html#method.is_valid "datas::Identifier::is_valid")
+/// 
+/// # Note
+/// Since a `.` is allowed in an identifier, a key whose name contains a `.` and a global key containing a `.` after its first character can not be built this way. Use [`Identifier::new`](struct.Identifier.html#method.new "datas::Identifier::new") instead
+/// 
+/// # Example
+/// ```
+/// use mininip::datas::Identifier;
+/// 
+/// let ident: Identifier = "valid since 1.2.0.key".parse().unwrap();
+/// assert_eq!(ident.section(), Some("valid since 1.2.0"));
+/// assert_eq!(ident.name(), "key");
+/// 
+/// assert!("section.1key".parse::<Identifier>().is_err());
+/// ```
+impl FromStr for Identifier {
+    type Err = InvalidIdentifier;
+
+    fn from_str(ident: &str) -> Result<Identifier, InvalidIdentifier> {
+        let (section, name) = match ident.rfind('.') {
+            Some(index) if index != 0 => (Some(&ident[..index]), &ident[index + 1..]),
+            _                         => (None, ident),
+        };
+
+        for i in section.into_iter().chain(core::iter::once(name)) {
+            if !Identifier::is_valid(i) {
+                return Err(InvalidIdentifier::new(String::from(ident), String::from(i)));
+            }
+        }
+
+        Ok(Identifier::new_unchecked(section.map(String::from), String::from(name)))
+    }
+}
+
+/// Same as [`FromStr`](struct.Identifier.html#impl-FromStr "datas::Identifier")
+impl TryFrom<String> for Identifier {
+    type Error = InvalidIdentifier;
+
+    fn try_from(ident: String) -> Result<Identifier, InvalidIdentifier> {
+        ident.parse()
+    }
+}
+
+/// Returns the name of the key, without its section
+impl AsRef<str> for Identifier {
+    fn as_ref(&self) -> &str {
+        &self.name
+    }
+}
+
 
 pub mod tree;
 
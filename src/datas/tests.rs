@@ -1,5 +1,8 @@
+#![allow(clippy::approx_constant)]
+
 use crate::datas::*;
 use crate::errors::Error;
+use std::convert::TryFrom;
 
 #[test]
 fn value_display() {
@@ -7,6 +10,10 @@ fn value_display() {
     let val = Value::Raw(String::from(txt));
 
     assert_eq!(format!("{}", val), txt);
+
+    // Displayed like it is dumped
+    assert_eq!(format!("{}", Value::Bool(true)), "true");
+    assert_eq!(format!("{}", Value::Bool(false)), "false");
 }
 
 #[test]
@@ -17,6 +24,21 @@ fn value_dump() {
     assert_eq!(dumped, "tr\\x0000e8s_content\\=\\x00263a \\; the symbol of hapiness");
 }
 
+#[test]
+fn value_dump_raw_with_whitespaces() {
+    let val = Value::Raw(String::from(" spaced "));
+    let dumped = val.dump();
+
+    assert_eq!(dumped, "' spaced '");
+    assert_eq!(Value::parse(&dumped).unwrap(), Value::Str(String::from(" spaced ")));
+}
+
+#[test]
+fn value_parse_str_keeps_whitespaces() {
+    assert_eq!(Value::parse(" \"  spaced  \" ").unwrap(), Value::Str(String::from("  spaced  ")));
+    assert_eq!(Value::parse("'\\t tab'").unwrap(), Value::Str(String::from("\t tab")));
+}
+
 #[test]
 fn value_parse_raw() {
     let val = Value::parse(r"Hello \x002665").unwrap();
@@ -47,6 +69,39 @@ fn value_parse_int() {
     assert_eq!(val, Value::Int(666));
 }
 
+#[test]
+fn value_parse_int_prefixed() {
+    assert_eq!(Value::parse("0xFF").unwrap(), Value::Int(0xFF));
+    assert_eq!(Value::parse("-0x10").unwrap(), Value::Int(-0x10));
+    assert_eq!(Value::parse("0o755").unwrap(), Value::Int(0o755));
+    assert_eq!(Value::parse("+0b1010").unwrap(), Value::Int(0b1010));
+    assert_eq!(Value::parse("0x_ff_00").unwrap(), Value::Int(0xff00));
+}
+
+#[test]
+fn value_parse_int_prefixed_invalid() {
+    assert_eq!(Value::parse("0x").unwrap(), Value::Raw(String::from("0x")));
+    assert_eq!(Value::parse("0xZZ").unwrap(), Value::Raw(String::from("0xZZ")));
+    assert_eq!(Value::parse("0b102").unwrap(), Value::Raw(String::from("0b102")));
+}
+
+#[test]
+fn value_parse_int_overflow() {
+    for i in &["0x8000000000000000", "99999999999999999999", "-9223372036854775809", "+0b1_0000000000000000000000000000000000000000000000000000000000000000"] {
+        match Value::parse(i) {
+            Err(Error::IntegerOverflow(err)) => {
+                assert_eq!(err.literal(), *i);
+                assert_eq!(format!("{}", err.with_line_number(3)), format!("Integer {} out of range in {} at line 3", i, i));
+            },
+            other                            => panic!("Wrong return value for {}: {:?}", i, other),
+        }
+    }
+
+    assert_eq!(Value::parse("-9223372036854775808").unwrap(), Value::Int(i64::MIN));
+    assert_eq!(Value::parse("1e20").unwrap(), Value::Float(1e20));
+    assert_eq!(Value::parse("'99999999999999999999'").unwrap(), Value::Str(String::from("99999999999999999999")));
+}
+
 #[test]
 fn value_parse_float() {
     let val = Value::parse("666.0").unwrap();
@@ -54,6 +109,83 @@ fn value_parse_float() {
     assert_eq!(val, Value::Float(666.0));
 }
 
+#[test]
+fn value_parse_array() {
+    let val = Value::parse_array(" a.com ,b.com, 42 ", ',').unwrap();
+
+    assert_eq!(val, Value::Array(vec![
+        Value::Raw(String::from("a.com")),
+        Value::Raw(String::from("b.com")),
+        Value::Int(42),
+    ]));
+}
+
+#[test]
+fn value_parse_array_escaped_separator() {
+    let val = Value::parse_array("a\\,b, 'c,d', e\\\\,f", ',').unwrap();
+
+    assert_eq!(val, Value::Array(vec![
+        Value::Raw(String::from("a,b")),
+        Value::Str(String::from("c,d")),
+        Value::Raw(String::from("e\\")),
+        Value::Raw(String::from("f")),
+    ]));
+}
+
+#[test]
+fn value_parse_array_empty_element() {
+    let val = Value::parse_array("a,,b,", ',').unwrap();
+
+    assert_eq!(val, Value::Array(vec![
+        Value::Raw(String::from("a")),
+        Value::Str(String::new()),
+        Value::Raw(String::from("b")),
+        Value::Str(String::new()),
+    ]));
+}
+
+#[test]
+fn value_parse_array_single() {
+    assert_eq!(Value::parse_array("a\\,b", ',').unwrap(), Value::Raw(String::from("a,b")));
+    assert_eq!(Value::parse_array("123", ',').unwrap(), Value::Int(123));
+}
+
+#[test]
+fn value_parse_array_ini_separator() {
+    let val = Value::parse_array("a\\:b:c", ':').unwrap();
+
+    assert_eq!(val, Value::Array(vec![
+        Value::Raw(String::from("a:b")),
+        Value::Raw(String::from("c")),
+    ]));
+}
+
+#[test]
+fn value_dump_array() {
+    let val = Value::Array(vec![
+        Value::Raw(String::from("a,b")),
+        Value::Str(String::from("c,d")),
+        Value::Str(String::new()),
+        Value::Bool(true),
+    ]);
+    let dumped = val.dump();
+
+    assert_eq!(dumped, "a\\,b,'c,d','',true");
+    assert_eq!(Value::parse_array(&dumped, ',').unwrap(), val);
+}
+
+#[test]
+fn value_dump_array_with_separator() {
+    let val = Value::Array(vec![
+        Value::Raw(String::from("a|b; c")),
+        Value::Int(1),
+    ]);
+    let dumped = val.dump_with_separator('|');
+
+    assert_eq!(dumped, "a\\|b\\; c|1");
+    assert_eq!(Value::parse_array(&dumped, '|').unwrap(), val);
+}
+
 #[test]
 fn value_parse_bool_on() {
     let val = Value::parse("on").unwrap();
@@ -117,6 +249,40 @@ fn value_parse_err() {
     assert!(val.is_err());
 }
 
+#[test]
+fn value_from_str() {
+    assert_eq!("42".parse::<Value>().unwrap(), Value::Int(42));
+    assert_eq!("-42".parse::<Value>().unwrap(), Value::Int(-42));
+    assert_eq!("0".parse::<Value>().unwrap(), Value::Int(0));
+    assert_eq!("1.0".parse::<Value>().unwrap(), Value::Float(1.0));
+    assert_eq!("0.5".parse::<Value>().unwrap(), Value::Float(0.5));
+    assert_eq!(".5".parse::<Value>().unwrap(), Value::Float(0.5));
+    assert_eq!("-1e3".parse::<Value>().unwrap(), Value::Float(-1000.0));
+    assert_eq!("true".parse::<Value>().unwrap(), Value::Bool(true));
+    assert_eq!("false".parse::<Value>().unwrap(), Value::Bool(false));
+}
+
+#[test]
+fn value_from_str_ambiguous() {
+    for i in &["007", "00.5", "0x10", "inf", "NaN", "1e", "1.2.3", ".", "-", "on", "True", "", " 42", "99999999999999999999"] {
+        assert_eq!(i.parse::<Value>().unwrap(), Value::Str(String::from(*i)), "{:?} must be a Str", i);
+    }
+}
+
+#[test]
+fn value_as_raw_str() {
+    assert_eq!(Value::Raw(String::from("a;b")).as_raw_str(), "a;b");
+    assert_eq!(Value::Int(-3).as_raw_str(), "-3");
+    assert_eq!(Value::Float(1.0).as_raw_str(), "1.0");
+    assert_eq!(Value::Float(0.25).as_raw_str(), "0.25");
+    assert_eq!(Value::Bool(true).as_raw_str(), "true");
+    assert_eq!(Value::Array(vec![Value::Int(1), Value::Bool(false)]).as_raw_str(), "1, false");
+
+    for i in &[Value::Int(12), Value::Float(-2.0), Value::Float(1e300), Value::Bool(false)] {
+        assert_eq!(&i.as_raw_str().parse::<Value>().unwrap(), i);
+    }
+}
+
 #[test]
 fn identifier_new_some() {
     let section = Some(String::from("Section_name"));
@@ -143,6 +309,62 @@ fn identifier_new_panics() {
     let _ident = Identifier::new(section, variable);
 }
 
+#[test]
+fn identifier_from_str() {
+    let ident: Identifier = "key".parse().unwrap();
+    assert_eq!(ident, Identifier::new(None, String::from("key")));
+
+    let ident: Identifier = ".key".parse().unwrap();
+    assert_eq!(ident, Identifier::new(None, String::from(".key")));
+
+    let ident: Identifier = "section.key".parse().unwrap();
+    assert_eq!(ident, Identifier::new(Some(String::from("section")), String::from("key")));
+
+    let ident: Identifier = "a.b.c".parse().unwrap();
+    assert_eq!(ident, Identifier::new(Some(String::from("a.b")), String::from("c")));
+}
+
+#[test]
+fn identifier_from_str_display_round_trip() {
+    let ident = Identifier::new(Some(String::from("valid since 1.2.0")), String::from("$key"));
+
+    assert_eq!(format!("{}", ident).parse::<Identifier>().unwrap(), ident);
+}
+
+#[test]
+fn identifier_from_str_invalid() {
+    for i in &["", "1key", "section.", "section.1key", "bad section!.key", "key?"] {
+        assert!(i.parse::<Identifier>().is_err(), "{:?} must be rejected", i);
+    }
+
+    let err = "section.1key".parse::<Identifier>().unwrap_err();
+    assert_eq!(format!("{}", err), "Invalid identifier 1key in section.1key");
+}
+
+#[test]
+fn identifier_ord() {
+    let global = Identifier::new(None, String::from("zeta"));
+    let upper = Identifier::new(Some(String::from("a")), String::from("Key"));
+    let lower = Identifier::new(Some(String::from("a")), String::from("key"));
+    let other = Identifier::new(Some(String::from("b")), String::from("alpha"));
+
+    assert!(global < upper);
+    assert!(upper < lower);
+    assert!(lower < other);
+    assert_eq!(lower.cmp(&lower.clone()), std::cmp::Ordering::Equal);
+    assert_eq!(lower.partial_cmp(&other), Some(std::cmp::Ordering::Less));
+}
+
+#[test]
+fn identifier_try_from_and_as_ref() {
+    use std::convert::TryFrom;
+
+    let ident = Identifier::try_from(String::from("section.key")).unwrap();
+    assert_eq!(ident.as_ref(), "key");
+    assert!(Identifier::try_from(String::from("no way")).is_ok());
+    assert!(Identifier::try_from(String::from("no way!")).is_err());
+}
+
 #[test]
 fn identifier_is_valid_full_test() {
     assert!(Identifier::is_valid("UPPERCASE_ONE"));
@@ -210,3 +432,168 @@ fn identifier_format_without_section() {
 
     assert_eq!(format!("{}", ident), variable);
 }
+
+#[test]
+fn value_dump_float() {
+    assert_eq!(Value::Float(3.14159265358979).dump(), "3.14159265358979");
+    assert_eq!(Value::Float(1.0).dump(), "1.0");
+    assert_eq!(Value::Float(-0.0).dump(), "-0.0");
+    assert_eq!(Value::Float(1e300).dump(), "1e300");
+    assert_eq!(Value::Float(1.5e-7).dump(), "1.5e-7");
+    assert_eq!(Value::Float(f64::INFINITY).dump(), "inf");
+    assert_eq!(Value::Float(f64::NEG_INFINITY).dump(), "-inf");
+    assert_eq!(Value::Float(f64::NAN).dump(), "nan");
+}
+
+#[test]
+fn value_float_round_trip() {
+    // A xorshift generator covers all the bit patterns without any dependency
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    for _ in 0..100_000 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+
+        let number = f64::from_bits(state);
+        let dumped = Value::Float(number).dump();
+        match Value::parse(&dumped) {
+            Ok(Value::Float(parsed)) if number.is_nan() => assert!(parsed.is_nan(), "{} gave {}", dumped, parsed),
+            Ok(Value::Float(parsed))                    => assert_eq!(parsed.to_bits(), number.to_bits(), "{} gave {}", dumped, parsed),
+            other                                       => panic!("{} gave {:?}", dumped, other),
+        }
+    }
+}
+
+#[test]
+fn value_parse_untyped() {
+    for i in &["42", "3.5", "on", "0xFF", "inf"] {
+        assert_eq!(Value::parse_untyped(i).unwrap(), Value::Raw(String::from(*i)));
+    }
+
+    assert_eq!(Value::parse_untyped("\"a; b\"").unwrap(), Value::Raw(String::from("a; b")));
+    assert_eq!(Value::parse_untyped("a\\=b").unwrap(), Value::Raw(String::from("a=b")));
+    assert!(Value::parse_untyped("'unterminated").is_err());
+    assert!(Value::parse_untyped("'").is_err());
+}
+
+#[test]
+fn value_raw() {
+    let values = [
+        Value::Raw(String::from("a=b")),
+        Value::Str(String::from(" spaced ")),
+        Value::Int(-3),
+        Value::Float(1e300),
+        Value::Bool(false),
+    ];
+
+    for i in &values {
+        assert_eq!(Value::parse_untyped(&i.dump()).unwrap(), Value::Raw(i.raw().into_owned()));
+    }
+
+    let array = Value::Array(vec![Value::Int(1), Value::Bool(true)]);
+    assert_eq!(array.raw(), "1, true");
+}
+
+#[test]
+fn value_color_dump() {
+    let opaque = Value::Color(0x1a, 0x2b, 0x3c, None);
+    let translucent = Value::Color(0xff, 0, 0, Some(0x80));
+
+    assert_eq!(opaque.dump(), "#1a2b3c");
+    assert_eq!(translucent.dump(), "#ff000080");
+    assert_eq!(format!("{}", translucent), "#ff000080");
+    assert_eq!(Value::parse_color(&translucent.dump()), translucent);
+    assert_eq!(Value::parse_color("#12345"), Value::Str(String::from("#12345")));
+    assert_eq!(Value::parse_color("#ggg"), Value::Str(String::from("#ggg")));
+}
+
+#[test]
+fn value_from_primitives() {
+    assert_eq!(Value::from(-3_i64), Value::Int(-3));
+    assert_eq!(Value::from(8080_u16), Value::Int(8080));
+    assert_eq!(Value::from(u32::MAX), Value::Int(4294967295));
+    assert_eq!(Value::from(0.5_f32), Value::Float(0.5));
+    assert_eq!(Value::from(true), Value::Bool(true));
+    assert_eq!(Value::from(String::from("a")), Value::Str(String::from("a")));
+
+    assert_eq!(i64::try_from(Value::Int(-3)), Ok(-3));
+    assert_eq!(i8::try_from(Value::Int(-129)), Err(Value::Int(-129)));
+    assert_eq!(u32::try_from(Value::Int(-1)), Err(Value::Int(-1)));
+    assert_eq!(f64::try_from(Value::Float(0.5)), Ok(0.5));
+    assert_eq!(f64::try_from(Value::Int(1)), Err(Value::Int(1)));
+    assert_eq!(bool::try_from(Value::Bool(false)), Ok(false));
+    assert_eq!(String::try_from(Value::Raw(String::from("a"))), Ok(String::from("a")));
+    assert_eq!(String::try_from(Value::Int(1)), Err(Value::Int(1)));
+}
+
+#[test]
+fn value_type_name() {
+    let values = [
+        (Value::Raw(String::new()), "raw"),
+        (Value::Str(String::new()), "str"),
+        (Value::Int(1), "int"),
+        (Value::Float(1.0), "float"),
+        (Value::Bool(true), "bool"),
+        (Value::Array(Vec::new()), "array"),
+        (Value::Color(0, 0, 0, None), "color"),
+    ];
+
+    for (value, name) in &values {
+        assert_eq!(value.type_name(), *name);
+    }
+}
+
+#[test]
+fn identifier_try_new() {
+    let ident = Identifier::try_new(Some("section"), String::from("key")).unwrap();
+    assert_eq!(ident, Identifier::new(Some(String::from("section")), String::from("key")));
+    assert_eq!(Identifier::try_new(None::<String>, "key").unwrap(), Identifier::new_unchecked(None, String::from("key")));
+
+    match Identifier::try_new(Some("1section"), "key") {
+        Err(Error::InvalidIdentifier(err)) => assert_eq!(format!("{}", err), "Invalid identifier 1section in 1section"),
+        other                              => panic!("Expected an InvalidIdentifier error, got {:?}", other),
+    }
+    assert!(Identifier::try_new(Some("section"), "key;").is_err());
+}
+
+#[test]
+fn value_hash_matches_equality() {
+    use std::collections::{hash_map::DefaultHasher, HashMap, HashSet};
+    use std::hash::{Hash, Hasher};
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    assert_eq!(Value::Float(0.0), Value::Float(-0.0));
+    assert_eq!(hash_of(&Value::Float(0.0)), hash_of(&Value::Float(-0.0)));
+    assert_eq!(hash_of(&Value::Array(vec![Value::Int(1), Value::Null])), hash_of(&Value::Array(vec![Value::Int(1), Value::Null])));
+    assert_ne!(hash_of(&Value::Raw(String::from("a"))), hash_of(&Value::Str(String::from("a"))));
+
+    let nan = ValueKey(Value::Array(vec![Value::Float(f64::NAN)]));
+    assert_eq!(nan, nan.clone());
+    assert_eq!(ValueKey(Value::Float(0.0)), ValueKey::from(Value::Float(-0.0)));
+    assert_ne!(ValueKey(Value::Int(1)), ValueKey(Value::Float(1.0)));
+
+    let values: HashSet<_> = vec![nan.clone(), nan, ValueKey(Value::Color(1, 2, 3, None))].into_iter().collect();
+    assert_eq!(values.len(), 2);
+
+    let mut keys = HashMap::new();
+    keys.insert(Identifier::new(None, String::from("a")), 1);
+    assert_eq!(keys[&Identifier::new(None, String::from("a"))], 1);
+}
+
+#[test]
+fn value_partial_cmp_numeric() {
+    use std::cmp::Ordering;
+
+    // Beyond 2^53, two `Int`s differing by 1 are the same `f64`
+    let big = Value::Int(i64::MAX);
+    assert_eq!(big.partial_cmp_numeric(&Value::Int(i64::MAX - 1), false), Some(Ordering::Greater));
+    assert_eq!(Value::Float(1.5).partial_cmp_numeric(&Value::Int(2), false), Some(Ordering::Less));
+    assert_eq!(Value::Float(f64::NAN).partial_cmp_numeric(&Value::Int(2), false), None);
+    assert_eq!(Value::Str(String::from("3")).partial_cmp_numeric(&Value::Int(2), true), None);
+    assert_eq!(Value::Bool(false).partial_cmp_numeric(&Value::Float(0.5), true), Some(Ordering::Less));
+}
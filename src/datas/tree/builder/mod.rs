@@ -0,0 +1,87 @@
+//! Contains the definition of [`TreeBuilder`](struct.TreeBuilder.html "datas::tree::TreeBuilder")
+
+use crate::prelude::*;
+use crate::datas::{Identifier, Value};
+use crate::errors::{Error, error_kinds::InvalidIdentifier};
+use super::Tree;
+
+/// A builder of a [`Tree`](struct.Tree.html "datas::tree::Tree") whose keys are set one after the other
+/// 
+/// The keys set before any call to [`section`](struct.TreeBuilder.html#method.section "datas::tree::TreeBuilder::section") are in the global section. The following ones are in the last section declared
+/// 
+/// # Example
+/// ```
+/// use mininip::datas::{tree::TreeBuilder, Value};
+/// use mininip::errors::Error;
+/// 
+/// # fn main() -> Result<(), Error> {
+/// let tree = TreeBuilder::new().set("name", Value::Str(String::from("service")))?
+///                              .section("db")?
+///                              .set("host", Value::Str(String::from("localhost")))?
+///                              .set("port", Value::Int(5432))?
+///                              .build();
+/// 
+/// assert_eq!(tree.get_str(None, "name").unwrap(), Some("service"));
+/// assert_eq!(tree.get_int(Some("db"), "port").unwrap(), Some(5432));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TreeBuilder {
+    tree: Tree,
+    /// The section of the keys set, `None` for the global section
+    section: Option<String>,
+}
+
+impl TreeBuilder {
+    /// Creates a new `TreeBuilder` building an empty `Tree`
+    pub fn new() -> TreeBuilder {
+        TreeBuilder::default()
+    }
+
+    /// Declares the section `name`. The keys set afterwards are in this section
+    /// 
+    /// A section without any key is not in the `Tree` built
+    /// 
+    /// # Return value
+    /// `Ok(self)` in case of success
+    /// 
+    /// `Err(error)` with `error` as an [`InvalidIdentifier`](../../errors/error_kinds/struct.InvalidIdentifier.html "errors::error_kinds::InvalidIdentifier") error if `name` is not a valid identifier
+    pub fn section(mut self, name: &str) -> Result<TreeBuilder, Error> {
+        check_identifier(name)?;
+
+        self.section = Some(String::from(name));
+        Ok(self)
+    }
+
+    /// Sets `value` as the value of the key `key` in the current section, replacing its previous value if any
+    /// 
+    /// # Return value
+    /// `Ok(self)` in case of success
+    /// 
+    /// `Err(error)` with `error` as an [`InvalidIdentifier`](../../errors/error_kinds/struct.InvalidIdentifier.html "errors::error_kinds::InvalidIdentifier") error if `key` is not a valid identifier
+    pub fn set(mut self, key: &str, value: Value) -> Result<TreeBuilder, Error> {
+        check_identifier(key)?;
+
+        self.tree.insert(Identifier::new(self.section.clone(), String::from(key)), value);
+        Ok(self)
+    }
+
+    /// Consumes `self` and returns the `Tree` built, keeping the order in which the sections and keys were set
+    pub fn build(self) -> Tree {
+        self.tree
+    }
+}
+
+/// Returns an `InvalidIdentifier` error if `name` is not a valid identifier
+fn check_identifier(name: &str) -> Result<(), Error> {
+    if Identifier::is_valid(name) {
+        Ok(())
+    } else {
+        Err(Error::from(InvalidIdentifier::new(String::from(name), String::from(name))))
+    }
+}
+
+
+#[cfg(test)]
+mod tests;
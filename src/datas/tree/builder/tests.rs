@@ -0,0 +1,41 @@
+use crate::datas::{tree::*, Value};
+use crate::errors::Error;
+
+#[test]
+fn tree_builder_sections() {
+    let tree = TreeBuilder::new().set("global", Value::Int(0)).unwrap()
+                                 .section("a").unwrap()
+                                 .set("x", Value::Int(1)).unwrap()
+                                 .section("b").unwrap()
+                                 .set("y", Value::Int(2)).unwrap()
+                                 .set("y", Value::Int(3)).unwrap()
+                                 .build();
+
+    let names: Vec<_> = tree.iter().map(|(section, key, value)| (section, key.name(), value)).collect();
+    assert_eq!(names, vec![
+        (None,      "global", &Value::Int(0)),
+        (Some("a"), "x",      &Value::Int(1)),
+        (Some("b"), "y",      &Value::Int(3)),
+    ]);
+}
+
+#[test]
+fn tree_builder_empty_section() {
+    let tree = TreeBuilder::new().section("empty").unwrap()
+                                 .build();
+
+    assert!(tree.section(Some("empty")).is_none());
+}
+
+#[test]
+fn tree_builder_invalid_identifiers() {
+    match TreeBuilder::new().section("bad section!") {
+        Err(Error::InvalidIdentifier(err)) => assert_eq!(format!("{}", err), "Invalid identifier bad section! in bad section!"),
+        other                              => panic!("Wrong return value: {:?}", other),
+    }
+
+    match TreeBuilder::new().set("bad key!", Value::Int(0)) {
+        Err(Error::InvalidIdentifier(_)) => {},
+        other                            => panic!("Wrong return value: {:?}", other),
+    }
+}
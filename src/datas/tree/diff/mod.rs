@@ -0,0 +1,233 @@
+//! Contains the definition of [`TreeDiff`](struct.TreeDiff.html "datas::tree::TreeDiff")
+
+use crate::prelude::*;
+use crate::datas::{Value, same_values};
+use super::{Tree, Section};
+use core::fmt::{self, Display};
+
+/// A difference between two `Tree`s, as listed by a [`TreeDiff`](struct.TreeDiff.html "datas::tree::TreeDiff")
+/// 
+/// The global section is never added nor removed, only its keys are
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// A section of the new `Tree` only. Its keys follow as `KeyAdded`
+    SectionAdded(String),
+    /// A section of the old `Tree` only. Its keys follow as `KeyRemoved`
+    SectionRemoved(String),
+    /// A key of the new `Tree` only with its value
+    KeyAdded {
+        section: Option<String>,
+        key: String,
+        value: Value,
+    },
+    /// A key of the old `Tree` only with its value
+    KeyRemoved {
+        section: Option<String>,
+        key: String,
+        value: Value,
+    },
+    /// A key of both `Tree`s whose value is different
+    ValueChanged {
+        section: Option<String>,
+        key: String,
+        old: Value,
+        new: Value,
+    },
+}
+
+/// Writes `[section] ` before a key if `section` is not the global section
+fn write_section(f: &mut fmt::Formatter, section: &Option<String>) -> fmt::Result {
+    match section {
+        Some(section) => write!(f, "[{}] ", section),
+        None          => Ok(()),
+    }
+}
+
+/// A change is displayed on a single line starting with `+` for an addition, `-` for a removal and `~` for a change, such as `~ [server] port = 80 -> 8080`. The values are written as dumped
+impl Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::SectionAdded(section)                   => write!(f, "+ [{}]", section),
+            Change::SectionRemoved(section)                 => write!(f, "- [{}]", section),
+            Change::KeyAdded { section, key, value }        => {
+                f.write_str("+ ")?;
+                write_section(f, section)?;
+                write!(f, "{} = {}", key, value.dump())
+            },
+            Change::KeyRemoved { section, key, value }      => {
+                f.write_str("- ")?;
+                write_section(f, section)?;
+                write!(f, "{} = {}", key, value.dump())
+            },
+            Change::ValueChanged { section, key, old, new } => {
+                f.write_str("~ ")?;
+                write_section(f, section)?;
+                write!(f, "{} = {} -> {}", key, old.dump(), new.dump())
+            },
+        }
+    }
+}
+
+
+/// The changes turning a `Tree` into another one, as returned by [`Tree::diff`](struct.Tree.html#method.diff "datas::tree::Tree::diff")
+/// 
+/// The changes follow the order of the sections and the keys of the old `Tree`, each section being followed by the keys added to it, and end with the sections added. A `TreeDiff` is displayed as one change per line (see [`Change`](enum.Change.html "datas::tree::Change"))
+/// 
+/// # Example
+/// ```
+/// use mininip::datas::tree::Change;
+/// use mininip::datas::Value;
+/// use mininip::parse::parse_content;
+/// 
+/// let old = parse_content("[server]\nport = 80\nhost = localhost\n").unwrap();
+/// let new = parse_content("[server]\nport = 8080\n[client]\nretries = 3\n").unwrap();
+/// 
+/// let diff = old.diff(&new);
+/// assert_eq!(diff.changes()[0], Change::ValueChanged {
+///     section: Some(String::from("server")),
+///     key: String::from("port"),
+///     old: Value::Int(80),
+///     new: Value::Int(8080),
+/// });
+/// assert_eq!(format!("{}", diff), "\
+/// ~ [server] port = 80 -> 8080\n\
+/// - [server] host = localhost\n\
+/// + [client]\n\
+/// + [client] retries = 3\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TreeDiff {
+    changes: Vec<Change>,
+}
+
+impl TreeDiff {
+    /// Returns the changes, in their order
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Consumes `self` and returns the changes, in their order
+    pub fn into_changes(self) -> Vec<Change> {
+        self.changes
+    }
+
+    /// Returns an iterator over the changes, in their order
+    pub fn iter(&self) -> core::slice::Iter<'_, Change> {
+        self.changes.iter()
+    }
+
+    /// Returns the number of changes
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns `true` if both `Tree`s hold the same keys with the same values
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Pushes the keys of `section` as `KeyRemoved` if `removed` is `true` or as `KeyAdded` otherwise
+    fn push_keys(&mut self, section: &Section, removed: bool) {
+        for (ident, value) in section.iter() {
+            let section = ident.section().map(String::from);
+            let key = String::from(ident.name());
+            let value = value.clone();
+
+            self.changes.push(if removed {
+                Change::KeyRemoved { section, key, value }
+            } else {
+                Change::KeyAdded { section, key, value }
+            });
+        }
+    }
+}
+
+impl Display for TreeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in &self.changes {
+            writeln!(f, "{}", i)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl IntoIterator for TreeDiff {
+    type Item = Change;
+    type IntoIter = alloc::vec::IntoIter<Change>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.changes.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a TreeDiff {
+    type Item = &'a Change;
+    type IntoIter = core::slice::Iter<'a, Change>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.changes.iter()
+    }
+}
+
+
+impl Tree {
+    /// Lists the changes turning `self` into `other`: the sections and keys added or removed and the values changed
+    /// 
+    /// Two values are the same if they are equal, two `NaN` floats with the same bit pattern being the same too. The names are matched like with [`get_value`](struct.Tree.html#method.get_value "datas::tree::Tree::get_value"), regardless of their case if `other` is case-insensitive. The comments and the order of the keys are not compared
+    pub fn diff(&self, other: &Tree) -> TreeDiff {
+        let mut diff = TreeDiff::default();
+
+        for section in self.sections() {
+            let name = section.name();
+            let new = match other.section(name) {
+                Some(new) => new,
+                None      => {
+                    if let Some(name) = name {
+                        diff.changes.push(Change::SectionRemoved(String::from(name)));
+                    }
+                    diff.push_keys(&section, true);
+                    continue;
+                },
+            };
+
+            for (ident, value) in section.iter() {
+                let section = ident.section().map(String::from);
+                let key = String::from(ident.name());
+
+                match new.get(ident.name()) {
+                    Some(other) if same_values(value, other) => {},
+                    Some(other)                              => diff.changes.push(Change::ValueChanged { section, key, old: value.clone(), new: other.clone() }),
+                    None                                     => diff.changes.push(Change::KeyRemoved { section, key, value: value.clone() }),
+                }
+            }
+
+            for (ident, value) in new.iter() {
+                if !self.contains_key(name, ident.name()) {
+                    diff.changes.push(Change::KeyAdded {
+                        section: ident.section().map(String::from),
+                        key: String::from(ident.name()),
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+
+        for section in other.sections() {
+            if self.contains_section(section.name()) {
+                continue;
+            }
+
+            if let Some(name) = section.name() {
+                diff.changes.push(Change::SectionAdded(String::from(name)));
+            }
+            diff.push_keys(&section, false);
+        }
+
+        diff
+    }
+}
+
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,42 @@
+use crate::datas::{tree::*, Value};
+use crate::parse::parse_content;
+
+#[test]
+fn tree_diff_lists_changes() {
+    let old = parse_content("\
+    name = service\n\
+    [server]\n\
+    port = 80\n\
+    ratio = nan\n\
+    [legacy]\n\
+    enabled = off\n").unwrap();
+    let new = parse_content("\
+    name = service\n\
+    version = 2\n\
+    [server]\n\
+    port = 8080\n\
+    ratio = nan\n").unwrap();
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.into_changes(), vec![
+        Change::KeyAdded {
+            section: None,
+            key: String::from("version"),
+            value: Value::Int(2),
+        },
+        Change::ValueChanged {
+            section: Some(String::from("server")),
+            key: String::from("port"),
+            old: Value::Int(80),
+            new: Value::Int(8080),
+        },
+        Change::SectionRemoved(String::from("legacy")),
+        Change::KeyRemoved {
+            section: Some(String::from("legacy")),
+            key: String::from("enabled"),
+            value: Value::Bool(false),
+        },
+    ]);
+
+    assert!(new.diff(&new).is_empty());
+}
@@ -0,0 +1,203 @@
+//! Contains the definition of [`FlatTree`](struct.FlatTree.html "datas::tree::FlatTree")
+
+use crate::prelude::*;
+use crate::datas::{Identifier, Value};
+use crate::errors::{Error, error_kinds::InvalidIdentifier};
+use super::Tree;
+
+/// The separator used by [`Tree::flatten`](struct.Tree.html#method.flatten "datas::tree::Tree::flatten")
+pub const DEFAULT_FLAT_SEPARATOR: char = '.';
+
+/// A `Tree` flattened into a single level of keys, as returned by [`Tree::flatten`](struct.Tree.html#method.flatten "datas::tree::Tree::flatten")
+/// 
+/// Each key of the `Tree` is named by the path of its section followed by its own name, joined by a separator. The keys of the global section keep their name. A `FlatTree` keeps the order of the keys in the `Tree`
+/// 
+/// # Example
+/// ```
+/// use mininip::datas::{tree::Tree, Identifier, Value};
+/// 
+/// let mut tree = Tree::new();
+/// tree.insert(Identifier::new(None, String::from("name")), Value::Raw(String::from("service")));
+/// tree.insert(Identifier::new(Some(String::from("db")), String::from("port")), Value::Int(5432));
+/// 
+/// let flat = tree.flatten();
+/// assert_eq!(flat.get("name"), Some(&Value::Raw(String::from("service"))));
+/// assert_eq!(flat.get("db.port"), Some(&Value::Int(5432)));
+/// 
+/// assert_eq!(flat.unflatten().unwrap(), tree);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FlatTree {
+    separator: char,
+    /// The flat keys and their values, in their order in the `Tree`
+    entries: Vec<(String, Value)>,
+    /// The index of each flat key in `entries`
+    index: HashMap<String, usize>,
+}
+
+impl FlatTree {
+    /// Creates an empty `FlatTree` whose keys are joined by `separator`
+    pub fn new(separator: char) -> FlatTree {
+        FlatTree {
+            separator,
+            entries: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Returns the separator joining the names of the sections and the keys
+    pub fn separator(&self) -> char {
+        self.separator
+    }
+
+    /// Returns the value of the flat key `key`
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.index.get(key)
+                  .map(|&n| &self.entries[n].1)
+    }
+
+    /// Sets the value of the flat key `key` to `value`. A new key is put after the existing ones
+    /// 
+    /// # Return value
+    /// `Some(old)` with `old` as the value `key` had before
+    /// 
+    /// `None` if `key` was not in `self`
+    pub fn insert(&mut self, key: String, value: Value) -> Option<Value> {
+        match self.index.get(&key) {
+            Some(&n) => Some(core::mem::replace(&mut self.entries[n].1, value)),
+            None     => {
+                self.index.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+                None
+            },
+        }
+    }
+
+    /// Returns the number of keys in `self`
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if `self` contains no key
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the flat keys and their values, in their order in the `Tree`
+    pub fn entries(&self) -> &[(String, Value)] {
+        &self.entries
+    }
+
+    /// Consumes `self` and returns the flat keys and their values, in their order in the `Tree`
+    pub fn into_entries(self) -> Vec<(String, Value)> {
+        self.entries
+    }
+
+    /// Re-nests the keys of `self` into a `Tree`
+    /// 
+    /// A key is split at each separator. The last part is the name of the key and the previous ones are the path of its section: a key without separator is in the global section, a key with a single one is in a plain section and a key with several ones is in a nested section (see [`Tree::get_subsection`](struct.Tree.html#method.get_subsection "datas::tree::Tree::get_subsection"))
+    /// 
+    /// # Return value
+    /// `Ok(tree)` with `tree` as the `Tree` whose flattening is `self`
+    /// 
+    /// `Err(error)` with `error` as an [`InvalidIdentifier`](../../errors/error_kinds/struct.InvalidIdentifier.html "errors::error_kinds::InvalidIdentifier") if a part of a key is not a valid identifier
+    /// 
+    /// # Note
+    /// The round trip is lossless as long as no section or key name of the `Tree` flattened contains the separator
+    pub fn unflatten(&self) -> Result<Tree, Error> {
+        let mut tree = Tree::new();
+        let mut paths = Vec::new();
+
+        for (key, value) in &self.entries {
+            let mut parts: Vec<String> = key.split(self.separator)
+                                            .map(String::from)
+                                            .collect();
+            if let Some(part) = parts.iter().find(|i| !Identifier::is_valid(i)) {
+                return Err(Error::from(InvalidIdentifier::new(key.clone(), part.clone())));
+            }
+
+            let name = parts.pop().expect("`split` yields at least one part");
+            let section = match parts.len() {
+                0 => None,
+                1 => parts.pop(),
+                _ => {
+                    let names: Vec<String> = parts.iter()
+                                                  .map(|i| i.replace('.', "\\."))
+                                                  .collect();
+                    let section = names.join(".");
+                    paths.push((section.clone(), parts));
+                    Some(section)
+                },
+            };
+
+            tree.insert(Identifier::new_unchecked(section, name), value.clone());
+        }
+
+        for (section, path) in paths {
+            tree.set_section_path(section, path);
+        }
+
+        Ok(tree)
+    }
+}
+
+impl PartialEq for FlatTree {
+    fn eq(&self, other: &FlatTree) -> bool {
+        self.separator == other.separator && self.entries == other.entries
+    }
+}
+
+
+impl Tree {
+    /// Flattens `self` into a single level of keys named `section.key`, `section.subsection.key` for nested sections or `key` for the global section
+    /// 
+    /// # See
+    /// [`flatten_with`](struct.Tree.html#method.flatten_with "datas::tree::Tree::flatten_with") to use another separator than a dot
+    /// 
+    /// [`FlatTree::unflatten`](struct.FlatTree.html#method.unflatten "datas::tree::FlatTree::unflatten") to re-nest the keys
+    pub fn flatten(&self) -> FlatTree {
+        self.flatten_with(DEFAULT_FLAT_SEPARATOR)
+    }
+
+    /// Flattens `self` like [`flatten`](struct.Tree.html#method.flatten "datas::tree::Tree::flatten") but joins the names with `separator`
+    /// 
+    /// A separator which is not a valid character of an identifier avoids clashing with names containing dots
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{tree::Tree, Identifier, Value};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert(Identifier::new(Some(String::from("example.com")), String::from("port")), Value::Int(443));
+    /// 
+    /// let flat = tree.flatten_with('/');
+    /// assert_eq!(flat.get("example.com/port"), Some(&Value::Int(443)));
+    /// assert_eq!(flat.unflatten().unwrap(), tree);
+    /// ```
+    pub fn flatten_with(&self, separator: char) -> FlatTree {
+        let mut flat = FlatTree::new(separator);
+        let mut buf = [0; 4];
+        let glue: &str = separator.encode_utf8(&mut buf);
+
+        for section in self.sections() {
+            let prefix = section.name().map(|name| match self.nested_path(name) {
+                Some(path) => path.join(glue),
+                None       => String::from(name),
+            });
+
+            for (ident, value) in section.iter() {
+                let key = match &prefix {
+                    Some(prefix) => format!("{}{}{}", prefix, separator, ident.name()),
+                    None         => String::from(ident.name()),
+                };
+                flat.insert(key, value.clone());
+            }
+        }
+
+        flat
+    }
+}
+
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,42 @@
+use crate::datas::{tree::*, Value};
+use crate::parse::{parse_content_with_options, ParserOptions};
+
+#[test]
+fn tree_flatten_round_trip() {
+    let options = ParserOptions {
+        nested_sections: true,
+        ..ParserOptions::default()
+    };
+    let tree = parse_content_with_options("\
+    name = service\n\
+    [server]\n\
+    port = 8080\n\
+    [server.tls]\n\
+    cert = a.pem\n", options).unwrap();
+
+    let flat = tree.flatten();
+    let keys: Vec<_> = flat.entries().iter().map(|(key, _)| key.as_str()).collect();
+    assert_eq!(keys, vec!["name", "server.port", "server.tls.cert"]);
+    assert_eq!(flat.get("server.tls.cert"), Some(&Value::Raw(String::from("a.pem"))));
+
+    let nested = flat.unflatten().unwrap();
+    assert_eq!(nested, tree);
+    assert_eq!(nested.get_subsection(&["server", "tls"]).unwrap().get("cert"), Some(&Value::Raw(String::from("a.pem"))));
+    assert_eq!(nested.flatten(), flat);
+}
+
+#[test]
+fn tree_flatten_custom_separator() {
+    let tree = parse_content_with_options("\
+    [example.com]\n\
+    port = 443\n", ParserOptions::default()).unwrap();
+
+    let flat = tree.flatten_with('/');
+    assert_eq!(flat.separator(), '/');
+    assert_eq!(flat.get("example.com/port"), Some(&Value::Int(443)));
+    assert_eq!(flat.unflatten().unwrap(), tree);
+
+    let mut invalid = FlatTree::new('/');
+    invalid.insert(String::from("section//key"), Value::Int(0));
+    assert!(invalid.unflatten().is_err());
+}
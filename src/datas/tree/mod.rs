@@ -4,49 +4,1368 @@
 //! `Tree` to convert a `HashMap<Identifier, Value>` into a more user-friendly data-type
 //! 
 //! `Section` to list the keys inside a section
+//! 
+//! `SectionMut` to update the keys of a section
+//! 
+//! `Iter` to walk through all the keys of a `Tree` with their values
+//! 
+//! `Entry` to insert or update a value in a single lookup
+//! 
+//! `MergePolicy` to choose how to merge two `Tree`s
+//! 
+//! `Quote` to know how a string was quoted in the parsed file
+//! 
+//! `SectionData` to read the keys of a removed section
+//! 
+//! `TreeBuilder` to build a `Tree` key by key
+//! 
+//! `FlatTree` to read the keys of a `Tree` as a single level of dotted keys
+//! 
+//! `TreeDiff` to list the changes between two `Tree`s
+//! 
+//! `Schema` to check the keys and the types of a `Tree`
 
+use crate::prelude::*;
 use crate::datas::{Identifier, Value};
-use std::collections::{HashMap, hash_map};
+use crate::errors::{Error, TypeError, error_kinds::{DuplicateKey, DuplicateSection, InvalidIdentifier}};
+use alloc::borrow::Cow;
+use crate::collections::hash_map;
+use core::fmt;
+use core::iter::FromIterator;
 
 /// A more user-friendly data-type to represent the data returned by `parser::Parser::data`
 /// 
 /// # Example
 /// ```
-/// use mininip::datas{Identifier, Value, self};
-/// use datas::tree::Tree;
+/// use mininip::datas::tree::Tree;
 /// use mininip::parse::parse_file;
 /// 
-/// let tree = Tree::from_data(parse_file("good.ini").unwrap());
+/// let tree = Tree::from(parse_file("good.ini").unwrap());
 /// for i in tree.sections() {
-///     println!("[{}] ; Section {}", i, i);
+///     if let Some(name) = i.name() {
+///         println!("[{}] ; Section {}", name, name);
+///     }
 ///     for j in i.keys() {
-///         println!("{}={} ; key {}", j.ident().name(), j.value(), j.ident().name());
+///         let value = tree.get_value(j.section(), j.name()).unwrap();
+///         println!("{}={} ; key {}", j.name(), value, j.name());
 ///     }
 /// }
 /// ```
+/// 
+/// # Order
+/// A `Tree` built by a [`Parser`](../../parse/struct.Parser.html "parse::Parser") or with [`Tree::new`](struct.Tree.html#method.new "datas::tree::Tree::new") iterates its sections and keys in their order of declaration, which is kept by [`dump_tree`](../../dump/fn.dump_tree.html "dump::dump_tree"). A `Tree` built from a `HashMap` has no such order so it is sorted alphabetically
+/// 
+/// # Case sensitivity
+/// A `Tree` built with [`Tree::with_case_insensitive`](struct.Tree.html#method.with_case_insensitive "datas::tree::Tree::with_case_insensitive") matches the section and key names regardless of their case while keeping their original spelling
+/// 
+/// # Equality
+/// Two `Tree`s are equal if they hold the same keys with the same values, whatever their order, their comments, their formats or their case sensitivity. Since a [`Value::Float`](../enum.Value.html "datas::Value") may be `NaN`, `Tree` does not implement `Eq`
+/// 
+/// # Debug
+/// A `Tree` is debugged as a map of its sections, in their order, each being a map of its keys to their values. The comments and the formats retained are not printed
+/// 
+/// ```
+/// use mininip::datas::{tree::Tree, Identifier, Value};
+/// 
+/// let mut tree = Tree::new();
+/// tree.insert(Identifier::new(None, String::from("name")), Value::Raw(String::from("service")));
+/// tree.insert(Identifier::new(Some(String::from("server")), String::from("port")), Value::Int(80));
+/// 
+/// assert_eq!(format!("{:?}", tree), r#"{None: {"name": Raw("service")}, Some("server"): {"port": Int(80)}}"#);
+/// ```
+#[derive(Clone)]
 pub struct Tree {
     cache: Cache,
     data: HashMap<Identifier, Value>,
+    case_insensitive: bool,
+    comments: Comments,
+    /// The text of the values as written in the parsed file, if retained
+    formats: HashMap<Identifier, String>,
+    /// The quotes around the quoted strings in the parsed file
+    quotes: HashMap<Identifier, Quote>,
+    /// The path of the sections declared as nested sections, from the outermost one
+    paths: HashMap<String, Vec<String>>,
+    /// The parent of the sections declared as `[child : parent]`
+    parents: HashMap<String, String>,
+    /// Set to `true` if the blank lines are retained among the comments, as empty lines
+    blank_lines: bool,
+}
+
+impl Tree {
+    /// Creates an empty `Tree`
+    /// 
+    /// The keys inserted with [`insert`](struct.Tree.html#method.insert "datas::tree::Tree::insert") are iterated in their insertion order
+    pub fn new() -> Tree {
+        Tree {
+            cache: Cache::new(),
+            data: HashMap::new(),
+            case_insensitive: false,
+            comments: Comments::new(),
+            formats: HashMap::new(),
+            quotes: HashMap::new(),
+            paths: HashMap::new(),
+            parents: HashMap::new(),
+            blank_lines: false,
+        }
+    }
+
+    /// Builds a case-insensitive `Tree` from `data`
+    /// 
+    /// The lookup methods such as [`get_value`](struct.Tree.html#method.get_value "datas::tree::Tree::get_value") and [`section`](struct.Tree.html#method.section "datas::tree::Tree::section") will then match the names regardless of their case. Two sections differing only by their case are merged under the spelling coming first in alphabetical order
+    /// 
+    /// # Return value
+    /// `Ok(tree)` with `tree` as the new object
+    /// 
+    /// `Err(error)` with `error` as a [`DuplicateKey`](../../errors/error_kinds/struct.DuplicateKey.html "errors::error_kinds::DuplicateKey") error when two keys of the same section differ only by their case
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{Identifier, Value};
+    /// use mininip::datas::tree::Tree;
+    /// use std::collections::HashMap;
+    /// 
+    /// let mut data = HashMap::new();
+    /// let ident = Identifier::new(Some(String::from("Settings")), String::from("Theme"));
+    /// data.insert(ident, Value::Str(String::from("dark")));
+    /// 
+    /// let tree = Tree::with_case_insensitive(data).unwrap();
+    /// assert_eq!(tree.get_value(Some("settings"), "THEME"), Some(&Value::Str(String::from("dark"))));
+    /// assert_eq!(tree.section(Some("SETTINGS")).unwrap().name(), Some("Settings"));
+    /// ```
+    pub fn with_case_insensitive(data: HashMap<Identifier, Value>) -> Result<Tree, Error> {
+        // Sorting first ensures the spelling kept for a section does not depend on the hash map ordering
+        let mut entries = data.into_iter().collect::<Vec<_>>();
+        entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut tree = Tree {
+            cache: Cache::new(),
+            data: HashMap::new(),
+            case_insensitive: true,
+            comments: Comments::new(),
+            formats: HashMap::new(),
+            quotes: HashMap::new(),
+            paths: HashMap::new(),
+            parents: HashMap::new(),
+            blank_lines: false,
+        };
+
+        for (mut ident, value) in entries {
+            if let Some(Some(section)) = tree.cache.find_section(ident.section(), true) {
+                let section = String::from(section);
+                ident.change_section(Some(section));
+            }
+
+            if let Some(first) = tree.cache.find_key(ident.section(), ident.name(), true) {
+                let section = ident.section().map(String::from);
+                let err = DuplicateKey::new(section, String::from(first), String::from(ident.name()));
+                return Err(Error::from(err));
+            }
+
+            tree.cache.push(&ident);
+            tree.data.insert(ident, value);
+        }
+        tree.cache.sort();
+
+        Ok(tree)
+    }
+
+    /// Returns `true` if the section `section` (`None` for the global section) is in `self`, `false` otherwise. Since a section exists only through its keys, an empty section is never in `self`
+    pub fn contains_section(&self, section: Option<&str>) -> bool {
+        self.cache.find_section(section, self.case_insensitive).is_some()
+    }
+
+    /// Returns `true` if the key `key` of the section `section` (`None` for the global section) is in `self`, `false` otherwise
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{tree::Tree, Identifier, Value};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert(Identifier::new(Some(String::from("server")), String::from("port")), Value::Int(80));
+    /// 
+    /// assert!(tree.contains_section(Some("server")));
+    /// assert!(tree.contains_key(Some("server"), "port"));
+    /// assert!(!tree.contains_key(Some("server"), "host"));
+    /// assert!(!tree.contains_key(Some("client"), "port"));
+    /// ```
+    pub fn contains_key(&self, section: Option<&str>, key: &str) -> bool {
+        // Looking for the section by hand avoids building an `Option<String>` to index the cache
+        let keys = self.cache.keys.iter()
+                                  .find(|(name, _)| match (name, section) {
+                                      (None, None)       => true,
+                                      (Some(a), Some(b)) => names_match(a, b, self.case_insensitive),
+                                      _                  => false,
+                                  });
+
+        match keys {
+            Some((_, keys)) => keys.iter().any(|i| names_match(i, key, self.case_insensitive)),
+            None            => false,
+        }
+    }
+
+    /// Returns the value of the key `key` in the section `section` (`None` for the global section) or `None` if there is not any
+    pub fn get_value(&self, section: Option<&str>, key: &str) -> Option<&Value> {
+        let ident = self.resolve(section, key)?;
+        self.data.get(&ident)
+    }
+
+    /// Returns the value of the key named by `path`, written `section.key`, or `None` if there is not any
+    /// 
+    /// `path` is split at its last dot: the text before it is the section and the text after it is the key. A path without any dot or with an empty section, such as `.name`, is a key of the global section. Since a nested section is named by its path joined with dots, `server.tls.cert` is the key `cert` of the section `tls` nested in `server`
+    /// 
+    /// # Note
+    /// A key whose name contains a dot can not be reached this way, since its last dot is read as the end of the section: `server.a.b` is the key `b` of the section `server.a` and never the key `a.b` of the section `server`. Use [`get_value`](struct.Tree.html#method.get_value "datas::tree::Tree::get_value") instead
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::parse::parse_content;
+    /// use mininip::datas::Value;
+    /// 
+    /// let tree = parse_content("name = service\n[server]\nport = 8080\n").unwrap();
+    /// assert_eq!(tree.get_path("server.port"), Some(&Value::Int(8080)));
+    /// assert_eq!(tree.get_path("name"), Some(&Value::Raw(String::from("service"))));
+    /// assert_eq!(tree.get_path(".name"), Some(&Value::Raw(String::from("service"))));
+    /// assert_eq!(tree.get_path("server.host"), None);
+    /// ```
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        match path.rfind('.') {
+            Some(0)   => self.get_value(None, &path[1..]),
+            Some(dot) => self.get_value(Some(&path[..dot]), &path[dot + 1..]),
+            None      => self.get_value(None, path),
+        }
+    }
+
+    /// Returns the value of the key `key` in the section `section` as an integer
+    /// 
+    /// # Return value
+    /// `Ok(Some(value))` with `value` as the value of the key
+    /// 
+    /// `Ok(None)` if there is not any key `key` in `section`
+    /// 
+    /// `Err(error)` with `error` as a [`TypeError`](../../errors/struct.TypeError.html "errors::TypeError") if the value is not a `Value::Int`
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::parse::Parser;
+    /// 
+    /// let mut parser = Parser::new();
+    /// parser.parse_line("port = 8080").unwrap();
+    /// parser.parse_line("host = localhost").unwrap();
+    /// let tree = parser.into_tree();
+    /// 
+    /// assert_eq!(tree.get_int(None, "port").unwrap(), Some(8080));
+    /// assert_eq!(tree.get_int(None, "timeout").unwrap(), None);
+    /// assert!(tree.get_int(None, "host").is_err());
+    /// ```
+    pub fn get_int(&self, section: Option<&str>, key: &str) -> Result<Option<i64>, TypeError> {
+        match self.get_value(section, key) {
+            Some(Value::Int(value)) => Ok(Some(*value)),
+            Some(value)             => Err(type_error(section, key, "int", value)),
+            None                    => Ok(None),
+        }
+    }
+
+    /// Returns the value of the key `key` in the section `section` as a floating-point number
+    /// 
+    /// # Return value
+    /// `Ok(Some(value))` with `value` as the value of the key
+    /// 
+    /// `Ok(None)` if there is not any key `key` in `section`
+    /// 
+    /// `Err(error)` with `error` as a [`TypeError`](../../errors/struct.TypeError.html "errors::TypeError") if the value is not a `Value::Float`. An integer is not converted
+    pub fn get_float(&self, section: Option<&str>, key: &str) -> Result<Option<f64>, TypeError> {
+        match self.get_value(section, key) {
+            Some(Value::Float(value)) => Ok(Some(*value)),
+            Some(value)               => Err(type_error(section, key, "float", value)),
+            None                      => Ok(None),
+        }
+    }
+
+    /// Returns the value of the key `key` in the section `section` as a boolean
+    /// 
+    /// # Return value
+    /// `Ok(Some(value))` with `value` as the value of the key
+    /// 
+    /// `Ok(None)` if there is not any key `key` in `section`
+    /// 
+    /// `Err(error)` with `error` as a [`TypeError`](../../errors/struct.TypeError.html "errors::TypeError") if the value is not a `Value::Bool`
+    pub fn get_bool(&self, section: Option<&str>, key: &str) -> Result<Option<bool>, TypeError> {
+        match self.get_value(section, key) {
+            Some(Value::Bool(value)) => Ok(Some(*value)),
+            Some(value)              => Err(type_error(section, key, "bool", value)),
+            None                     => Ok(None),
+        }
+    }
+
+    /// Returns the value of the key `key` in the section `section` as a string
+    /// 
+    /// # Return value
+    /// `Ok(Some(value))` with `value` as the value of the key
+    /// 
+    /// `Ok(None)` if there is not any key `key` in `section`
+    /// 
+    /// `Err(error)` with `error` as a [`TypeError`](../../errors/struct.TypeError.html "errors::TypeError") if the value is neither a `Value::Str` nor a `Value::Raw`
+    pub fn get_str(&self, section: Option<&str>, key: &str) -> Result<Option<&str>, TypeError> {
+        match self.get_value(section, key) {
+            Some(Value::Str(value)) | Some(Value::Raw(value)) => Ok(Some(value)),
+            Some(value)                                       => Err(type_error(section, key, "str", value)),
+            None                                              => Ok(None),
+        }
+    }
+
+    /// Returns the value of the key `key` in the section `section` as its canonical text, whatever its type (see [`Value::as_raw_str`](../enum.Value.html#method.as_raw_str "datas::Value::as_raw_str")), or `None` if there is not any key `key` in `section`
+    /// 
+    /// The elements of an `Array` are joined by `, ` and a `Null` is an empty string
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::parse::parse_content;
+    /// 
+    /// let tree = parse_content("port = 8080\nverbose = yes\nname = 'my service'\n").unwrap();
+    /// 
+    /// assert_eq!(tree.get_raw(None, "port").as_deref(), Some("8080"));
+    /// assert_eq!(tree.get_raw(None, "verbose").as_deref(), Some("true"));
+    /// assert_eq!(tree.get_raw(None, "name").as_deref(), Some("my service"));
+    /// assert_eq!(tree.get_raw(None, "timeout"), None);
+    /// ```
+    pub fn get_raw(&self, section: Option<&str>, key: &str) -> Option<Cow<'_, str>> {
+        self.get_value(section, key)
+            .map(Value::as_raw_str)
+    }
+
+    /// Returns the value of the key `key` in the section `section` as an integer, or `default` if there is not any key `key` in `section` or if its value is not a `Value::Int`
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::parse::Parser;
+    /// 
+    /// let mut parser = Parser::new();
+    /// parser.parse_line("port = 8080").unwrap();
+    /// parser.parse_line("host = localhost").unwrap();
+    /// let tree = parser.into_tree();
+    /// 
+    /// assert_eq!(tree.get_int_or(None, "port", 80), 8080);
+    /// assert_eq!(tree.get_int_or(None, "timeout", 30), 30);
+    /// assert_eq!(tree.get_int_or(None, "host", 0), 0);
+    /// ```
+    pub fn get_int_or(&self, section: Option<&str>, key: &str, default: i64) -> i64 {
+        self.get_int(section, key)
+            .ok()
+            .flatten()
+            .unwrap_or(default)
+    }
+
+    /// Returns the value of the key `key` in the section `section` as a floating-point number, or `default` if there is not any key `key` in `section` or if its value is not a `Value::Float`
+    pub fn get_float_or(&self, section: Option<&str>, key: &str, default: f64) -> f64 {
+        self.get_float(section, key)
+            .ok()
+            .flatten()
+            .unwrap_or(default)
+    }
+
+    /// Returns the value of the key `key` in the section `section` as a boolean, or `default` if there is not any key `key` in `section` or if its value is not a `Value::Bool`
+    pub fn get_bool_or(&self, section: Option<&str>, key: &str, default: bool) -> bool {
+        self.get_bool(section, key)
+            .ok()
+            .flatten()
+            .unwrap_or(default)
+    }
+
+    /// Returns the value of the key `key` in the section `section` as a string, or `default` if there is not any key `key` in `section` or if its value is neither a `Value::Str` nor a `Value::Raw`
+    pub fn get_str_or<'a>(&'a self, section: Option<&str>, key: &str, default: &'a str) -> &'a str {
+        self.get_str(section, key)
+            .ok()
+            .flatten()
+            .unwrap_or(default)
+    }
+
+    /// Inserts `value` as the value of `ident`
+    /// 
+    /// A new key is added at the end of its section, and a new section at the end of the `Tree`
+    /// 
+    /// # Return value
+    /// `Some(old)` with `old` as the value replaced if `ident` was already in `self`, `None` otherwise. In a case-insensitive `Tree`, the original spelling of an existing key is kept
+    pub fn insert(&mut self, mut ident: Identifier, value: Value) -> Option<Value> {
+        if let Some(existing) = self.resolve(ident.section(), ident.name()) {
+            return self.data.insert(existing, value);
+        }
+
+        if let Some(Some(section)) = self.cache.find_section(ident.section(), self.case_insensitive) {
+            let section = String::from(section);
+            ident.change_section(Some(section));
+        }
+
+        self.cache.push(&ident);
+        self.data.insert(ident, value)
+    }
+
+    /// Appends `value` to the `Value::Array` of the key `key` in the section `section` (`None` for the global section), which is created with `value` as its only element if the key is not in `self`
+    /// 
+    /// The elements of an array may be of different types so `value` is never checked
+    /// 
+    /// # Return value
+    /// `Ok(())` in case of success
+    /// 
+    /// `Err(error)` with `error` as an [`Error::Type`](../../errors/enum.Error.html#variant.Type "errors::Error::Type") if the value of the key is not a `Value::Array`, or as an [`InvalidIdentifier`](../../errors/error_kinds/struct.InvalidIdentifier.html "errors::error_kinds::InvalidIdentifier") error if the key is not in `self` and either `section` or `key` is not a valid identifier. `self` is then left unchanged
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{tree::Tree, Value};
+    /// 
+    /// let mut tree = Tree::new();
+    /// 
+    /// tree.push_to_array(None, "servers", Value::Raw(String::from("a.com"))).unwrap();
+    /// tree.push_to_array(None, "servers", Value::Int(8080)).unwrap();
+    /// 
+    /// let expected = Value::Array(vec![Value::Raw(String::from("a.com")), Value::Int(8080)]);
+    /// assert_eq!(tree.get_value(None, "servers"), Some(&expected));
+    /// ```
+    pub fn push_to_array(&mut self, section: Option<&str>, key: &str, value: Value) -> Result<(), Error> {
+        match self.entry(section, key)? {
+            Entry::Occupied(mut entry) => match entry.get_mut() {
+                Value::Array(values) => {
+                    values.push(value);
+                    Ok(())
+                },
+                found                => Err(Error::from(type_error(section, key, "array", found))),
+            },
+            Entry::Vacant(entry)       => {
+                entry.insert(Value::Array(vec![value]));
+                Ok(())
+            },
+        }
+    }
+
+    /// Returns the entry of the key `key` in the section `section` (`None` for the global section) for an in-place manipulation
+    /// 
+    /// # Return value
+    /// `Ok(entry)` with `entry` as either an `Entry::Occupied` if the key is in `self` or an `Entry::Vacant` otherwise. Inserting into a vacant entry creates its section if needed
+    /// 
+    /// `Err(error)` with `error` as an [`InvalidIdentifier`](../../errors/error_kinds/struct.InvalidIdentifier.html "errors::error_kinds::InvalidIdentifier") error if the key is vacant and either `section` or `key` is not a valid identifier
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{tree::Tree, Value};
+    /// 
+    /// let mut tree = Tree::new();
+    /// 
+    /// for _ in 0..3 {
+    ///     tree.entry(Some("stats"), "launches").unwrap()
+    ///         .and_modify(|value| if let Value::Int(n) = value { *n += 1 })
+    ///         .or_insert(Value::Int(1));
+    /// }
+    /// 
+    /// assert_eq!(tree.get_int(Some("stats"), "launches").unwrap(), Some(3));
+    /// assert!(tree.entry(Some("bad section!"), "launches").is_err());
+    /// ```
+    pub fn entry(&mut self, section: Option<&str>, key: &str) -> Result<Entry<'_>, Error> {
+        if let Some(ident) = self.resolve(section, key) {
+            return Ok(Entry::Occupied(OccupiedEntry {
+                tree: self,
+                ident,
+            }));
+        }
+
+        let invalid = match section {
+            Some(section) if !Identifier::is_valid_section(section) => Some(section),
+            _ if !Identifier::is_valid(key)                         => Some(key),
+            _                                                       => None,
+        };
+        if let Some(i) = invalid {
+            return Err(Error::from(InvalidIdentifier::new(String::from(i), String::from(i))));
+        }
+
+        Ok(Entry::Vacant(VacantEntry {
+            tree: self,
+            ident: Identifier::new(section.map(String::from), String::from(key)),
+        }))
+    }
+
+    /// Removes the key `key` of the section `section` (`None` for the global section)
+    /// 
+    /// A section left without any key is removed too
+    /// 
+    /// # Return value
+    /// `Some(value)` with `value` as the value removed, `None` if the key is not in `self`
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{tree::Tree, Identifier, Value};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert(Identifier::new(Some(String::from("server")), String::from("port")), Value::Int(80));
+    /// 
+    /// assert_eq!(tree.remove_key(Some("server"), "port"), Some(Value::Int(80)));
+    /// assert_eq!(tree.remove_key(Some("server"), "port"), None);
+    /// assert!(tree.section(Some("server")).is_none());
+    /// ```
+    pub fn remove_key(&mut self, section: Option<&str>, key: &str) -> Option<Value> {
+        let ident = self.resolve(section, key)?;
+        let section = ident.section().map(String::from);
+
+        let keys = self.cache.keys.get_mut(&section)
+                                  .expect("The section of a key is in the cache");
+        keys.retain(|i| i != ident.name());
+        if keys.is_empty() {
+            self.forget_section(section.as_deref());
+        }
+
+        self.comments.keys.remove(&ident);
+        self.formats.remove(&ident);
+        self.quotes.remove(&ident);
+        self.data.remove(&ident)
+    }
+
+    /// Removes the section `section` (`None` for the global section) with all its keys
+    /// 
+    /// # Return value
+    /// `Some(data)` with `data` as the keys removed and their values (see [`SectionData`](struct.SectionData.html "datas::tree::SectionData")), `None` if the section is not in `self`
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{tree::Tree, Identifier, Value};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert(Identifier::new(Some(String::from("server")), String::from("port")), Value::Int(80));
+    /// 
+    /// let removed = tree.remove_section(Some("server")).unwrap();
+    /// assert_eq!(removed.get("port"), Some(&Value::Int(80)));
+    /// assert!(tree.section(Some("server")).is_none());
+    /// ```
+    pub fn remove_section(&mut self, section: Option<&str>) -> Option<SectionData> {
+        let section = self.cache.find_section(section, self.case_insensitive)?.map(String::from);
+        let keys = self.forget_section(section.as_deref());
+
+        let entries = keys.into_iter()
+                          .map(|key| {
+                              let ident = Identifier::new(section.clone(), key);
+                              self.comments.keys.remove(&ident);
+                              self.formats.remove(&ident);
+                              self.quotes.remove(&ident);
+                              let value = self.data.remove(&ident)
+                                                   .expect("The keys of the cache are in the data");
+
+                              (ident, value)
+                          })
+                          .collect();
+
+        Some(SectionData {
+            name: section,
+            entries,
+        })
+    }
+
+    /// Clears the value of every key of `self`, keeping its sections, its keys, their order and their comments
+    /// 
+    /// Clearing a value sets it to an empty [`Value::Str`](../enum.Value.html "datas::Value"), dumped as `key = ''`: the key is not removed, whatever the type of its former value. The formats and the quotes retained from the parsed file are forgotten so that the value is dumped as is
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{tree::Tree, Identifier, Value};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert(Identifier::new(Some(String::from("server")), String::from("port")), Value::Int(80));
+    /// 
+    /// tree.clear_values();
+    /// assert_eq!(tree.get_value(Some("server"), "port"), Some(&Value::Str(String::new())));
+    /// assert_eq!(tree.len(), 1);
+    /// ```
+    pub fn clear_values(&mut self) {
+        for value in self.data.values_mut() {
+            *value = Value::Str(String::new());
+        }
+
+        self.formats.clear();
+        self.quotes.clear();
+    }
+
+    /// Removes all the sections and keys of `self` with their comments, including the comments ending the file. `self` keeps its case sensitivity
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{tree::Tree, Identifier, Value};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert(Identifier::new(Some(String::from("server")), String::from("port")), Value::Int(80));
+    /// 
+    /// tree.clear();
+    /// assert!(tree.is_empty());
+    /// assert!(tree.section(Some("server")).is_none());
+    /// ```
+    pub fn clear(&mut self) {
+        self.cache = Cache::new();
+        self.data.clear();
+        self.comments = Comments::new();
+        self.formats.clear();
+        self.quotes.clear();
+        self.paths.clear();
+        self.parents.clear();
+    }
+
+    /// Renames the section `old` as `new`, keeping its keys, their values, their comments and its position among the sections
+    /// 
+    /// Nothing is done if there is not any section `old` in `self`. A section renamed is no longer seen as a nested section (see [`get_subsection`](struct.Tree.html#method.get_subsection "datas::tree::Tree::get_subsection"))
+    /// 
+    /// # Return value
+    /// `Ok(())` in case of success or if `old` is not in `self`
+    /// 
+    /// `Err(error)` with `error` as an [`InvalidIdentifier`](../../errors/error_kinds/struct.InvalidIdentifier.html "errors::error_kinds::InvalidIdentifier") error if `new` is not a valid identifier or as a [`DuplicateSection`](../../errors/error_kinds/struct.DuplicateSection.html "errors::error_kinds::DuplicateSection") error if `new` is already a section of `self`, since both sections would be merged silently. `self` is then left unchanged
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{tree::Tree, Identifier, Value};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert(Identifier::new(Some(String::from("db")), String::from("port")), Value::Int(5432));
+    /// tree.insert(Identifier::new(Some(String::from("server")), String::from("port")), Value::Int(80));
+    /// 
+    /// tree.rename_section("db", "database").unwrap();
+    /// assert_eq!(tree.get_int(Some("database"), "port").unwrap(), Some(5432));
+    /// assert!(tree.section(Some("db")).is_none());
+    /// 
+    /// assert!(tree.rename_section("database", "server").is_err());
+    /// ```
+    pub fn rename_section(&mut self, old: &str, new: &str) -> Result<(), Error> {
+        let old = match self.cache.find_section(Some(old), self.case_insensitive) {
+            Some(Some(old)) => String::from(old),
+            _               => return Ok(()),
+        };
+
+        if !Identifier::is_valid(new) {
+            return Err(Error::from(InvalidIdentifier::new(String::from(new), String::from(new))));
+        }
+        // Changing only the case of a section of a case-insensitive `Tree` is allowed
+        if let Some(Some(existing)) = self.cache.find_section(Some(new), self.case_insensitive) {
+            if existing != old {
+                return Err(Error::from(DuplicateSection::new(String::from(existing))));
+            }
+        }
+
+        let position = self.cache.sections.iter()
+                                          .position(|i| *i == old)
+                                          .expect("The section was just found");
+        self.cache.sections[position] = String::from(new);
+
+        let keys = self.cache.keys.remove(&Some(old.clone()))
+                                  .expect("The section was just found");
+        for key in &keys {
+            let ident = Identifier::new(Some(old.clone()), key.clone());
+            let renamed = Identifier::new(Some(String::from(new)), key.clone());
+
+            let value = self.data.remove(&ident)
+                                 .expect("The keys of the cache are in the data");
+            self.data.insert(renamed.clone(), value);
+            if let Some(comments) = self.comments.keys.remove(&ident) {
+                self.comments.keys.insert(renamed.clone(), comments);
+            }
+            if let Some(quote) = self.quotes.remove(&ident) {
+                self.quotes.insert(renamed.clone(), quote);
+            }
+            if let Some(format) = self.formats.remove(&ident) {
+                self.formats.insert(renamed, format);
+            }
+        }
+        self.cache.keys.insert(Some(String::from(new)), keys);
+
+        if let Some(comments) = self.comments.sections.remove(&old) {
+            self.comments.sections.insert(String::from(new), comments);
+        }
+        self.paths.remove(&old);
+        if let Some(parent) = self.parents.remove(&old) {
+            self.parents.insert(String::from(new), parent);
+        }
+        for parent in self.parents.values_mut() {
+            if *parent == old {
+                *parent = String::from(new);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the section `section` (spelled as registered) from the cache with its comments and its path and returns the names of its keys
+    fn forget_section(&mut self, section: Option<&str>) -> Vec<String> {
+        if let Some(name) = section {
+            self.cache.sections.retain(|i| i != name);
+            self.comments.sections.remove(name);
+            self.paths.remove(name);
+            self.parents.remove(name);
+        }
+
+        self.cache.keys.remove(&section.map(String::from))
+                       .unwrap_or_default()
+    }
+
+    /// Merges `other` into `self`, key by key. The keys of `other` which are not in `self` are added after the keys of `self`, with their comments
+    /// 
+    /// # Parameters
+    /// `other` the `Tree` to merge into `self`
+    /// 
+    /// `policy` the behaviour when a key is in both `self` and `other` (see [`MergePolicy`](enum.MergePolicy.html "datas::tree::MergePolicy"))
+    /// 
+    /// # Return value
+    /// `Ok(())` in case of success
+    /// 
+    /// `Err(error)` with `error` as a [`DuplicateKey`](../../errors/error_kinds/struct.DuplicateKey.html "errors::error_kinds::DuplicateKey") error naming the first key conflicting if `policy` is `MergePolicy::Error`. `self` is then left unchanged
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{tree::{Tree, MergePolicy}, Identifier, Value};
+    /// 
+    /// let mut defaults = Tree::new();
+    /// defaults.insert(Identifier::new(Some(String::from("server")), String::from("port")), Value::Int(80));
+    /// defaults.insert(Identifier::new(Some(String::from("server")), String::from("host")), Value::Raw(String::from("localhost")));
+    /// 
+    /// let mut overrides = Tree::new();
+    /// overrides.insert(Identifier::new(Some(String::from("server")), String::from("port")), Value::Int(8080));
+    /// 
+    /// defaults.merge(overrides, MergePolicy::Overwrite).unwrap();
+    /// assert_eq!(defaults.get_int(Some("server"), "port").unwrap(), Some(8080));
+    /// assert_eq!(defaults.get_str(Some("server"), "host").unwrap(), Some("localhost"));
+    /// ```
+    pub fn merge(&mut self, mut other: Tree, policy: MergePolicy) -> Result<(), Error> {
+        let keys: Vec<Identifier> = other.sections()
+                                         .flat_map(|i| i.keys().collect::<Vec<Identifier>>())
+                                         .collect();
+
+        if policy == MergePolicy::Error {
+            for i in &keys {
+                if let Some(existing) = self.resolve(i.section(), i.name()) {
+                    let section = existing.section().map(String::from);
+                    let err = DuplicateKey::new(section, String::from(existing.name()), String::from(i.name()));
+                    return Err(Error::from(err));
+                }
+            }
+        }
+
+        for ident in keys {
+            let exists = self.contains_key(ident.section(), ident.name());
+            if exists && policy == MergePolicy::KeepExisting {
+                continue;
+            }
+
+            if let Some(section) = ident.section() {
+                if !self.contains_section(Some(section)) {
+                    let comments = other.comments.sections.remove(section).unwrap_or_default();
+                    self.comment_section(section, comments);
+                    if let Some(path) = other.paths.remove(section) {
+                        self.set_section_path(String::from(section), path);
+                    }
+                    if let Some(parent) = other.parents.remove(section) {
+                        self.set_section_parent(String::from(section), parent);
+                    }
+                }
+            }
+            if !exists {
+                let comments = other.comments.keys.remove(&ident).unwrap_or_default();
+                self.comment_key(&ident, comments);
+            }
+
+            let value = other.data.remove(&ident)
+                                  .expect("The keys iterated are in `other`");
+            let format = other.formats.remove(&ident);
+            let quote = other.quotes.remove(&ident);
+            self.insert(ident.clone(), value);
+
+            let ident = self.resolve(ident.section(), ident.name())
+                            .expect("The key was just inserted");
+            if let Some(format) = format {
+                self.set_format(ident.clone(), format);
+            }
+            self.set_quote(ident, quote);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the comments written before the key `key` of the section `section`, or before the declaration of `section` if `key` is `None`
+    /// 
+    /// # Return value
+    /// `Some(comments)` with `comments` as the comment lines, as written in the file with their leading comment character. The blank lines retained with [`ParserOptions::keep_blank_lines`](../../parse/struct.ParserOptions.html#structfield.keep_blank_lines "parse::ParserOptions::keep_blank_lines") are empty strings among them
+    /// 
+    /// `None` if there is not any comment there or if the comments were not retained while parsing (see [`ParserOptions::keep_comments`](../../parse/struct.ParserOptions.html#structfield.keep_comments "parse::ParserOptions::keep_comments"))
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::parse::{Parser, ParserOptions};
+    /// 
+    /// let options = ParserOptions {
+    ///     keep_comments: true,
+    ///     ..ParserOptions::default()
+    /// };
+    /// let mut parser = Parser::with_options(options);
+    /// 
+    /// parser.parse_line("; The answer").unwrap();
+    /// parser.parse_line("answer = 42").unwrap();
+    /// 
+    /// let tree = parser.into_tree();
+    /// assert_eq!(tree.comments_for(None, Some("answer")), Some(&[String::from("; The answer")][..]));
+    /// ```
+    pub fn comments_for(&self, section: Option<&str>, key: Option<&str>) -> Option<&[String]> {
+        let comments = match key {
+            Some(key) => self.comments.keys.get(&self.resolve(section, key)?),
+            None      => self.comments.sections.get(self.cache.find_section(section, self.case_insensitive)??),
+        };
+
+        comments.map(Vec::as_slice)
+    }
+
+    /// Returns the comments written after the last key of the file
+    pub fn trailing_comments(&self) -> &[String] {
+        &self.comments.trailing
+    }
+
+    /// Attaches `comments` to the key `ident`, which may be inserted later
+    pub(crate) fn comment_key(&mut self, ident: &Identifier, comments: Vec<String>) {
+        if comments.is_empty() {
+            return;
+        }
+
+        let ident = self.resolve(ident.section(), ident.name())
+                        .unwrap_or_else(|| ident.clone());
+        self.comments.keys.entry(ident)
+                          .or_default()
+                          .extend(comments);
+    }
+
+    /// Attaches `comments` to the declaration of the section `section`
+    pub(crate) fn comment_section(&mut self, section: &str, comments: Vec<String>) {
+        if comments.is_empty() {
+            return;
+        }
+
+        self.comments.sections.entry(String::from(section))
+                              .or_default()
+                              .extend(comments);
+    }
+
+    /// Adds `comments` to the comments written after the last key of the file
+    pub(crate) fn comment_end(&mut self, comments: Vec<String>) {
+        self.comments.trailing.extend(comments);
+    }
+
+    /// Retains the blank lines read from now on among the comments, as empty lines, so that the dumpers write them back instead of separating the sections with a blank line
+    pub(crate) fn retain_blank_lines(&mut self) {
+        self.blank_lines = true;
+    }
+
+    /// Returns `true` if the blank lines are retained among the comments
+    pub(crate) fn keeps_blank_lines(&self) -> bool {
+        self.blank_lines
+    }
+
+    /// Returns the text of the value of `ident` as written in the parsed file, if retained (see [`ParserOptions::keep_formats`](../../parse/struct.ParserOptions.html#structfield.keep_formats "parse::ParserOptions::keep_formats"))
+    /// 
+    /// # Note
+    /// This text may be outdated if the value was changed since, so it should be parsed again and compared to the current value before being used
+    pub(crate) fn format_of(&self, ident: &Identifier) -> Option<&str> {
+        self.formats.get(ident).map(String::as_str)
+    }
+
+    /// Retains `format` as the text of the value of `ident`
+    pub(crate) fn set_format(&mut self, ident: Identifier, format: String) {
+        self.formats.insert(ident, format);
+    }
+
+    /// Returns the quotes around the value of the key `key` of the section `section` in the parsed file
+    /// 
+    /// The quotes are always retained by a [`Parser`](../../parse/struct.Parser.html "parse::Parser") and [`dump_tree`](../../dump/fn.dump_tree.html "dump::dump_tree") writes a [`Value::Str`](../enum.Value.html "datas::Value") back between the same quotes, even if its text was changed since
+    /// 
+    /// # Return value
+    /// `Some(quote)` with `quote` as the quotes around the value, `None` if the key is not in `self` or if its value was not quoted, as an unquoted value which is not a valid number (see [`Value::parse`](../enum.Value.html#method.parse "datas::Value::parse"))
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::tree::Quote;
+    /// use mininip::parse::parse_content;
+    /// 
+    /// let tree = parse_content("a = 'single'\nb = \"double\"\nc = 007\n").unwrap();
+    /// assert_eq!(tree.quote_of(None, "a"), Some(Quote::Single));
+    /// assert_eq!(tree.quote_of(None, "b"), Some(Quote::Double));
+    /// assert_eq!(tree.quote_of(None, "c"), None);
+    /// ```
+    pub fn quote_of(&self, section: Option<&str>, key: &str) -> Option<Quote> {
+        let ident = self.resolve(section, key)?;
+        self.quotes.get(&ident).copied()
+    }
+
+    /// Retains `quote` as the quotes around the value of `ident`, `None` forgetting them
+    pub(crate) fn set_quote(&mut self, ident: Identifier, quote: Option<Quote>) {
+        match quote {
+            Some(quote) => self.quotes.insert(ident, quote),
+            None        => self.quotes.remove(&ident),
+        };
+    }
+
+    /// Returns the section named `name` (`None` for the global section) or `None` if there is not any
+    pub fn section(&self, name: Option<&str>) -> Option<Section<'_>> {
+        let ident = self.cache.find_section(name, self.case_insensitive)?;
+
+        Some(Section {
+            ident,
+            target: self,
+        })
+    }
+
+    /// Returns the keys of the section named `name` (`None` for the global section) with the canonical text of their values (see [`Value::as_raw_str`](../enum.Value.html#method.as_raw_str "datas::Value::as_raw_str")), as expected by the APIs reading plain strings
+    /// 
+    /// The keys and the texts are cloned into the map returned, which keeps the spelling of the keys even if `self` is case-insensitive. A [`Value::Null`](../enum.Value.html "datas::Value") gives an empty string
+    /// 
+    /// # Return value
+    /// `Some(map)` with `map` as the keys and their texts, `None` if there is not any section `name` in `self`
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::parse::parse_content;
+    /// 
+    /// let tree = parse_content("name = 'my service'\n[server]\nport = 8080\nverbose = yes\n").unwrap();
+    /// 
+    /// let server = tree.section_as_string_map(Some("server")).unwrap();
+    /// assert_eq!(server["port"], "8080");
+    /// assert_eq!(server["verbose"], "true");
+    /// assert_eq!(tree.section_as_string_map(None).unwrap()["name"], "my service");
+    /// assert!(tree.section_as_string_map(Some("client")).is_none());
+    /// ```
+    pub fn section_as_string_map(&self, name: Option<&str>) -> Option<HashMap<String, String>> {
+        let section = self.section(name)?;
+
+        Some(section.iter()
+                    .map(|(ident, value)| (String::from(ident.name()), value.as_raw_str().into_owned()))
+                    .collect())
+    }
+
+    /// Returns the section named `name` (`None` for the global section) for an in-place manipulation, or `None` if there is not any
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::parse::Parser;
+    /// use mininip::datas::Value;
+    /// 
+    /// let mut parser = Parser::new();
+    /// parser.parse_line("[server]").unwrap();
+    /// parser.parse_line("port = 80").unwrap();
+    /// let mut tree = parser.into_tree();
+    /// 
+    /// let mut server = tree.section_mut(Some("server")).unwrap();
+    /// server.set("port", Value::Int(8080)).unwrap();
+    /// server.set("host", Value::Raw(String::from("localhost"))).unwrap();
+    /// 
+    /// let server = tree.section(Some("server")).unwrap();
+    /// assert_eq!(server.get("port"), Some(&Value::Int(8080)));
+    /// assert_eq!(server.len(), 2);
+    /// ```
+    pub fn section_mut(&mut self, name: Option<&str>) -> Option<SectionMut<'_>> {
+        let ident = self.cache.find_section(name, self.case_insensitive)?;
+
+        Some(SectionMut {
+            ident: ident.map(String::from),
+            target: self,
+        })
+    }
+
+    /// Returns the section whose path is `path`, from the outermost section to the innermost one. An empty `path` is the global section
+    /// 
+    /// A section is nested only if it was declared so while parsing with [`ParserOptions::nested_sections`](../../parse/struct.ParserOptions.html#structfield.nested_sections "parse::ParserOptions::nested_sections"). Any other section has a path of one element which is its name
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::parse::{Parser, ParserOptions};
+    /// 
+    /// let options = ParserOptions {
+    ///     nested_sections: true,
+    ///     ..ParserOptions::default()
+    /// };
+    /// let mut parser = Parser::with_options(options);
+    /// parser.parse_line("[server.tls]").unwrap();
+    /// parser.parse_line("enabled = on").unwrap();
+    /// parser.parse_line("[version\\.2]").unwrap();
+    /// parser.parse_line("enabled = off").unwrap();
+    /// let tree = parser.into_tree();
+    /// 
+    /// assert!(tree.get_subsection(&["server", "tls"]).is_some());
+    /// assert!(tree.get_subsection(&["version.2"]).is_some());
+    /// assert!(tree.get_subsection(&["version", "2"]).is_none());
+    /// ```
+    pub fn get_subsection(&self, path: &[&str]) -> Option<Section<'_>> {
+        if path.is_empty() {
+            return self.section(None);
+        }
+
+        let ident = self.cache.sections.iter()
+                                       .find(|i| {
+                                           let own = self.section_path(i);
+                                           own.len() == path.len() && self.paths_match_prefix(&own, path)
+                                       })?;
+        Some(Section {
+            ident: Some(ident),
+            target: self,
+        })
+    }
+
+    /// Returns the path of the section named `section`
+    fn section_path<'a>(&'a self, section: &'a str) -> Vec<&'a str> {
+        match self.nested_path(section) {
+            Some(path) => path.iter().map(String::as_str).collect(),
+            None       => vec![section],
+        }
+    }
+
+    /// Returns the path of the section named `section` if it was declared as a nested section
+    pub(crate) fn nested_path(&self, section: &str) -> Option<&[String]> {
+        self.paths.get(section).map(Vec::as_slice)
+    }
+
+    /// Returns `true` if `path` starts with `prefix`, according to the case sensitivity of `self`
+    fn paths_match_prefix(&self, path: &[&str], prefix: &[&str]) -> bool {
+        path.len() >= prefix.len() && path.iter()
+                                          .zip(prefix)
+                                          .all(|(a, b)| names_match(a, b, self.case_insensitive))
+    }
+
+    /// Sets `path` as the path of the nested section `section`, whose name is made of the elements of `path` separated by `.`
+    pub(crate) fn set_section_path(&mut self, section: String, path: Vec<String>) {
+        self.paths.insert(section, path);
+    }
+
+    /// Returns the section the section `section` inherits from, if it was declared as `[section : parent]` (see [`ParserOptions::section_inheritance`](../../parse/struct.ParserOptions.html#structfield.section_inheritance "parse::ParserOptions::section_inheritance"))
+    /// 
+    /// The keys inherited are copied into `section` by the parser so they are read like its own keys. The parent is only retained so that [`dump_tree`](../../dump/fn.dump_tree.html "dump::dump_tree") writes the declaration back as is
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::parse::{parse_content_with_options, ParserOptions};
+    /// 
+    /// let options = ParserOptions {
+    ///     section_inheritance: true,
+    ///     ..ParserOptions::default()
+    /// };
+    /// let tree = parse_content_with_options("[default]\nport = 80\n[dev : default]\n", options).unwrap();
+    /// assert_eq!(tree.parent_of("dev"), Some("default"));
+    /// assert_eq!(tree.parent_of("default"), None);
+    /// assert_eq!(tree.get_int(Some("dev"), "port").unwrap(), Some(80));
+    /// ```
+    pub fn parent_of(&self, section: &str) -> Option<&str> {
+        let section = self.cache.find_section(Some(section), self.case_insensitive)??;
+        self.parents.get(section).map(String::as_str)
+    }
+
+    /// Returns the parent of the section `section`, spelled as registered, even if it has no key
+    pub(crate) fn section_parent(&self, section: &str) -> Option<&str> {
+        self.parents.get(section).map(String::as_str)
+    }
+
+    /// Sets `parent` as the section `section` inherits from
+    pub(crate) fn set_section_parent(&mut self, section: String, parent: String) {
+        self.parents.insert(section, parent);
+    }
+
+    /// Finds the `Identifier` stored in `self` matching `section` and `key` according to the case sensitivity of `self`
+    // `Option::is_none_or` would need Rust 1.82
+    #[allow(clippy::unnecessary_map_or)]
+    fn resolve(&self, section: Option<&str>, key: &str) -> Option<Identifier> {
+        if !self.case_insensitive {
+            // Building an identifier from invalid names would panic and they can't be in `self` anyway
+            if !Identifier::is_valid(key) || !section.map_or(true, Identifier::is_valid_section) {
+                return None;
+            }
+
+            let ident = Identifier::new(section.map(String::from), String::from(key));
+            return if self.data.contains_key(&ident) {
+                Some(ident)
+            } else {
+                None
+            };
+        }
+
+        let section = self.cache.find_section(section, true)?;
+        let key = self.cache.find_key(section, key, true)?;
+
+        Some(Identifier::new(section.map(String::from), String::from(key)))
+    }
+
+    /// Iterates over the sections of a `Tree`
+    pub fn sections(&self) -> SectionIterator<'_> {
+        SectionIterator {
+            iterator: self.cache.sections.iter(),
+            target: self,
+            awaited: false,
+        }
+    }
+
+    /// Iterates over the sections of a `Tree` like [`sections`](struct.Tree.html#method.sections "datas::tree::Tree::sections"), each with the comment lines written before its declaration. The comments of the global section are the ones written before its first key
+    /// 
+    /// The comments are borrowed from `self` and a section without any comment comes with an empty slice. They are only retained if asked while parsing (see [`ParserOptions::keep_comments`](../../parse/struct.ParserOptions.html#structfield.keep_comments "parse::ParserOptions::keep_comments"))
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::parse::{Parser, ParserOptions};
+    /// 
+    /// let options = ParserOptions {
+    ///     keep_comments: true,
+    ///     ..ParserOptions::default()
+    /// };
+    /// let mut parser = Parser::with_options(options);
+    /// parser.parse_line("; The name of the service").unwrap();
+    /// parser.parse_line("name = mininip").unwrap();
+    /// parser.parse_line("; The server settings").unwrap();
+    /// parser.parse_line("[server]").unwrap();
+    /// parser.parse_line("port = 8080").unwrap();
+    /// let tree = parser.into_tree();
+    /// 
+    /// let sections: Vec<_> = tree.sections_with_comments()
+    ///                            .map(|(section, comments)| (section.name(), comments))
+    ///                            .collect();
+    /// assert_eq!(sections, vec![
+    ///     (None, &[String::from("; The name of the service")][..]),
+    ///     (Some("server"), &[String::from("; The server settings")][..]),
+    /// ]);
+    /// ```
+    pub fn sections_with_comments(&self) -> SectionsWithComments<'_> {
+        SectionsWithComments {
+            iterator: self.sections(),
+            target: self,
+        }
+    }
+
+    /// Iterates over the keys of a `Tree` with their section and their value, section by section in the order of [`sections`](struct.Tree.html#method.sections "datas::tree::Tree::sections")
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{tree::Tree, Identifier, Value};
+    /// 
+    /// let mut tree = Tree::new();
+    /// tree.insert(Identifier::new(None, String::from("name")), Value::Raw(String::from("service")));
+    /// tree.insert(Identifier::new(Some(String::from("server")), String::from("port")), Value::Int(80));
+    /// 
+    /// let mut iter = tree.iter();
+    /// assert_eq!(iter.next().map(|(section, key, _)| (section, key.name())), Some((None, "name")));
+    /// assert_eq!(iter.next().map(|(section, _, value)| (section, value)), Some((Some("server"), &Value::Int(80))));
+    /// assert!(iter.next().is_none());
+    /// ```
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            iterator: self.sections().flat_map(Section::entries),
+        }
+    }
+
+    /// Iterates over the keys of the section `section` (`None` for the global section) with their value. The iterator is empty if `section` is not in `self`
+    pub fn keys_in(&self, section: Option<&str>) -> SectionEntries<'_> {
+        match self.section(section) {
+            Some(section) => section.entries(),
+            None          => SectionEntries {
+                iterator: [].iter(),
+                section: None,
+                target: self,
+            },
+        }
+    }
+
+    /// Iterates over the identifiers of the keys of the section `section` (`None` for the global section) whose name starts with `prefix`, in their order. The iterator is empty if `section` is not in `self`
+    /// 
+    /// The prefix is matched regardless of its case in a case-insensitive `Tree`
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{tree::Tree, Identifier, Value};
+    /// 
+    /// let mut tree = Tree::new();
+    /// for i in &["plugin.a.enabled", "plugin.b.enabled", "theme"] {
+    ///     tree.insert(Identifier::new(None, String::from(*i)), Value::Bool(true));
+    /// }
+    /// 
+    /// let plugins: Vec<&str> = tree.keys_with_prefix(None, "plugin.").map(Identifier::name).collect();
+    /// assert_eq!(plugins, ["plugin.a.enabled", "plugin.b.enabled"]);
+    /// ```
+    pub fn keys_with_prefix<'a, 'p>(&'a self, section: Option<&str>, prefix: &'p str) -> MatchingKeys<'a, 'p> {
+        MatchingKeys {
+            entries: self.keys_in(section),
+            pattern: Pattern::Prefix(prefix),
+            case_insensitive: self.case_insensitive,
+        }
+    }
+
+    /// Iterates over the identifiers of the keys of the section `section` (`None` for the global section) whose name matches the glob `pattern`, in their order. The iterator is empty if `section` is not in `self`
+    /// 
+    /// A `*` of `pattern` matches any sequence of characters, even an empty one, and any other character matches itself (regardless of its case in a case-insensitive `Tree`)
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::datas::{tree::Tree, Identifier, Value};
+    /// 
+    /// let mut tree = Tree::new();
+    /// for i in &["plugin.a.enabled", "plugin.a.path", "plugin.b.enabled"] {
+    ///     tree.insert(Identifier::new(None, String::from(*i)), Value::Bool(true));
+    /// }
+    /// 
+    /// let enabled: Vec<&str> = tree.keys_matching(None, "plugin.*.enabled").map(Identifier::name).collect();
+    /// assert_eq!(enabled, ["plugin.a.enabled", "plugin.b.enabled"]);
+    /// ```
+    pub fn keys_matching<'a, 'p>(&'a self, section: Option<&str>, pattern: &'p str) -> MatchingKeys<'a, 'p> {
+        MatchingKeys {
+            entries: self.keys_in(section),
+            pattern: Pattern::Glob(pattern),
+            case_insensitive: self.case_insensitive,
+        }
+    }
+
+    /// Returns the number of keys in `self`, in all its sections including the global one
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if `self` does not contain any key. Since a section exists only through its keys, a `Tree` without any key does not contain any section either
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the number of sections in `self`, counting the global section if it contains a key
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::parse::Parser;
+    /// 
+    /// let mut parser = Parser::new();
+    /// parser.parse_line("name = service").unwrap();
+    /// parser.parse_line("[server]").unwrap();
+    /// parser.parse_line("host = localhost").unwrap();
+    /// parser.parse_line("port = 8080").unwrap();
+    /// let tree = parser.into_tree();
+    /// 
+    /// assert_eq!(tree.len(), 3);
+    /// assert_eq!(tree.section_count(), 2);
+    /// ```
+    pub fn section_count(&self) -> usize {
+        self.cache.keys.len()
+    }
+
+    /// Returns an immutable reference to the owned data
+    pub fn get_data(&self) -> &HashMap<Identifier, Value> {
+        &self.data
+    }
+
+    /// Consumes `self` and returns the owned data
+    pub fn into_data(self) -> HashMap<Identifier, Value> {
+        self.data
+    }
+
+    /// Returns the canonical form of `self` to be read back following `options`, which is the same for any two equal `Tree`s, to compare or hash configurations
+    /// 
+    /// The data of `self` is dumped following [`DumpOptions::canonical`](../../dump/struct.DumpOptions.html#method.canonical "dump::DumpOptions::canonical"), without the comments, the blank lines nor the original text of the values retained while parsing. The key-value separator is the first one of `options.kv_separators` while the escapes and the name of the global section are the ones of `options`. Parsing the canonical form of a `Tree` parsed following `options` gives back an equal `Tree`, so normalizing it again gives the same text
+    /// 
+    /// # Note
+    /// The elements of an array are separated by `,` so they are only read back as an array if [`ParserOptions::array_separator`](../../parse/struct.ParserOptions.html#structfield.array_separator "parse::ParserOptions::array_separator") is `Some(',')`
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::parse::{parse_content, ParserOptions};
+    /// 
+    /// let a = parse_content("b = 0x10\n; A comment\na = yes\n").unwrap();
+    /// let b = parse_content("a=on\nb=16\n").unwrap();
+    /// 
+    /// let options = ParserOptions::default();
+    /// assert_eq!(a.normalize(&options), "a=true\nb=16\n");
+    /// assert_eq!(a.normalize(&options), b.normalize(&options));
+    /// ```
+    pub fn normalize(&self, options: &crate::parse::ParserOptions) -> String {
+        let data: Tree = self.iter()
+                             .map(|(_, ident, value)| (ident.clone(), value.clone()))
+                             .collect();
+
+        let dump_options = crate::dump::DumpOptions {
+            kv_separator: options.kv_separators.first().copied().unwrap_or('='),
+            global_section_name: options.global_section_name.clone(),
+            escapes: options.escapes.clone(),
+            percent_escapes: options.percent_escapes,
+            ..crate::dump::DumpOptions::canonical()
+        };
+        crate::dump::dump_tree_with_options(&data, dump_options)
+    }
+}
+
+impl<'a> IntoIterator for &'a Tree {
+    type Item = (Option<&'a str>, &'a Identifier, &'a Value);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// Consumes a `Tree` into its keys and their values, section by section in the order of [`sections`](struct.Tree.html#method.sections "datas::tree::Tree::sections")
+impl IntoIterator for Tree {
+    type Item = (Identifier, Value);
+    type IntoIter = IntoIter;
+
+    fn into_iter(mut self) -> IntoIter {
+        let keys: Vec<Identifier> = self.iter()
+                                        .map(|(_, ident, _)| ident.clone())
+                                        .collect();
+        let entries: Vec<(Identifier, Value)> = keys.into_iter()
+                                                    .map(|ident| {
+                                                        let value = self.data.remove(&ident)
+                                                                             .expect("The keys iterated are in the data");
+                                                        (ident, value)
+                                                    })
+                                                    .collect();
+
+        IntoIter {
+            iterator: entries.into_iter(),
+        }
+    }
+}
+
+/// Builds a `Tree` from keys and their values, inserted in their order with [`Tree::insert`](struct.Tree.html#method.insert "datas::tree::Tree::insert") so a key given twice keeps its last value
+/// 
+/// # Example
+/// ```
+/// use mininip::datas::{tree::Tree, Identifier, Value};
+/// 
+/// let mut tree = Tree::new();
+/// tree.insert(Identifier::new(None, String::from("name")), Value::Raw(String::from("service")));
+/// tree.insert(Identifier::new(Some(String::from("server")), String::from("port")), Value::Int(80));
+/// tree.insert(Identifier::new(Some(String::from("server")), String::from("debug")), Value::Bool(true));
+/// 
+/// let server: Tree = tree.into_iter()
+///                        .filter(|(ident, _)| ident.section() == Some("server"))
+///                        .collect();
+/// assert_eq!(server.len(), 2);
+/// assert_eq!(server.get_value(Some("server"), "port"), Some(&Value::Int(80)));
+/// ```
+impl FromIterator<(Identifier, Value)> for Tree {
+    fn from_iter<T: IntoIterator<Item = (Identifier, Value)>>(iter: T) -> Tree {
+        let mut tree = Tree::new();
+        tree.extend(iter);
+
+        tree
+    }
 }
 
-impl Tree {
-    /// Iterates over the sections of a `Tree`
-    pub fn sections(&self) -> SectionIterator<'_> {
-        SectionIterator {
-            iterator: self.cache.sections.iter(),
-            target: self,
-            awaited: false,
+/// Inserts keys and their values with [`Tree::insert`](struct.Tree.html#method.insert "datas::tree::Tree::insert"), in their order
+impl Extend<(Identifier, Value)> for Tree {
+    fn extend<T: IntoIterator<Item = (Identifier, Value)>>(&mut self, iter: T) {
+        for (ident, value) in iter {
+            self.insert(ident, value);
         }
     }
+}
 
-    /// Returns an immutable reference to the owned data
-    pub fn get_data(&self) -> &HashMap<Identifier, Value> {
-        &self.data
+impl fmt::Debug for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+         .entries(self.sections().map(|i| (i.name(), i)))
+         .finish()
+    }
+}
+
+impl PartialEq for Tree {
+    fn eq(&self, other: &Tree) -> bool {
+        self.data == other.data
     }
+}
 
-    /// Consumes `self` and returns the owned data
-    pub fn into_data(self) -> HashMap<Identifier, Value> {
-        self.data
+impl Default for Tree {
+    fn default() -> Self {
+        Tree::new()
     }
 }
 
@@ -54,7 +1373,14 @@ impl From<HashMap<Identifier, Value>> for Tree {
     fn from(data: HashMap<Identifier, Value>) -> Tree {
         Tree {
             cache: Cache::from(&data),
-            data: data,
+            data,
+            case_insensitive: false,
+            comments: Comments::new(),
+            formats: HashMap::new(),
+            quotes: HashMap::new(),
+            paths: HashMap::new(),
+            parents: HashMap::new(),
+            blank_lines: false,
         }
     }
 }
@@ -63,7 +1389,7 @@ impl From<HashMap<Identifier, Value>> for Tree {
 /// An iterator over sections in a `Tree`
 pub struct SectionIterator<'a> {
     /// An iterator over the sections names in the `Tree`
-    iterator: std::slice::Iter<'a, String>,
+    iterator: core::slice::Iter<'a, String>,
     /// The `Tree` owning the data iterated
     target: &'a Tree,
     /// Set to `true` if already awaited, `false` otherwise. Necessary because
@@ -79,7 +1405,7 @@ impl<'a> Iterator for SectionIterator<'a> {
         if !self.awaited {
             self.awaited = true;
 
-            if self.target.cache.keys.get(&None).is_some() {
+            if self.target.cache.keys.contains_key(&None) {
                 return Some(Section {
                     ident: None,
                     target: self.target,
@@ -89,23 +1415,46 @@ impl<'a> Iterator for SectionIterator<'a> {
 
         let ident = self.iterator.next()?;
         Some(Section {
-            ident: Some(&ident),
+            ident: Some(ident),
             target: self.target,
         })
     }
 }
 
+impl<'a> DoubleEndedIterator for SectionIterator<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.iterator.next_back() {
+            Some(ident) => Some(Section {
+                ident: Some(ident),
+                target: self.target,
+            }),
+            // Only the global section may be left
+            None        => self.next(),
+        }
+    }
+}
+
 
 /// A section in a `Tree`
+/// 
+/// A `Section` is debugged as a map of its keys to their values
 pub struct Section<'a> {
     ident: Option<&'a str>,
     target: &'a Tree,
 }
 
+impl fmt::Debug for Section<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+         .entries(self.iter().map(|(ident, value)| (ident.name(), value)))
+         .finish()
+    }
+}
+
 impl<'a> Section<'a> {
     /// Returns the identifier (name) of this section
     pub fn name(&self) -> Option<&'a str> {
-        self.ident.clone()
+        self.ident
     }
 
     /// Returns an iterator over the keys of this section
@@ -116,29 +1465,146 @@ impl<'a> Section<'a> {
         }
     }
 
+    /// Returns the value of the key `key` of this section or `None` if there is not any
+    pub fn get(&self, key: &str) -> Option<&'a Value> {
+        self.target.get_value(self.ident, key)
+    }
+
+    /// Returns the number of keys in this section
+    pub fn len(&self) -> usize {
+        self.keys_internal_iterator().len()
+    }
+
+    /// Returns `true` if this section does not contain any key. Since a section exists only through its keys, it is always `false` for a section of a `Tree`
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the keys of this section with their value, without consuming this section
+    pub fn iter(&self) -> SectionEntries<'a> {
+        SectionEntries {
+            iterator: self.keys_internal_iterator(),
+            section: self.ident,
+            target: self.target,
+        }
+    }
+
+    /// Returns an iterator over the keys of this section with their value
+    pub fn entries(self) -> SectionEntries<'a> {
+        SectionEntries {
+            iterator: self.keys_internal_iterator(),
+            section: self.ident,
+            target: self.target,
+        }
+    }
+
     /// Returns an iterator ofer the keys of this section.
     /// 
     /// # Note
     /// This iterator is the one internally used by the vector storing these key
     /// names. It must not be exposed in a public interface
-    fn keys_internal_iterator(&self) -> std::slice::Iter<'a, String> {
+    fn keys_internal_iterator(&self) -> core::slice::Iter<'a, String> {
         self.target.cache.keys[&self.name_owned()].iter()
     }
 
+    /// Returns the path of this section, from the outermost section to this one. It is empty for the global section
+    /// 
+    /// # See
+    /// [`Tree::get_subsection`](struct.Tree.html#method.get_subsection "datas::tree::Tree::get_subsection") to learn more about nested sections
+    pub fn path(&self) -> Vec<&'a str> {
+        match self.ident {
+            Some(name) => self.target.section_path(name),
+            None       => Vec::new(),
+        }
+    }
+
+    /// Returns the sections directly nested in this section, in their order in the `Tree`. The subsections of the global section are the sections which are not nested
+    pub fn subsections(&self) -> Vec<Section<'a>> {
+        let path = self.path();
+        let target = self.target;
+
+        target.cache.sections.iter()
+                             .filter(|i| {
+                                 let own = target.section_path(i);
+                                 own.len() == path.len() + 1 && target.paths_match_prefix(&own, &path)
+                             })
+                             .map(|i| Section {
+                                 ident: Some(i),
+                                 target,
+                             })
+                             .collect()
+    }
+
     /// Returns the identifier of this section like it must be passed to an
     /// `Identifier`: an `Option<String>` instead of an `Option<&str>`
     pub fn name_owned(&self) -> Option<String> {
-        match self.ident {
-            Some(val) => Some(String::from(val)),
-            None      => None,
+        self.ident.map(String::from)
+    }
+}
+
+
+/// A section in a `Tree` borrowed mutably, to read and update its keys
+/// 
+/// # See
+/// [`Tree::section_mut`](struct.Tree.html#method.section_mut "datas::tree::Tree::section_mut") to get a `SectionMut`
+pub struct SectionMut<'a> {
+    ident: Option<String>,
+    target: &'a mut Tree,
+}
+
+impl<'a> SectionMut<'a> {
+    /// Returns the identifier (name) of this section
+    pub fn name(&self) -> Option<&str> {
+        self.ident.as_deref()
+    }
+
+    /// Returns the value of the key `key` of this section or `None` if there is not any
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.target.get_value(self.ident.as_deref(), key)
+    }
+
+    /// Returns the number of keys in this section. It is `0` once all its keys are removed
+    pub fn len(&self) -> usize {
+        self.target.section(self.ident.as_deref())
+                   .map_or(0, |i| i.len())
+    }
+
+    /// Returns `true` if this section does not contain any key
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sets `value` as the value of the key `key` of this section
+    /// 
+    /// # Return value
+    /// `Ok(Some(old))` with `old` as the value replaced if `key` was already in this section, `Ok(None)` otherwise
+    /// 
+    /// `Err(error)` with `error` as an [`InvalidIdentifier`](../../errors/error_kinds/struct.InvalidIdentifier.html "errors::error_kinds::InvalidIdentifier") error if `key` is new and is not a valid identifier
+    pub fn set(&mut self, key: &str, value: Value) -> Result<Option<Value>, Error> {
+        match self.target.entry(self.ident.as_deref(), key)? {
+            Entry::Occupied(mut entry) => Ok(Some(entry.insert(value))),
+            Entry::Vacant(entry)       => {
+                entry.insert(value);
+                Ok(None)
+            },
         }
     }
+
+    /// Removes the key `key` of this section
+    /// 
+    /// Like with [`Tree::remove_key`](struct.Tree.html#method.remove_key "datas::tree::Tree::remove_key"), the section is removed from the `Tree` with its last key. Setting a key afterwards declares it again at the end of the `Tree`
+    /// 
+    /// # Return value
+    /// `Some(value)` with `value` as the value removed, `None` if the key is not in this section
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.target.remove_key(self.ident.as_deref(), key)
+    }
 }
 
 
 /// An iterator over keys in a given section
 pub struct KeyIterator<'a> {
-    iterator: std::slice::Iter<'a, String>,
+    iterator: core::slice::Iter<'a, String>,
     target: &'a Section<'a>,
 }
 
@@ -154,8 +1620,371 @@ impl<'a> Iterator for KeyIterator<'a> {
 }
 
 
+/// An iterator over the keys of a section with their value, returned by [`Section::entries`](struct.Section.html#method.entries "datas::tree::Section::entries") and [`Tree::keys_in`](struct.Tree.html#method.keys_in "datas::tree::Tree::keys_in")
+pub struct SectionEntries<'a> {
+    iterator: core::slice::Iter<'a, String>,
+    section: Option<&'a str>,
+    target: &'a Tree,
+}
+
+impl<'a> SectionEntries<'a> {
+    /// Returns the key named `key` of the section iterated and its value
+    fn lookup(&self, key: &str) -> (&'a Identifier, &'a Value) {
+        let ident = Identifier::new(self.section.map(String::from), String::from(key));
+        self.target.data.get_key_value(&ident)
+                        .expect("The keys of the cache are in the data")
+    }
+}
+
+impl<'a> Iterator for SectionEntries<'a> {
+    type Item = (&'a Identifier, &'a Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.iterator.next()?;
+        Some(self.lookup(key))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for SectionEntries<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let key = self.iterator.next_back()?;
+        Some(self.lookup(key))
+    }
+}
+
+impl<'a> ExactSizeIterator for SectionEntries<'a> {}
+
+
+/// An iterator over the identifiers of the keys of a section matching a pattern, returned by [`Tree::keys_with_prefix`](struct.Tree.html#method.keys_with_prefix "datas::tree::Tree::keys_with_prefix") and [`Tree::keys_matching`](struct.Tree.html#method.keys_matching "datas::tree::Tree::keys_matching")
+pub struct MatchingKeys<'a, 'p> {
+    entries: SectionEntries<'a>,
+    pattern: Pattern<'p>,
+    case_insensitive: bool,
+}
+
+/// A pattern matched by `MatchingKeys`
+enum Pattern<'p> {
+    Prefix(&'p str),
+    Glob(&'p str),
+}
+
+impl<'a, 'p> MatchingKeys<'a, 'p> {
+    /// Returns `true` if the key named `key` matches the pattern
+    fn matches(&self, key: &str) -> bool {
+        match self.pattern {
+            Pattern::Prefix(prefix) => key.get(..prefix.len()).is_some_and(|start| names_match(start, prefix, self.case_insensitive)),
+            Pattern::Glob(pattern)  => glob_match(pattern.as_bytes(), key.as_bytes(), self.case_insensitive),
+        }
+    }
+}
+
+impl<'a, 'p> Iterator for MatchingKeys<'a, 'p> {
+    type Item = &'a Identifier;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The names are filtered before being looked up, which would allocate an `Identifier`
+        while let Some(key) = self.entries.iterator.next() {
+            if self.matches(key) {
+                return Some(self.entries.lookup(key).0);
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns `true` if `name` matches the glob `pattern` where `*` matches any sequence of bytes. Since an identifier is pure ASCII, it is compared byte per byte
+fn glob_match(pattern: &[u8], name: &[u8], case_insensitive: bool) -> bool {
+    let same = |a: u8, b: u8| if case_insensitive { a.eq_ignore_ascii_case(&b) } else { a == b };
+
+    // The position following the last `*` met and the position in `name` it was matched up to, to backtrack on a mismatch
+    let mut star = None;
+    let (mut p, mut n) = (0, 0);
+    while n < name.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            p += 1;
+            star = Some((p, n));
+        } else if p < pattern.len() && same(pattern[p], name[n]) {
+            p += 1;
+            n += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p;
+            n = star_n + 1;
+            star = Some((star_p, n));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+
+/// An iterator over the keys of a `Tree` with their value, consuming the tree
+pub struct IntoIter {
+    iterator: alloc::vec::IntoIter<(Identifier, Value)>,
+}
+
+impl Iterator for IntoIter {
+    type Item = (Identifier, Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterator.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for IntoIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iterator.next_back()
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+
+/// An iterator over all the keys of a `Tree` with their section and their value, returned by [`Tree::iter`](struct.Tree.html#method.iter "datas::tree::Tree::iter")
+pub struct Iter<'a> {
+    iterator: core::iter::FlatMap<SectionIterator<'a>, SectionEntries<'a>, fn(Section<'a>) -> SectionEntries<'a>>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (Option<&'a str>, &'a Identifier, &'a Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (ident, value) = self.iterator.next()?;
+        Some((ident.section(), ident, value))
+    }
+}
+
+impl<'a> DoubleEndedIterator for Iter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (ident, value) = self.iterator.next_back()?;
+        Some((ident.section(), ident, value))
+    }
+}
+
+
+/// An iterator over the sections of a `Tree` with their comments, returned by [`Tree::sections_with_comments`](struct.Tree.html#method.sections_with_comments "datas::tree::Tree::sections_with_comments")
+pub struct SectionsWithComments<'a> {
+    iterator: SectionIterator<'a>,
+    target: &'a Tree,
+}
+
+impl<'a> SectionsWithComments<'a> {
+    /// Returns the comments written before the declaration of `section`, or before the first key of the global section
+    fn comments_of(&self, section: &Section<'a>) -> &'a [String] {
+        let comments = &self.target.comments;
+        let found = match section.name() {
+            Some(name) => comments.sections.get(name),
+            // Looking up the first key by its `Identifier` would allocate its name
+            None       => {
+                let first = self.target.cache.keys[&None].first();
+                comments.keys.iter()
+                             .find(|(ident, _)| ident.section().is_none() && Some(ident.name()) == first.map(String::as_str))
+                             .map(|(_, comments)| comments)
+            },
+        };
+
+        found.map_or(&[], Vec::as_slice)
+    }
+}
+
+impl<'a> Iterator for SectionsWithComments<'a> {
+    type Item = (Section<'a>, &'a [String]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let section = self.iterator.next()?;
+        let comments = self.comments_of(&section);
+        Some((section, comments))
+    }
+}
+
+impl<'a> DoubleEndedIterator for SectionsWithComments<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let section = self.iterator.next_back()?;
+        let comments = self.comments_of(&section);
+        Some((section, comments))
+    }
+}
+
+
+/// The data of a section removed from a `Tree` by [`Tree::remove_section`](struct.Tree.html#method.remove_section "datas::tree::Tree::remove_section")
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionData {
+    name: Option<String>,
+    /// The keys of the section and their values, in their order in the `Tree`
+    entries: Vec<(Identifier, Value)>,
+}
+
+impl SectionData {
+    /// Returns the name of the section removed, `None` for the global section
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the value of the key `key` of the section removed
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.entries.iter()
+                    .find(|(ident, _)| ident.name() == key)
+                    .map(|(_, value)| value)
+    }
+
+    /// Returns the keys of the section removed and their values, in their order in the `Tree`
+    pub fn entries(&self) -> &[(Identifier, Value)] {
+        &self.entries
+    }
+
+    /// Consumes `self` and returns the keys of the section removed and their values, in their order in the `Tree`
+    pub fn into_entries(self) -> Vec<(Identifier, Value)> {
+        self.entries
+    }
+}
+
+
+/// The behaviour of [`Tree::merge`](struct.Tree.html#method.merge "datas::tree::Tree::merge") when a key is in both `Tree`s
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// The value of the `Tree` merged replaces the existing one
+    Overwrite,
+    /// The existing value is kept
+    KeepExisting,
+    /// The merge fails
+    Error,
+}
+
+
+/// The quotes around a quoted string in a parsed file, as returned by [`Tree::quote_of`](struct.Tree.html#method.quote_of "datas::tree::Tree::quote_of")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Quote {
+    /// `'value'`
+    Single,
+    /// `"value"`
+    Double,
+    /// `"""value"""`, possibly spanning several lines
+    Triple,
+}
+
+
+/// A view into a single key of a `Tree`, which may be either occupied or vacant. It is returned by [`Tree::entry`](struct.Tree.html#method.entry "datas::tree::Tree::entry")
+pub enum Entry<'a> {
+    /// The key is in the `Tree`
+    Occupied(OccupiedEntry<'a>),
+    /// The key is not in the `Tree`
+    Vacant(VacantEntry<'a>),
+}
+
+impl<'a> Entry<'a> {
+    /// Returns the identifier of this entry
+    pub fn key(&self) -> &Identifier {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry)   => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant and returns a mutable reference to the value of the entry
+    pub fn or_insert(self, default: Value) -> &'a mut Value {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry)   => entry.insert(default),
+        }
+    }
+
+    /// Inserts the result of `default` if the entry is vacant and returns a mutable reference to the value of the entry
+    pub fn or_insert_with<F: FnOnce() -> Value>(self, default: F) -> &'a mut Value {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry)   => entry.insert(default()),
+        }
+    }
+
+    /// Calls `function` on the value of the entry if it is occupied and returns the entry
+    pub fn and_modify<F: FnOnce(&mut Value)>(self, function: F) -> Entry<'a> {
+        match self {
+            Entry::Occupied(mut entry) => {
+                function(entry.get_mut());
+                Entry::Occupied(entry)
+            },
+            Entry::Vacant(entry)       => Entry::Vacant(entry),
+        }
+    }
+}
+
+
+/// An entry of a key which is in a `Tree`
+pub struct OccupiedEntry<'a> {
+    tree: &'a mut Tree,
+    /// The identifier as it is spelled in `tree`
+    ident: Identifier,
+}
+
+impl<'a> OccupiedEntry<'a> {
+    /// Returns the identifier of this entry, as spelled in the `Tree`
+    pub fn key(&self) -> &Identifier {
+        &self.ident
+    }
+
+    /// Returns a reference to the value of this entry
+    pub fn get(&self) -> &Value {
+        &self.tree.data[&self.ident]
+    }
+
+    /// Returns a mutable reference to the value of this entry
+    pub fn get_mut(&mut self) -> &mut Value {
+        self.tree.data.get_mut(&self.ident)
+                      .expect("An occupied entry is in its tree")
+    }
+
+    /// Consumes `self` and returns a mutable reference to the value of this entry bound to the lifetime of the `Tree`
+    pub fn into_mut(self) -> &'a mut Value {
+        self.tree.data.get_mut(&self.ident)
+                      .expect("An occupied entry is in its tree")
+    }
+
+    /// Replaces the value of this entry by `value` and returns the old one
+    pub fn insert(&mut self, value: Value) -> Value {
+        core::mem::replace(self.get_mut(), value)
+    }
+}
+
+
+/// An entry of a key which is not in a `Tree`
+pub struct VacantEntry<'a> {
+    tree: &'a mut Tree,
+    ident: Identifier,
+}
+
+impl<'a> VacantEntry<'a> {
+    /// Returns the identifier of this entry
+    pub fn key(&self) -> &Identifier {
+        &self.ident
+    }
+
+    /// Inserts `value` as the value of this entry, creating its section if needed, and returns a mutable reference to it
+    pub fn insert(self, value: Value) -> &'a mut Value {
+        let VacantEntry { tree, ident } = self;
+
+        tree.insert(ident.clone(), value);
+        let ident = tree.resolve(ident.section(), ident.name())
+                        .expect("The key was just inserted");
+
+        tree.data.get_mut(&ident)
+                 .expect("The key was just inserted")
+    }
+}
+
+
 /// A cached result of an extraction of all the section and keys names. Will be
 /// kept and updated forever in the owning `Tree`
+#[derive(Debug, Clone)]
 struct Cache {
     /// An ordered list of sections
     sections: Vec<String>,
@@ -163,49 +1992,122 @@ struct Cache {
     keys: HashMap<Option<String>, Vec<String>>,
 }
 
-impl From<&HashMap<Identifier, Value>> for Cache {
-    fn from(data: &HashMap<Identifier, Value>) -> Cache {
-        let mut sections = Vec::new();
-        let mut keys = HashMap::<_, Vec<String>>::new();
+impl Cache {
+    /// Creates an empty `Cache`
+    fn new() -> Cache {
+        Cache {
+            sections: Vec::new(),
+            keys: HashMap::new(),
+        }
+    }
 
-        for i in data.keys() {
-            let section_name = match i.section() {
-                Some(val) => Some(String::from(val)),
-                None      => None,
-            };
+    /// Registers `ident` at the end of its section, creating the section if necessary. `ident` must not be registered yet
+    fn push(&mut self, ident: &Identifier) {
+        let section_name = ident.section().map(String::from);
 
-            match keys.entry(section_name.clone()) {
-                hash_map::Entry::Occupied(mut entry) => entry.get_mut().push(String::from(i.name())),
-                hash_map::Entry::Vacant(entry)       => {
-                    let vec = vec![String::from(i.name())];
-                    entry.insert(vec);
+        match self.keys.entry(section_name.clone()) {
+            hash_map::Entry::Occupied(mut entry) => entry.get_mut().push(String::from(ident.name())),
+            hash_map::Entry::Vacant(entry)       => {
+                entry.insert(vec![String::from(ident.name())]);
 
-                    if let Some(val) = section_name {
-                        sections.push(val);
-                    }
-                },
-            }
+                if let Some(val) = section_name {
+                    self.sections.push(val);
+                }
+            },
         }
+    }
 
+    /// Sorts the sections and the keys inside each section by name
+    fn sort(&mut self) {
         // No collisions so unstable sorting is more efficient
-        sections.sort_unstable();
+        self.sections.sort_unstable();
+        for i in self.keys.values_mut() {
+            i.sort_unstable();
+        }
+    }
 
-        if let Some(val) = keys.get_mut(&None) {
-            val.sort_unstable();
+    /// Returns the registered spelling of the section `section` as `Some(spelling)` or `None` if there is not any
+    fn find_section(&self, section: Option<&str>, case_insensitive: bool) -> Option<Option<&str>> {
+        match section {
+            None if self.keys.contains_key(&None) => Some(None),
+            None                                  => None,
+            Some(name)                            => self.sections.iter()
+                                                                  .find(|i| names_match(i, name, case_insensitive))
+                                                                  .map(|i| Some(i.as_str())),
         }
-        for i in &sections {
-            keys.get_mut(&Some(i.clone()))
-                .expect("Any section name in `section` should be in `keys`")
-                .sort_unstable();
+    }
+
+    /// Returns the registered spelling of the key `key` in the section `section` or `None` if there is not any. `section` must be spelled as registered
+    fn find_key(&self, section: Option<&str>, key: &str, case_insensitive: bool) -> Option<&str> {
+        self.keys.get(&section.map(String::from))?
+            .iter()
+            .find(|i| names_match(i, key, case_insensitive))
+            .map(String::as_str)
+    }
+}
+
+/// Builds a `TypeError` for the key `key` of `section` expected to be `expected` but containing `found`
+fn type_error(section: Option<&str>, key: &str, expected: &'static str, found: &Value) -> TypeError {
+    TypeError::new(section.map(String::from), String::from(key), expected, found.type_name())
+}
+
+/// Compares two section or key names, ignoring their case if `case_insensitive` is set. Since an identifier is pure ASCII, an ASCII comparison is enough
+fn names_match(a: &str, b: &str, case_insensitive: bool) -> bool {
+    if case_insensitive {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a == b
+    }
+}
+
+impl From<&HashMap<Identifier, Value>> for Cache {
+    fn from(data: &HashMap<Identifier, Value>) -> Cache {
+        let mut cache = Cache::new();
+
+        for i in data.keys() {
+            cache.push(i);
         }
 
-        Cache {
-            sections,
-            keys,
+        cache.sort();
+        cache
+    }
+}
+
+
+/// The comments retained while parsing, attached to the element following them
+#[derive(Debug, Clone)]
+struct Comments {
+    /// The comments written before a section declaration
+    sections: HashMap<String, Vec<String>>,
+    /// The comments written before an assignment
+    keys: HashMap<Identifier, Vec<String>>,
+    /// The comments written after the last key of the file
+    trailing: Vec<String>,
+}
+
+impl Comments {
+    /// Creates an empty `Comments`
+    fn new() -> Comments {
+        Comments {
+            sections: HashMap::new(),
+            keys: HashMap::new(),
+            trailing: Vec::new(),
         }
     }
 }
 
 
+mod builder;
+pub use builder::*;
+
+mod flat;
+pub use flat::*;
+
+mod diff;
+pub use diff::*;
+
+mod schema;
+pub use schema::*;
+
 #[cfg(test)]
 mod tests;
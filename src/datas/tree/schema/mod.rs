@@ -0,0 +1,277 @@
+//! Contains the definition of [`Schema`](struct.Schema.html "datas::tree::Schema")
+
+use crate::prelude::*;
+use crate::datas::Value;
+use super::Tree;
+use core::fmt::{self, Display};
+
+/// The type expected for a value by a [`Schema`](struct.Schema.html "datas::tree::Schema")
+/// 
+/// Each type matches the values accepted by the typed getter of the same name of `Tree`, such as [`get_str`](struct.Tree.html#method.get_str "datas::tree::Tree::get_str") which accepts a `Value::Raw` as well
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    /// Any value
+    Any,
+    /// A `Value::Str` or a `Value::Raw`
+    Str,
+    /// A `Value::Int`
+    Int,
+    /// A `Value::Float`. An integer is not accepted
+    Float,
+    /// A `Value::Bool`
+    Bool,
+    /// A `Value::Array`
+    Array,
+    /// A `Value::Color`
+    Color,
+}
+
+impl ValueType {
+    /// Returns `true` if `value` has the type `self`
+    pub fn matches(self, value: &Value) -> bool {
+        matches!((self, value), (ValueType::Any, _)
+                              | (ValueType::Str, Value::Str(_))
+                              | (ValueType::Str, Value::Raw(_))
+                              | (ValueType::Int, Value::Int(_))
+                              | (ValueType::Float, Value::Float(_))
+                              | (ValueType::Bool, Value::Bool(_))
+                              | (ValueType::Array, Value::Array(_))
+                              | (ValueType::Color, Value::Color(..)))
+    }
+
+    /// Returns the name of `self`, which is the one given by [`Value::type_name`](../enum.Value.html#method.type_name "datas::Value::type_name") or `"any"`
+    pub fn name(self) -> &'static str {
+        match self {
+            ValueType::Any   => "any",
+            ValueType::Str   => "str",
+            ValueType::Int   => "int",
+            ValueType::Float => "float",
+            ValueType::Bool  => "bool",
+            ValueType::Array => "array",
+            ValueType::Color => "color",
+        }
+    }
+}
+
+impl Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+
+/// A difference between a `Tree` and a [`Schema`](struct.Schema.html "datas::tree::Schema"), as returned by [`Schema::validate`](struct.Schema.html#method.validate "datas::tree::Schema::validate")
+/// 
+/// # Note
+/// A `Tree` does not retain the lines of its keys so a violation only names the key concerned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// A required key is not in the `Tree`
+    MissingKey {
+        section: Option<String>,
+        key: String,
+    },
+    /// The value of a key does not have the type expected
+    WrongType {
+        section: Option<String>,
+        key: String,
+        expected: ValueType,
+        /// The name of the type of the value found (see [`Value::type_name`](../enum.Value.html#method.type_name "datas::Value::type_name"))
+        found: &'static str,
+    },
+    /// A key of the `Tree` is not declared by a strict schema
+    UnknownKey {
+        section: Option<String>,
+        key: String,
+    },
+}
+
+impl Violation {
+    /// Returns the section of the key concerned (`None` for the global section)
+    pub fn section(&self) -> Option<&str> {
+        match self {
+            Violation::MissingKey { section, .. } => section.as_deref(),
+            Violation::WrongType { section, .. }  => section.as_deref(),
+            Violation::UnknownKey { section, .. } => section.as_deref(),
+        }
+    }
+
+    /// Returns the key concerned
+    pub fn key(&self) -> &str {
+        match self {
+            Violation::MissingKey { key, .. } => key,
+            Violation::WrongType { key, .. }  => key,
+            Violation::UnknownKey { key, .. } => key,
+        }
+    }
+}
+
+/// Writes ` in section section` or ` in the global section`
+fn write_section(f: &mut fmt::Formatter, section: &Option<String>) -> fmt::Result {
+    match section {
+        Some(section) => write!(f, " in section {}", section),
+        None          => f.write_str(" in the global section"),
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::MissingKey { section, key }                 => {
+                write!(f, "Missing key {}", key)?;
+                write_section(f, section)
+            },
+            Violation::WrongType { section, key, expected, found } => {
+                write!(f, "Expected {} for key {}", expected, key)?;
+                write_section(f, section)?;
+                write!(f, ", found {}", found)
+            },
+            Violation::UnknownKey { section, key }                 => {
+                write!(f, "Unknown key {}", key)?;
+                write_section(f, section)
+            },
+        }
+    }
+}
+
+
+/// A key declared by a `Schema`
+#[derive(Debug, Clone, PartialEq)]
+struct KeySchema {
+    section: Option<String>,
+    key: String,
+    value_type: ValueType,
+    required: bool,
+}
+
+/// The sections, keys and types expected in a [`Tree`](struct.Tree.html "datas::tree::Tree"), built with a [`SchemaBuilder`](struct.SchemaBuilder.html "datas::tree::SchemaBuilder")
+/// 
+/// # Example
+/// ```
+/// use mininip::datas::tree::{Schema, ValueType, Violation};
+/// use mininip::parse::parse_content;
+/// 
+/// let schema = Schema::builder().require("db", "port", ValueType::Int)
+///                               .optional("db", "ssl", ValueType::Bool)
+///                               .require(None, "name", ValueType::Str)
+///                               .strict(true)
+///                               .build();
+/// 
+/// let tree = parse_content("name = service\n[db]\nport = 5432\nssl = on\n").unwrap();
+/// assert!(schema.validate(&tree).is_empty());
+/// 
+/// let tree = parse_content("[db]\nport = default\nuser = admin\n").unwrap();
+/// let violations: Vec<String> = schema.validate(&tree).iter().map(|i| format!("{}", i)).collect();
+/// assert_eq!(violations, [
+///     "Expected int for key port in section db, found raw",
+///     "Missing key name in the global section",
+///     "Unknown key user in section db",
+/// ]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Schema {
+    keys: Vec<KeySchema>,
+    strict: bool,
+}
+
+impl Schema {
+    /// Returns a `SchemaBuilder` building an empty schema, which is not strict
+    pub fn builder() -> SchemaBuilder {
+        SchemaBuilder::default()
+    }
+
+    /// Returns `true` if the keys not declared by `self` are violations
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Checks `tree` against `self`
+    /// 
+    /// The names are matched like with [`Tree::get_value`](struct.Tree.html#method.get_value "datas::tree::Tree::get_value"), regardless of their case if `tree` is case-insensitive. A key declared twice must match both declarations
+    /// 
+    /// # Return value
+    /// The violations found, empty if `tree` follows `self`. The missing keys and the wrong types come first in the order of declaration of the keys in `self`, followed by the unknown keys in the order of `tree` if `self` is strict
+    pub fn validate(&self, tree: &Tree) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let mut known = Vec::with_capacity(self.keys.len());
+
+        for i in &self.keys {
+            let section = i.section.clone();
+            let key = i.key.clone();
+
+            let ident = match tree.resolve(i.section.as_deref(), &i.key) {
+                Some(ident) => ident,
+                None        => {
+                    if i.required {
+                        violations.push(Violation::MissingKey { section, key });
+                    }
+                    continue;
+                },
+            };
+
+            let value = &tree.get_data()[&ident];
+            if !i.value_type.matches(value) {
+                violations.push(Violation::WrongType { section, key, expected: i.value_type, found: value.type_name() });
+            }
+            known.push(ident);
+        }
+
+        if self.strict {
+            let unknown = tree.iter()
+                              .filter(|(_, ident, _)| !known.contains(ident))
+                              .map(|(section, ident, _)| Violation::UnknownKey {
+                                  section: section.map(String::from),
+                                  key: String::from(ident.name()),
+                              });
+            violations.extend(unknown);
+        }
+
+        violations
+    }
+}
+
+/// A builder of a [`Schema`](struct.Schema.html "datas::tree::Schema") whose keys are declared one after the other
+/// 
+/// The section of a key is either the name of a section or `None` for the global section. A name which is not a valid identifier (see [`Identifier::is_valid`](../struct.Identifier.html#method.is_valid "datas::Identifier::is_valid")) is never found in a `Tree`
+#[derive(Debug, Clone, Default)]
+pub struct SchemaBuilder {
+    schema: Schema,
+}
+
+impl SchemaBuilder {
+    /// Declares the key `key` of the section `section`, which must be in the `Tree` with a value of type `value_type`
+    pub fn require<'a, S: Into<Option<&'a str>>>(self, section: S, key: &str, value_type: ValueType) -> SchemaBuilder {
+        self.declare(section.into(), key, value_type, true)
+    }
+
+    /// Declares the key `key` of the section `section`, which may be missing from the `Tree` but must have a value of type `value_type` otherwise
+    pub fn optional<'a, S: Into<Option<&'a str>>>(self, section: S, key: &str, value_type: ValueType) -> SchemaBuilder {
+        self.declare(section.into(), key, value_type, false)
+    }
+
+    /// Reports the keys which are not declared as violations if `strict` is `true`. A schema is not strict by default
+    pub fn strict(mut self, strict: bool) -> SchemaBuilder {
+        self.schema.strict = strict;
+        self
+    }
+
+    /// Consumes `self` and returns the `Schema` built
+    pub fn build(self) -> Schema {
+        self.schema
+    }
+
+    /// Declares the key `key` of the section `section`
+    fn declare(mut self, section: Option<&str>, key: &str, value_type: ValueType, required: bool) -> SchemaBuilder {
+        self.schema.keys.push(KeySchema {
+            section: section.map(String::from),
+            key: String::from(key),
+            value_type,
+            required,
+        });
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests;
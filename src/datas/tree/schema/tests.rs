@@ -0,0 +1,51 @@
+use crate::datas::{tree::*, Identifier, Value};
+use std::collections::HashMap;
+
+fn schema() -> Schema {
+    Schema::builder().require("db", "port", ValueType::Int)
+                     .optional("db", "ssl", ValueType::Bool)
+                     .require(None, "name", ValueType::Str)
+                     .optional(None, "tags", ValueType::Any)
+                     .build()
+}
+
+#[test]
+fn schema_validate() {
+    let tree = TreeBuilder::new().set("name", Value::Raw(String::from("service"))).unwrap()
+                                 .section("db").unwrap()
+                                 .set("port", Value::Int(5432)).unwrap()
+                                 .set("user", Value::Raw(String::from("admin"))).unwrap()
+                                 .build();
+    assert!(schema().validate(&tree).is_empty());
+    assert!(!schema().is_strict());
+
+    let tree = TreeBuilder::new().set("tags", Value::Array(Vec::new())).unwrap()
+                                 .section("db").unwrap()
+                                 .set("port", Value::Float(5432.0)).unwrap()
+                                 .set("ssl", Value::Raw(String::from("maybe"))).unwrap()
+                                 .build();
+    assert_eq!(schema().validate(&tree), vec![
+        Violation::WrongType { section: Some(String::from("db")), key: String::from("port"), expected: ValueType::Int, found: "float" },
+        Violation::WrongType { section: Some(String::from("db")), key: String::from("ssl"), expected: ValueType::Bool, found: "raw" },
+        Violation::MissingKey { section: None, key: String::from("name") },
+    ]);
+}
+
+#[test]
+fn schema_validate_strict_case_insensitive() {
+    let mut data = HashMap::new();
+    data.insert(Identifier::new(Some(String::from("DB")), String::from("Port")), Value::Int(5432));
+    data.insert(Identifier::new(Some(String::from("DB")), String::from("Host")), Value::Raw(String::from("localhost")));
+    data.insert(Identifier::new(None, String::from("Name")), Value::Str(String::from("service")));
+    let tree = Tree::with_case_insensitive(data).unwrap();
+
+    let schema = Schema::builder().require("db", "port", ValueType::Int)
+                                  .require(None, "name", ValueType::Str)
+                                  .strict(true)
+                                  .build();
+    let violations = schema.validate(&tree);
+    assert_eq!(violations, vec![Violation::UnknownKey { section: Some(String::from("DB")), key: String::from("Host") }]);
+    assert_eq!(violations[0].section(), Some("DB"));
+    assert_eq!(violations[0].key(), "Host");
+    assert_eq!(format!("{}", violations[0]), "Unknown key Host in section DB");
+}
@@ -1,5 +1,9 @@
+#![allow(clippy::approx_constant)]
+
 use crate::datas::{tree::*, Identifier, Value};
 use crate::parse::Parser;
+use crate::errors::Error;
+use std::borrow::Cow;
 
 #[test]
 fn cache_from_data() {
@@ -99,3 +103,790 @@ fn key_iterator_no_global() {
 
     assert_eq!(foo.name(), Some("foo"));
 }
+
+#[test]
+fn tree_get_value_case_sensitive() {
+    let mut data = HashMap::new();
+    data.insert(Identifier::new(Some(String::from("Settings")), String::from("theme")), Value::Str(String::from("dark")));
+
+    let tree = Tree::from(data);
+    assert_eq!(tree.get_value(Some("Settings"), "theme"), Some(&Value::Str(String::from("dark"))));
+    assert_eq!(tree.get_value(Some("settings"), "theme"), None);
+    assert_eq!(tree.get_value(Some("Settings"), "Theme"), None);
+    assert_eq!(tree.get_value(Some("Settings"), "invalid key!"), None);
+}
+
+#[test]
+fn tree_case_insensitive_lookup() {
+    let mut data = HashMap::new();
+    data.insert(Identifier::new(None, String::from("Version")), Value::Int(1));
+    data.insert(Identifier::new(Some(String::from("Settings")), String::from("theme")), Value::Str(String::from("dark")));
+
+    let tree = Tree::with_case_insensitive(data)
+        .expect("There is no duplicate key in this tree");
+    assert_eq!(tree.get_value(None, "VERSION"), Some(&Value::Int(1)));
+    assert_eq!(tree.get_value(Some("SETTINGS"), "Theme"), Some(&Value::Str(String::from("dark"))));
+    assert_eq!(tree.section(Some("settings")).unwrap().name(), Some("Settings"));
+    assert!(tree.section(Some("other")).is_none());
+}
+
+#[test]
+fn tree_case_insensitive_merges_sections() {
+    let mut data = HashMap::new();
+    data.insert(Identifier::new(Some(String::from("Settings")), String::from("theme")), Value::Str(String::from("dark")));
+    data.insert(Identifier::new(Some(String::from("settings")), String::from("font")), Value::Str(String::from("mono")));
+
+    let tree = Tree::with_case_insensitive(data)
+        .expect("There is no duplicate key in this tree");
+    let names = tree.sections()
+                    .map(|i| i.name())
+                    .collect::<Vec<_>>();
+    assert_eq!(names, vec![Some("Settings")]);
+
+    let settings = tree.section(Some("settings")).unwrap();
+    let keys = settings.keys()
+                       .map(|i| String::from(i.name()))
+                       .collect::<Vec<_>>();
+    assert_eq!(keys, vec![String::from("font"), String::from("theme")]);
+    assert_eq!(tree.get_value(Some("settings"), "font"), Some(&Value::Str(String::from("mono"))));
+}
+
+#[test]
+fn tree_case_insensitive_duplicate_key() {
+    let mut data = HashMap::new();
+    data.insert(Identifier::new(Some(String::from("Settings")), String::from("theme")), Value::Str(String::from("dark")));
+    data.insert(Identifier::new(Some(String::from("settings")), String::from("Theme")), Value::Str(String::from("light")));
+
+    match Tree::with_case_insensitive(data) {
+        Ok(_)                       => panic!("These keys are the same in a case-insensitive tree"),
+        Err(Error::DuplicateKey(_)) => {},
+        Err(err)                    => panic!("Wrong error returned: {:?}", err),
+    }
+}
+
+/// Returns a tree containing one key of each type in the section `types`
+fn typed_tree() -> Tree {
+    let mut tree = Tree::new();
+    let section = Some(String::from("types"));
+
+    tree.insert(Identifier::new(section.clone(), String::from("int")), Value::Int(42));
+    tree.insert(Identifier::new(section.clone(), String::from("float")), Value::Float(0.5));
+    tree.insert(Identifier::new(section.clone(), String::from("bool")), Value::Bool(true));
+    tree.insert(Identifier::new(section.clone(), String::from("str")), Value::Str(String::from("quoted")));
+    tree.insert(Identifier::new(section,         String::from("raw")), Value::Raw(String::from("raw")));
+
+    tree
+}
+
+#[test]
+fn tree_typed_getters() {
+    let tree = typed_tree();
+
+    assert_eq!(tree.get_int(Some("types"), "int").unwrap(), Some(42));
+    assert_eq!(tree.get_float(Some("types"), "float").unwrap(), Some(0.5));
+    assert_eq!(tree.get_bool(Some("types"), "bool").unwrap(), Some(true));
+    assert_eq!(tree.get_str(Some("types"), "str").unwrap(), Some("quoted"));
+    assert_eq!(tree.get_str(Some("types"), "raw").unwrap(), Some("raw"));
+}
+
+#[test]
+fn tree_typed_getters_missing_key() {
+    let tree = typed_tree();
+
+    assert_eq!(tree.get_int(Some("types"), "missing").unwrap(), None);
+    assert_eq!(tree.get_bool(Some("missing"), "bool").unwrap(), None);
+    assert_eq!(tree.get_str(None, "str").unwrap(), None);
+}
+
+#[test]
+fn tree_typed_getters_type_mismatch() {
+    let tree = typed_tree();
+
+    let err = tree.get_int(Some("types"), "float")
+                  .expect_err("A float is not an integer");
+    assert_eq!(err.section(), Some("types"));
+    assert_eq!(err.key(), "float");
+    assert_eq!(err.expected(), "int");
+    assert_eq!(err.found(), "float");
+
+    assert!(tree.get_float(Some("types"), "int").is_err());
+    assert!(tree.get_bool(Some("types"), "str").is_err());
+    assert!(tree.get_str(Some("types"), "bool").is_err());
+}
+
+#[test]
+fn tree_typed_getters_with_default() {
+    let tree = typed_tree();
+
+    assert_eq!(tree.get_int_or(Some("types"), "int", 0), 42);
+    assert_eq!(tree.get_int_or(Some("types"), "float", 0), 0);
+    assert_eq!(tree.get_float_or(Some("types"), "missing", 1.5), 1.5);
+    assert!(tree.get_bool_or(Some("types"), "bool", false));
+    assert!(tree.get_bool_or(Some("types"), "int", true));
+    assert_eq!(tree.get_str_or(Some("types"), "raw", "default"), "raw");
+    assert_eq!(tree.get_str_or(None, "str", "default"), "default");
+}
+
+#[test]
+fn tree_entry_vacant() {
+    let mut tree = Tree::new();
+
+    *tree.entry(Some("section"), "key").unwrap().or_insert(Value::Int(1)) = Value::Int(2);
+
+    assert_eq!(tree.get_value(Some("section"), "key"), Some(&Value::Int(2)));
+    let sections: Vec<Option<&str>> = tree.sections().map(|i| i.name()).collect();
+    assert_eq!(sections, vec![Some("section")]);
+}
+
+#[test]
+fn tree_entry_occupied() {
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(None, String::from("counter")), Value::Int(41));
+
+    tree.entry(None, "counter").unwrap()
+        .and_modify(|value| if let Value::Int(n) = value { *n += 1 })
+        .or_insert(Value::Int(0));
+
+    assert_eq!(tree.get_value(None, "counter"), Some(&Value::Int(42)));
+
+    match tree.entry(None, "counter").unwrap() {
+        Entry::Occupied(mut entry) => assert_eq!(entry.insert(Value::Bool(true)), Value::Int(42)),
+        Entry::Vacant(_)           => panic!("The key exists"),
+    }
+    assert_eq!(tree.get_value(None, "counter"), Some(&Value::Bool(true)));
+}
+
+#[test]
+fn tree_entry_and_modify_vacant() {
+    let mut tree = Tree::new();
+
+    tree.entry(None, "key").unwrap()
+        .and_modify(|_| panic!("The entry is vacant"))
+        .or_insert_with(|| Value::Raw(String::from("default")));
+
+    assert_eq!(tree.get_value(None, "key"), Some(&Value::Raw(String::from("default"))));
+}
+
+#[test]
+fn tree_entry_invalid_identifier() {
+    let mut tree = Tree::new();
+
+    match tree.entry(Some("bad section!"), "key") {
+        Err(Error::InvalidIdentifier(_)) => {},
+        _                                => panic!("An invalid section must be rejected"),
+    }
+    match tree.entry(None, "1key") {
+        Err(Error::InvalidIdentifier(_)) => {},
+        _                                => panic!("An invalid key must be rejected"),
+    }
+    assert_eq!(tree.sections().count(), 0);
+}
+
+#[test]
+fn tree_entry_case_insensitive() {
+    let mut data = HashMap::new();
+    data.insert(Identifier::new(Some(String::from("Section")), String::from("Key")), Value::Int(1));
+    let mut tree = Tree::with_case_insensitive(data).unwrap();
+
+    let entry = tree.entry(Some("SECTION"), "key").unwrap();
+    assert_eq!(entry.key(), &Identifier::new(Some(String::from("Section")), String::from("Key")));
+    entry.and_modify(|value| *value = Value::Int(2));
+
+    tree.entry(Some("section"), "other").unwrap().or_insert(Value::Int(3));
+    assert_eq!(tree.get_value(Some("Section"), "other"), Some(&Value::Int(3)));
+    assert_eq!(tree.sections().count(), 1);
+}
+
+fn merge_trees() -> (Tree, Tree) {
+    let mut first = Parser::new();
+    first.parse_line("global = 1").unwrap();
+    first.parse_line("[server]").unwrap();
+    first.parse_line("host = localhost").unwrap();
+    first.parse_line("port = 80").unwrap();
+
+    let options = crate::parse::ParserOptions {
+        keep_comments: true,
+        ..crate::parse::ParserOptions::default()
+    };
+    let mut second = Parser::with_options(options);
+    second.parse_line("[server]").unwrap();
+    second.parse_line("port = 8080").unwrap();
+    second.parse_line("; The timeout in seconds").unwrap();
+    second.parse_line("timeout = 30").unwrap();
+    second.parse_line("; The logs").unwrap();
+    second.parse_line("[log]").unwrap();
+    second.parse_line("level = info").unwrap();
+
+    (first.into_tree(), second.into_tree())
+}
+
+#[test]
+fn tree_merge_overwrite() {
+    let (mut tree, other) = merge_trees();
+    tree.merge(other, MergePolicy::Overwrite).unwrap();
+
+    assert_eq!(tree.get_value(None, "global"), Some(&Value::Int(1)));
+    assert_eq!(tree.get_value(Some("server"), "host"), Some(&Value::Raw(String::from("localhost"))));
+    assert_eq!(tree.get_value(Some("server"), "port"), Some(&Value::Int(8080)));
+    assert_eq!(tree.get_value(Some("server"), "timeout"), Some(&Value::Int(30)));
+    assert_eq!(tree.get_value(Some("log"), "level"), Some(&Value::Raw(String::from("info"))));
+
+    let keys: Vec<String> = tree.section(Some("server")).unwrap()
+                                .keys()
+                                .map(|i| String::from(i.name()))
+                                .collect();
+    assert_eq!(keys, vec!["host", "port", "timeout"]);
+
+    assert_eq!(tree.comments_for(Some("server"), Some("timeout")), Some(&[String::from("; The timeout in seconds")][..]));
+    assert_eq!(tree.comments_for(Some("log"), None), Some(&[String::from("; The logs")][..]));
+}
+
+#[test]
+fn tree_merge_keep_existing() {
+    let (mut tree, other) = merge_trees();
+    tree.merge(other, MergePolicy::KeepExisting).unwrap();
+
+    assert_eq!(tree.get_value(Some("server"), "port"), Some(&Value::Int(80)));
+    assert_eq!(tree.get_value(Some("server"), "timeout"), Some(&Value::Int(30)));
+}
+
+#[test]
+fn tree_merge_error() {
+    let (mut tree, other) = merge_trees();
+
+    match tree.merge(other, MergePolicy::Error) {
+        Err(Error::DuplicateKey(err)) => assert_eq!(format!("{}", err), "Duplicate key port in section server (already defined as port)"),
+        other                         => panic!("Wrong return value: {:?}", other),
+    }
+
+    // Nothing is merged on error
+    assert_eq!(tree.get_value(Some("server"), "port"), Some(&Value::Int(80)));
+    assert_eq!(tree.get_value(Some("server"), "timeout"), None);
+    assert!(tree.section(Some("log")).is_none());
+}
+
+#[test]
+fn tree_merge_error_without_conflict() {
+    let (mut tree, _) = merge_trees();
+    let mut other = Tree::new();
+    other.insert(Identifier::new(None, String::from("other")), Value::Int(2));
+
+    tree.merge(other, MergePolicy::Error).unwrap();
+    assert_eq!(tree.get_value(None, "other"), Some(&Value::Int(2)));
+}
+
+fn nested_tree() -> Tree {
+    let options = crate::parse::ParserOptions {
+        nested_sections: true,
+        ..crate::parse::ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+    for i in &["name = service", "[server]", "host = localhost", "[server.tls]", "enabled = on", "[server.tls.cert]", "path = a.pem", "[version\\.2]", "name = two"] {
+        parser.parse_line(i).unwrap();
+    }
+
+    parser.into_tree()
+}
+
+#[test]
+fn tree_get_subsection() {
+    let tree = nested_tree();
+
+    let tls = tree.get_subsection(&["server", "tls"]).unwrap();
+    assert_eq!(tls.name(), Some("server.tls"));
+    assert_eq!(tls.path(), vec!["server", "tls"]);
+    assert_eq!(tree.get_value(Some("server.tls"), "enabled"), Some(&Value::Bool(true)));
+
+    assert_eq!(tree.get_subsection(&["version.2"]).unwrap().path(), vec!["version.2"]);
+    assert!(tree.get_subsection(&["version", "2"]).is_none());
+    assert!(tree.get_subsection(&["tls"]).is_none());
+    assert_eq!(tree.get_subsection(&[]).unwrap().name(), None);
+}
+
+#[test]
+fn section_subsections() {
+    let tree = nested_tree();
+
+    let server = tree.get_subsection(&["server"]).unwrap();
+    let names: Vec<_> = server.subsections().iter().map(|i| i.name()).collect();
+    assert_eq!(names, vec![Some("server.tls")]);
+
+    let global = tree.section(None).unwrap();
+    let names: Vec<_> = global.subsections().iter().map(|i| i.name()).collect();
+    assert_eq!(names, vec![Some("server"), Some("version\\.2")]);
+}
+
+#[test]
+fn tree_remove_key() {
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(Some(String::from("a")), String::from("x")), Value::Int(1));
+    tree.insert(Identifier::new(Some(String::from("a")), String::from("y")), Value::Int(2));
+
+    assert_eq!(tree.remove_key(Some("a"), "x"), Some(Value::Int(1)));
+    assert_eq!(tree.remove_key(Some("a"), "x"), None);
+    assert_eq!(tree.remove_key(Some("missing"), "x"), None);
+    assert_eq!(tree.section(Some("a")).unwrap().keys().collect::<Vec<_>>(), vec![Identifier::new(Some(String::from("a")), String::from("y"))]);
+
+    assert_eq!(tree.remove_key(Some("a"), "y"), Some(Value::Int(2)));
+    assert!(tree.section(Some("a")).is_none());
+    assert_eq!(tree.sections().count(), 0);
+}
+
+#[test]
+fn tree_remove_section() {
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(None, String::from("name")), Value::Int(0));
+    tree.insert(Identifier::new(Some(String::from("a")), String::from("x")), Value::Int(1));
+    tree.insert(Identifier::new(Some(String::from("a")), String::from("y")), Value::Int(2));
+
+    let removed = tree.remove_section(Some("a")).unwrap();
+    assert_eq!(removed.name(), Some("a"));
+    assert_eq!(removed.entries().len(), 2);
+    assert_eq!(removed.get("y"), Some(&Value::Int(2)));
+
+    assert!(tree.remove_section(Some("a")).is_none());
+    assert_eq!(tree.get_value(Some("a"), "x"), None);
+    assert_eq!(tree.get_data().len(), 1);
+
+    let global = tree.remove_section(None).unwrap();
+    assert_eq!(global.into_entries(), vec![(Identifier::new(None, String::from("name")), Value::Int(0))]);
+    assert!(tree.get_data().is_empty());
+}
+
+#[test]
+fn tree_clear_values_and_clear() {
+    let options = crate::parse::ParserOptions {
+        keep_comments: true,
+        ..crate::parse::ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+    parser.parse_line("name = \"service\"").unwrap();
+    parser.parse_line("[server]").unwrap();
+    parser.parse_line("; The port").unwrap();
+    parser.parse_line("port = 80").unwrap();
+    let mut tree = parser.into_tree();
+
+    tree.clear_values();
+    let empty = Value::Str(String::new());
+    assert_eq!(tree.get_value(None, "name"), Some(&empty));
+    assert_eq!(tree.get_value(Some("server"), "port"), Some(&empty));
+    assert_eq!(tree.quote_of(None, "name"), None);
+    assert_eq!(tree.comments_for(Some("server"), Some("port")), Some(&[String::from("; The port")][..]));
+    assert_eq!(tree.sections().map(|i| i.name_owned()).collect::<Vec<_>>(), vec![None, Some(String::from("server"))]);
+
+    tree.clear();
+    assert!(tree.is_empty());
+    assert_eq!(tree.section_count(), 0);
+    assert_eq!(tree.comments_for(Some("server"), Some("port")), None);
+}
+
+#[test]
+fn tree_remove_case_insensitive() {
+    let mut data = HashMap::new();
+    data.insert(Identifier::new(Some(String::from("Server")), String::from("Port")), Value::Int(80));
+    let mut tree = Tree::with_case_insensitive(data).unwrap();
+
+    assert_eq!(tree.remove_key(Some("SERVER"), "port"), Some(Value::Int(80)));
+    assert!(tree.section(Some("server")).is_none());
+}
+
+fn iter_tree() -> Tree {
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(Some(String::from("a")), String::from("x")), Value::Int(1));
+    tree.insert(Identifier::new(None, String::from("g")), Value::Int(0));
+    tree.insert(Identifier::new(Some(String::from("b")), String::from("y")), Value::Int(2));
+    tree.insert(Identifier::new(Some(String::from("a")), String::from("z")), Value::Int(3));
+
+    tree
+}
+
+#[test]
+fn tree_iter() {
+    let tree = iter_tree();
+
+    let found: Vec<_> = tree.iter().map(|(section, key, value)| (section, key.name(), value)).collect();
+    assert_eq!(found, vec![
+        (None,      "g", &Value::Int(0)),
+        (Some("a"), "x", &Value::Int(1)),
+        (Some("a"), "z", &Value::Int(3)),
+        (Some("b"), "y", &Value::Int(2)),
+    ]);
+
+    let reversed: Vec<_> = tree.iter().rev().map(|(_, key, _)| key.name()).collect();
+    assert_eq!(reversed, vec!["y", "z", "x", "g"]);
+
+    assert_eq!((&tree).into_iter().count(), 4);
+    assert_eq!(Tree::new().iter().count(), 0);
+}
+
+#[test]
+fn tree_keys_in() {
+    let tree = iter_tree();
+
+    let keys: Vec<_> = tree.keys_in(Some("a")).map(|(key, value)| (key.name(), value)).collect();
+    assert_eq!(keys, vec![("x", &Value::Int(1)), ("z", &Value::Int(3))]);
+    assert_eq!(tree.keys_in(Some("a")).next_back().map(|(key, _)| key.name()), Some("z"));
+    assert_eq!(tree.keys_in(None).len(), 1);
+    assert_eq!(tree.keys_in(Some("missing")).count(), 0);
+}
+
+#[test]
+fn tree_sections_rev() {
+    let tree = iter_tree();
+
+    let names: Vec<_> = tree.sections().rev().map(|i| i.name()).collect();
+    assert_eq!(names, vec![Some("b"), Some("a"), None]);
+}
+
+#[test]
+fn tree_contains() {
+    let tree = iter_tree();
+
+    assert!(tree.contains_section(None));
+    assert!(tree.contains_section(Some("a")));
+    assert!(!tree.contains_section(Some("c")));
+
+    assert!(tree.contains_key(None, "g"));
+    assert!(tree.contains_key(Some("a"), "z"));
+    assert!(!tree.contains_key(Some("a"), "y"));
+    assert!(!tree.contains_key(Some("c"), "x"));
+    assert!(!tree.contains_key(Some("A"), "x"));
+}
+
+#[test]
+fn tree_contains_case_insensitive() {
+    let mut data = HashMap::new();
+    data.insert(Identifier::new(Some(String::from("Server")), String::from("Port")), Value::Int(80));
+    let tree = Tree::with_case_insensitive(data).unwrap();
+
+    assert!(tree.contains_section(Some("SERVER")));
+    assert!(tree.contains_key(Some("server"), "PORT"));
+    assert!(!tree.contains_key(None, "port"));
+}
+
+#[test]
+fn tree_eq() {
+    let mut a = Tree::new();
+    a.insert(Identifier::new(None, String::from("x")), Value::Int(1));
+    a.insert(Identifier::new(Some(String::from("s")), String::from("y")), Value::Bool(true));
+
+    let mut b = Tree::new();
+    b.insert(Identifier::new(Some(String::from("s")), String::from("y")), Value::Bool(true));
+    b.insert(Identifier::new(None, String::from("x")), Value::Int(1));
+    assert_eq!(a, b);
+
+    b.insert(Identifier::new(None, String::from("x")), Value::Float(1.0));
+    assert_ne!(a, b);
+}
+
+#[test]
+fn tree_section_view() {
+    let tree = typed_tree();
+    let section = tree.section(Some("types")).unwrap();
+
+    assert_eq!(section.get("int"), Some(&Value::Int(42)));
+    assert_eq!(section.get("missing"), None);
+    assert_eq!(section.len(), 5);
+    assert!(!section.is_empty());
+
+    let names: Vec<&str> = section.iter().map(|(ident, _)| ident.name()).collect();
+    assert_eq!(names, vec!["int", "float", "bool", "str", "raw"]);
+}
+
+#[test]
+fn tree_section_as_string_map() {
+    let tree = typed_tree();
+    let map = tree.section_as_string_map(Some("types")).unwrap();
+
+    assert_eq!(map.len(), 5);
+    assert_eq!(map["int"], "42");
+    assert_eq!(map["bool"], "true");
+
+    let mut data = HashMap::new();
+    data.insert(Identifier::new(Some(String::from("Server")), String::from("Hosts")), Value::Array(vec![Value::Int(1), Value::Raw(String::from("b"))]));
+    data.insert(Identifier::new(Some(String::from("Server")), String::from("Flag")), Value::Null);
+    let tree = Tree::with_case_insensitive(data).unwrap();
+
+    let map = tree.section_as_string_map(Some("server")).unwrap();
+    assert_eq!(map["Hosts"], "1, b");
+    assert_eq!(map["Flag"], "");
+    assert_eq!(tree.section_as_string_map(None), None);
+}
+
+#[test]
+fn tree_get_raw() {
+    let mut tree = typed_tree();
+    tree.insert(Identifier::new(None, String::from("list")), Value::Array(vec![Value::Int(1), Value::Bool(false)]));
+
+    assert_eq!(tree.get_raw(Some("types"), "int").as_deref(), Some("42"));
+    assert_eq!(tree.get_raw(Some("types"), "float").as_deref(), Some("0.5"));
+    assert_eq!(tree.get_raw(Some("types"), "bool").as_deref(), Some("true"));
+    assert_eq!(tree.get_raw(None, "list").as_deref(), Some("1, false"));
+    assert_eq!(tree.get_raw(None, "int"), None);
+
+    // The text is the same as the one of Value::raw
+    for (_, ident, value) in tree.iter() {
+        assert_eq!(tree.get_raw(ident.section(), ident.name()), Some(value.raw()));
+    }
+
+    // The strings are borrowed from the tree
+    assert!(matches!(tree.get_raw(Some("types"), "str"), Some(Cow::Borrowed("quoted"))));
+}
+
+#[test]
+fn tree_section_mut() {
+    let mut tree = typed_tree();
+    assert!(tree.section_mut(Some("missing")).is_none());
+
+    let mut section = tree.section_mut(Some("types")).unwrap();
+    assert_eq!(section.set("int", Value::Int(1)).unwrap(), Some(Value::Int(42)));
+    assert_eq!(section.set("new", Value::Int(2)).unwrap(), None);
+    assert!(section.set("bad key!", Value::Int(3)).is_err());
+    assert_eq!(section.remove("float"), Some(Value::Float(0.5)));
+    assert_eq!(section.remove("float"), None);
+    assert_eq!(section.len(), 5);
+
+    assert_eq!(tree.get_value(Some("types"), "int"), Some(&Value::Int(1)));
+    assert_eq!(tree.get_value(Some("types"), "new"), Some(&Value::Int(2)));
+    assert!(!tree.contains_key(Some("types"), "float"));
+}
+
+#[test]
+fn tree_len() {
+    let mut tree = Tree::new();
+    assert!(tree.is_empty());
+    assert_eq!(tree.section_count(), 0);
+
+    tree.insert(Identifier::new(Some(String::from("a")), String::from("x")), Value::Int(1));
+    tree.insert(Identifier::new(Some(String::from("a")), String::from("y")), Value::Int(2));
+    assert_eq!(tree.len(), 2);
+    assert_eq!(tree.section_count(), 1);
+
+    tree.remove_key(Some("a"), "x");
+    tree.remove_key(Some("a"), "y");
+    assert!(tree.is_empty());
+    assert_eq!(tree.section_count(), 0);
+}
+
+#[test]
+fn tree_push_to_array() {
+    let mut tree = typed_tree();
+
+    tree.push_to_array(Some("types"), "list", Value::Int(1)).unwrap();
+    tree.push_to_array(Some("types"), "list", Value::Bool(false)).unwrap();
+    assert_eq!(tree.get_value(Some("types"), "list"), Some(&Value::Array(vec![Value::Int(1), Value::Bool(false)])));
+
+    match tree.push_to_array(Some("types"), "int", Value::Int(2)) {
+        Err(Error::Type(err)) => {
+            assert_eq!(err.expected(), "array");
+            assert_eq!(err.found(), "int");
+        },
+        result                => panic!("An integer is not an array, got {:?}", result),
+    }
+    assert_eq!(tree.get_value(Some("types"), "int"), Some(&Value::Int(42)));
+
+    assert!(matches!(tree.push_to_array(Some("types"), "1key", Value::Int(3)), Err(Error::InvalidIdentifier(_))));
+    assert!(matches!(tree.push_to_array(Some("bad@section"), "list", Value::Int(3)), Err(Error::InvalidIdentifier(_))));
+    assert_eq!(tree.get_value(Some("types"), "1key"), None);
+}
+
+#[test]
+fn tree_rename_section() {
+    let options = crate::parse::ParserOptions {
+        keep_comments: true,
+        ..crate::parse::ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+    parser.parse_line("[first]").unwrap();
+    parser.parse_line("a = 1").unwrap();
+    parser.parse_line("; The old section").unwrap();
+    parser.parse_line("[old]").unwrap();
+    parser.parse_line("; The key").unwrap();
+    parser.parse_line("key = value").unwrap();
+    parser.parse_line("[last]").unwrap();
+    parser.parse_line("b = 2").unwrap();
+    let mut tree = parser.into_tree();
+
+    tree.rename_section("old", "new").unwrap();
+    let names: Vec<Option<&str>> = tree.sections().map(|i| i.name()).collect();
+    assert_eq!(names, vec![Some("first"), Some("new"), Some("last")]);
+    assert_eq!(tree.get_value(Some("new"), "key"), Some(&Value::Raw(String::from("value"))));
+    assert_eq!(tree.comments_for(Some("new"), None), Some(&[String::from("; The old section")][..]));
+    assert_eq!(tree.comments_for(Some("new"), Some("key")), Some(&[String::from("; The key")][..]));
+    assert!(!tree.contains_section(Some("old")));
+
+    // A missing section is ignored
+    tree.rename_section("old", "other").unwrap();
+    assert_eq!(tree.section_count(), 3);
+
+    match tree.rename_section("new", "last") {
+        Err(Error::DuplicateSection(err)) => assert_eq!(err.section(), "last"),
+        other                             => panic!("Expected a DuplicateSection, found {:?}", other),
+    }
+    assert!(matches!(tree.rename_section("new", "bad!"), Err(Error::InvalidIdentifier(_))));
+    assert_eq!(tree.get_value(Some("new"), "key"), Some(&Value::Raw(String::from("value"))));
+}
+
+#[test]
+fn tree_sections_with_comments() {
+    let (_, tree) = merge_trees();
+
+    let sections: Vec<(Option<&str>, &[String])> = tree.sections_with_comments()
+                                                       .map(|(section, comments)| (section.name(), comments))
+                                                       .collect();
+    assert_eq!(sections, vec![
+        (Some("server"), &[][..]),
+        (Some("log"), &[String::from("; The logs")][..]),
+    ]);
+
+    // The comments of a key other than the first one of the global section are not the comments of the section
+    let mut parser = Parser::with_options(crate::parse::ParserOptions {
+        keep_comments: true,
+        ..crate::parse::ParserOptions::default()
+    });
+    parser.parse_line("a = 1").unwrap();
+    parser.parse_line("; About b").unwrap();
+    parser.parse_line("b = 2").unwrap();
+    let tree = parser.into_tree();
+
+    let (section, comments) = tree.sections_with_comments().next_back().unwrap();
+    assert_eq!(section.name(), None);
+    assert!(comments.is_empty());
+}
+
+#[test]
+fn tree_clone_and_debug() {
+    let mut tree = typed_tree();
+    let snapshot = tree.clone();
+
+    tree.push_to_array(Some("types"), "list", Value::Int(1)).unwrap();
+    tree.remove_key(Some("types"), "int");
+    assert_ne!(tree, snapshot);
+    assert_eq!(snapshot.get_value(Some("types"), "int"), Some(&Value::Int(42)));
+    assert_eq!(snapshot.get_value(Some("types"), "list"), None);
+
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(Some(String::from("a")), String::from("x")), Value::Array(vec![Value::Bool(true)]));
+    assert_eq!(format!("{:?}", tree), r#"{Some("a"): {"x": Array([Bool(true)])}}"#);
+    assert_eq!(format!("{:?}", tree.section(Some("a")).unwrap()), r#"{"x": Array([Bool(true)])}"#);
+    assert_eq!(format!("{:?}", Tree::new()), "{}");
+}
+
+#[test]
+fn tree_keys_with_prefix_and_glob() {
+    let mut tree = Tree::new();
+    for i in &["plugin.a.enabled", "plugin.a.path", "plugin.bb.enabled", "plugins", "theme"] {
+        tree.insert(Identifier::new(Some(String::from("app")), String::from(*i)), Value::Bool(true));
+    }
+
+    fn names<'a>(iter: MatchingKeys<'a, '_>) -> Vec<&'a str> {
+        iter.map(Identifier::name).collect()
+    }
+    assert_eq!(names(tree.keys_with_prefix(Some("app"), "plugin.")), ["plugin.a.enabled", "plugin.a.path", "plugin.bb.enabled"]);
+    assert_eq!(names(tree.keys_with_prefix(Some("app"), "")).len(), 5);
+    assert!(names(tree.keys_with_prefix(None, "plugin")).is_empty());
+
+    assert_eq!(names(tree.keys_matching(Some("app"), "plugin.*.enabled")), ["plugin.a.enabled", "plugin.bb.enabled"]);
+    assert_eq!(names(tree.keys_matching(Some("app"), "*s")), ["plugins"]);
+    assert_eq!(names(tree.keys_matching(Some("app"), "theme")), ["theme"]);
+    assert_eq!(names(tree.keys_matching(Some("app"), "*")).len(), 5);
+    assert!(names(tree.keys_matching(Some("app"), "plugin.*.")).is_empty());
+
+    let data = tree.into_data();
+    let tree = Tree::with_case_insensitive(data).unwrap();
+    assert_eq!(names(tree.keys_matching(Some("APP"), "PLUGIN.A.*")), ["plugin.a.enabled", "plugin.a.path"]);
+    assert_eq!(names(tree.keys_with_prefix(Some("app"), "Plugins")), ["plugins"]);
+}
+
+#[test]
+fn tree_into_and_from_iter() {
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(Some(String::from("b")), String::from("y")), Value::Int(2));
+    tree.insert(Identifier::new(None, String::from("x")), Value::Int(1));
+    tree.insert(Identifier::new(Some(String::from("a")), String::from("z")), Value::Int(3));
+    tree.insert(Identifier::new(Some(String::from("b")), String::from("w")), Value::Int(4));
+
+    let entries: Vec<(Identifier, Value)> = tree.clone().into_iter().collect();
+    let names: Vec<&str> = entries.iter().map(|(ident, _)| ident.name()).collect();
+    assert_eq!(names, ["x", "y", "w", "z"]);
+
+    let rebuilt: Tree = entries.into_iter().collect();
+    assert_eq!(rebuilt, tree);
+    let sections: Vec<Option<&str>> = rebuilt.sections().map(|i| i.name()).collect();
+    assert_eq!(sections, [None, Some("b"), Some("a")]);
+
+    let doubled: Tree = tree.into_iter()
+                            .map(|(ident, value)| match value {
+                                Value::Int(n) => (ident, Value::Int(n * 2)),
+                                value         => (ident, value),
+                            })
+                            .chain(std::iter::once((Identifier::new(None, String::from("x")), Value::Int(0))))
+                            .collect();
+    assert_eq!(doubled.get_value(None, "x"), Some(&Value::Int(0)));
+    assert_eq!(doubled.get_value(Some("b"), "w"), Some(&Value::Int(8)));
+}
+
+#[test]
+fn tree_get_path() {
+    let mut tree = nested_tree();
+    tree.insert(Identifier::new(Some(String::from("server")), String::from("a.b")), Value::Int(1));
+
+    assert_eq!(tree.get_path("server.tls.enabled"), Some(&Value::Bool(true)));
+    assert_eq!(tree.get_path("server.tls"), None);
+    // The last dot ends the section
+    assert_eq!(tree.get_path("server.a.b"), None);
+    assert_eq!(tree.get_value(Some("server"), "a.b"), Some(&Value::Int(1)));
+    assert_eq!(tree.get_path("server."), None);
+    assert_eq!(tree.get_path(""), None);
+
+    // An empty section is the global one
+    assert_eq!(tree.get_path(".name"), Some(&Value::Raw(String::from("service"))));
+    assert_eq!(tree.get_path("."), None);
+}
+
+#[test]
+fn tree_normalize_is_idempotent() {
+    let options = crate::parse::ParserOptions {
+        keep_comments: true,
+        keep_formats: true,
+        array_separator: Some(','),
+        ..crate::parse::ParserOptions::default()
+    };
+    let content = "\
+    ; The service\n\
+    name   = 'my service'\n\
+    ratio  = 0.50\n\
+    [server]\n\
+    port = 0x1F90\n\
+    hosts = a.com, 'b;c'\n\
+    [a]\n\
+    key = second\\;\n";
+    let tree = crate::parse::parse_content_with_options(content, options).unwrap();
+
+    let read_back = crate::parse::ParserOptions {
+        array_separator: Some(','),
+        ..crate::parse::ParserOptions::default()
+    };
+
+    let normalized = tree.normalize(&read_back);
+    assert_eq!(normalized, "name='my service'\nratio=0.5\n\n[a]\nkey=second\\;\n\n[server]\nhosts=a.com,'b\\;c'\nport=8080\n");
+    let parsed = crate::parse::parse_content_with_options(&normalized, read_back.clone()).unwrap();
+    assert_eq!(parsed, tree);
+    assert_eq!(parsed.normalize(&read_back), normalized);
+}
+
+#[test]
+fn tree_normalize_follows_the_parser_options() {
+    let options = crate::parse::ParserOptions {
+        kv_separators: vec![':', '='],
+        global_section_name: Some(String::from("DEFAULT")),
+        ..crate::parse::ParserOptions::default()
+    };
+    let tree = crate::parse::parse_content_with_options("b: 2\na = 1\n[s]\nk: v\n", options.clone()).unwrap();
+
+    let normalized = tree.normalize(&options);
+    assert_eq!(normalized, "[DEFAULT]\na:1\nb:2\n\n[s]\nk:v\n");
+    let parsed = crate::parse::parse_content_with_options(&normalized, options.clone()).unwrap();
+    assert_eq!(parsed, tree);
+    assert_eq!(parsed.normalize(&options), normalized);
+}
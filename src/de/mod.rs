@@ -0,0 +1,221 @@
+//! Deserializes a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") into any type implementing `serde::Deserialize`. Requires the `serde` feature
+//! 
+//! A `Tree` is seen as a map whose entries are the keys of the global section, followed by the named sections. Each section is itself a map of its keys. The values keep their INI type (see [`Value`](../datas/enum.Value.html "datas::Value")): an `Int` can be read as any integer or float, a `Bool` as a `bool`, a `Str` or a `Raw` as a string or a unit enum variant and an `Array` as a sequence
+//! 
+//! # Example
+//! ```
+//! use mininip::parse::Parser;
+//! use mininip::de::from_tree;
+//! use serde::Deserialize;
+//! 
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     name: String,
+//!     server: Server,
+//! }
+//! 
+//! #[derive(Deserialize)]
+//! struct Server {
+//!     port: u16,
+//!     verbose: Option<bool>,
+//! }
+//! 
+//! let mut parser = Parser::new();
+//! parser.parse_line("name = 'my service'").unwrap();
+//! parser.parse_line("[server]").unwrap();
+//! parser.parse_line("port = 8080").unwrap();
+//! let tree = parser.into_tree();
+//! 
+//! let config: Config = from_tree(&tree).unwrap();
+//! assert_eq!(config.name, "my service");
+//! assert_eq!(config.server.port, 8080);
+//! assert_eq!(config.server.verbose, None);
+//! ```
+
+use crate::datas::{Value, tree::{Tree, Section}};
+use crate::errors::SerdeError;
+use serde::de::{self, Deserialize, DeserializeSeed, Visitor, MapAccess, SeqAccess, IntoDeserializer};
+use serde::forward_to_deserialize_any;
+
+/// Deserializes `tree` into a `T`
+/// 
+/// # Return value
+/// `Ok(value)` with `value` as the data of `tree`
+/// 
+/// `Err(error)` with `error` as a [`SerdeError`](../errors/struct.SerdeError.html "errors::SerdeError") if `tree` does not match with `T`. If the error is due to a key, `error` knows this key and its section
+pub fn from_tree<'de, T: Deserialize<'de>>(tree: &'de Tree) -> Result<T, SerdeError> {
+    let mut entries = Vec::new();
+    let mut sections = Vec::new();
+    for i in tree.sections() {
+        match i.name() {
+            None       => entries = keys_of(tree, &i),
+            Some(name) => sections.push((name, Entry::Section(keys_of(tree, &i)))),
+        }
+    }
+    entries.extend(sections);
+
+    T::deserialize(MapDeserializer::new(None, entries))
+}
+
+/// Returns the keys of `section` associated to their values
+fn keys_of<'de>(tree: &'de Tree, section: &Section<'de>) -> Vec<(&'de str, Entry<'de>)> {
+    section.keys()
+           .map(|ident| {
+               let (ident, value) = tree.get_data()
+                                        .get_key_value(&ident)
+                                        .expect("The keys of a section are in its tree");
+               (ident.name(), Entry::Value(value))
+           })
+           .collect()
+}
+
+
+/// An entry of a map, which is either the value of a key or a section
+enum Entry<'de> {
+    Value(&'de Value),
+    Section(Vec<(&'de str, Entry<'de>)>),
+}
+
+/// A deserializer over either a whole `Tree` or a single section
+struct MapDeserializer<'de> {
+    /// The section deserialized, `None` for the global section which also contains the named ones
+    section: Option<&'de str>,
+    entries: std::vec::IntoIter<(&'de str, Entry<'de>)>,
+    /// The entry whose key was just deserialized
+    current: Option<(&'de str, Entry<'de>)>,
+}
+
+impl<'de> MapDeserializer<'de> {
+    fn new(section: Option<&'de str>, entries: Vec<(&'de str, Entry<'de>)>) -> MapDeserializer<'de> {
+        MapDeserializer {
+            section,
+            entries: entries.into_iter(),
+            current: None,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for MapDeserializer<'de> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        let section = self.section;
+        visitor.visit_map(self)
+               .map_err(|err| err.locate(section, None))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer<'de> {
+    type Error = SerdeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, SerdeError> {
+        let (key, entry) = match self.entries.next() {
+            Some(val) => val,
+            None      => return Ok(None),
+        };
+        self.current = Some((key, entry));
+
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value, SerdeError> {
+        let (key, entry) = self.current.take()
+                                       .expect("`next_value_seed` is called after `next_key_seed`");
+
+        match entry {
+            Entry::Value(value)     => seed.deserialize(ValueDeserializer { value })
+                                           .map_err(|err| err.locate(self.section, Some(key))),
+            Entry::Section(entries) => seed.deserialize(MapDeserializer::new(Some(key), entries)),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+
+/// A deserializer over a single value
+struct ValueDeserializer<'de> {
+    value: &'de Value,
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.value {
+            Value::Raw(string)   => visitor.visit_borrowed_str(string),
+            Value::Str(string)   => visitor.visit_borrowed_str(string),
+            Value::Int(number)   => visitor.visit_i64(*number),
+            Value::Float(number) => visitor.visit_f64(*number),
+            Value::Bool(boolean) => visitor.visit_bool(*boolean),
+            Value::Array(values) => visitor.visit_seq(SeqDeserializer { values: values.iter() }),
+            Value::Color(..)     => visitor.visit_string(self.value.raw().into_owned()),
+            Value::Null          => visitor.visit_unit(),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _           => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, SerdeError> {
+        match self.value {
+            Value::Raw(string) | Value::Str(string) => visitor.visit_enum(string.as_str().into_deserializer()),
+            _                                       => self.deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+
+/// A deserializer over the elements of an `Array`
+struct SeqDeserializer<'de> {
+    values: std::slice::Iter<'de, Value>,
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
+    type Error = SerdeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, SerdeError> {
+        match self.values.next() {
+            Some(value) => seed.deserialize(ValueDeserializer { value }).map(Some),
+            None        => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+
+#[cfg(test)]
+mod tests;
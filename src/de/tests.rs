@@ -0,0 +1,145 @@
+use crate::de::*;
+use crate::parse::{Parser, ParserOptions};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+fn tree_of(content: &str) -> Tree {
+    let options = ParserOptions {
+        array_separator: Some(','),
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+    for i in content.lines() {
+        parser.parse_line(i).unwrap();
+    }
+
+    parser.into_tree()
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+enum Level {
+    Debug,
+    Info,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Server {
+    host: String,
+    port: u16,
+    ratio: f64,
+    verbose: bool,
+    level: Level,
+    aliases: Vec<String>,
+    timeout: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    name: String,
+    server: Server,
+}
+
+#[test]
+fn from_tree_struct() {
+    let tree = tree_of("\
+    name = 'my service'\n\
+    [server]\n\
+    host = localhost\n\
+    port = 8080\n\
+    ratio = 1\n\
+    verbose = yes\n\
+    level = Info\n\
+    aliases = a.com, b.com\n");
+
+    let config: Config = from_tree(&tree).unwrap();
+    assert_eq!(config, Config {
+        name: String::from("my service"),
+        server: Server {
+            host: String::from("localhost"),
+            port: 8080,
+            ratio: 1.0,
+            verbose: true,
+            level: Level::Info,
+            aliases: vec![String::from("a.com"), String::from("b.com")],
+            timeout: None,
+        },
+    });
+}
+
+#[test]
+fn from_tree_maps() {
+    let tree = tree_of("\
+    [a]\n\
+    x = 1\n\
+    [b]\n\
+    y = 2\n");
+
+    let data: HashMap<String, HashMap<String, i64>> = from_tree(&tree).unwrap();
+    assert_eq!(data["a"]["x"], 1);
+    assert_eq!(data["b"]["y"], 2);
+}
+
+#[test]
+fn from_tree_missing_key() {
+    let tree = tree_of("\
+    name = service\n\
+    [server]\n\
+    host = localhost\n");
+
+    let err = from_tree::<Config>(&tree).unwrap_err();
+    assert_eq!(err.section(), Some("server"));
+    assert_eq!(err.key(), Some("port"));
+    assert_eq!(format!("{}", err), "Missing value for key port in section server");
+}
+
+#[test]
+fn from_tree_missing_section() {
+    let tree = tree_of("name = service\n");
+
+    let err = from_tree::<Config>(&tree).unwrap_err();
+    assert_eq!(err.section(), None);
+    assert_eq!(err.key(), Some("server"));
+}
+
+#[test]
+fn from_tree_wrong_type() {
+    let tree = tree_of("\
+    name = service\n\
+    [server]\n\
+    host = localhost\n\
+    port = eighty\n");
+
+    let err = from_tree::<Config>(&tree).unwrap_err();
+    assert_eq!(err.section(), Some("server"));
+    assert_eq!(err.key(), Some("port"));
+}
+
+#[test]
+fn from_tree_borrows() {
+    #[derive(Deserialize)]
+    struct Borrowed<'a> {
+        name: &'a str,
+    }
+
+    let tree = tree_of("name = service\n");
+    let borrowed: Borrowed = from_tree(&tree).unwrap();
+    assert_eq!(borrowed.name, "service");
+}
+
+#[test]
+fn from_tree_null_is_none() {
+    #[derive(Deserialize)]
+    struct Flags {
+        verbose: Option<bool>,
+        quiet: Option<bool>,
+    }
+
+    let options = ParserOptions {
+        bare_keys: true,
+        ..ParserOptions::default()
+    };
+    let tree = crate::parse::parse_content_with_options("verbose\nquiet = yes\n", options).unwrap();
+    let flags: Flags = from_tree(&tree).unwrap();
+    assert_eq!(flags.verbose, None);
+    assert_eq!(flags.quiet, Some(true));
+}
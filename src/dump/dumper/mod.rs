@@ -1,9 +1,17 @@
 //! Provides a `Dumper` structure which creates a new INI file content
 
-use crate::datas::{Identifier, Value};
-use std::collections::{hash_map, HashMap};
+use crate::prelude::*;
+use crate::datas::{Identifier, Value, tree::{Tree, Section, Quote}};
+use crate::parse::{BoolTokens, from_default_escapes, to_percent_escapes};
+use super::{DumpOptions, BoolFormat, dump_str};
+use alloc::borrow::Cow;
+use crate::collections::hash_map;
+use core::convert::Infallible;
+#[cfg(feature = "std")]
 use std::path::Path;
+#[cfg(feature = "std")]
 use std::fs::File;
+#[cfg(feature = "std")]
 use std::io::{self, Write};
 
 /// A stated object, which from couples of [`Identifier`](../datas/struct.Identifier.html "datas::Identifier") and [`Value`](../datas/enum.Value.html "datas::Value"), creates a new INI tree, directly dumpable into a new file
@@ -53,12 +61,12 @@ impl Dumper {
 
     /// Dumps a couple [`Identifier`](../datas/struct.Identifier.html "datas::Identifier") / [`Value`](../datas/enum.Value.html "datas::Value") into `self`
     pub fn dump(&mut self, identifier: Identifier, value: Value) {
-        let line = format!("{}={}", identifier.name(), value.dump());
-
-        let key = match identifier.section() {
-            Some(val) => Some(String::from(val)),
-            None      => None,
+        let line = match value {
+            Value::Null => String::from(identifier.name()),
+            value       => format!("{}={}", identifier.name(), dump_multiline(&value)),
         };
+
+        let key = identifier.section().map(String::from);
         match self.tree.entry(key) {
             hash_map::Entry::Occupied(mut entry) => entry.get_mut().push(line),
             hash_map::Entry::Vacant(entry)       => { entry.insert(vec![line]); },
@@ -109,6 +117,12 @@ impl Dumper {
     }
 }
 
+impl Default for Dumper {
+    fn default() -> Self {
+        Dumper::new()
+    }
+}
+
 /// Dumps a `HashMap<Identifier, Value>` into a file
 /// 
 /// # Parameters
@@ -118,6 +132,7 @@ impl Dumper {
 /// 
 /// # Return value
 /// Since any [`Dumper`](struct.Dumper.html "dump::Dumper") operation is infallible, it only returns an `io::Result<()>` which indicates a file manipulation error
+#[cfg(feature = "std")]
 pub fn dump_into_file<T: AsRef<Path>>(path: T, data: HashMap<Identifier, Value>) -> io::Result<()> {
     let mut file = File::create(path)?;
     let mut dumper = Dumper::new();
@@ -126,10 +141,396 @@ pub fn dump_into_file<T: AsRef<Path>>(path: T, data: HashMap<Identifier, Value>)
         dumper.dump(k, v);
     }
 
-    file.write(dumper.generate().as_bytes())?;
+    file.write_all(dumper.generate().as_bytes())?;
+    Ok(())
+}
+
+/// Dumps a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") into a `String`
+/// 
+/// Unlike a [`Dumper`](struct.Dumper.html "dump::Dumper"), the sections and keys are dumped in the order they are iterated by `tree` and the comments it retains are dumped before the element they are attached to
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{Parser, ParserOptions};
+/// use mininip::dump::dump_tree;
+/// 
+/// let content = "\
+/// ; The answer to life, the universe and everything\n\
+/// answer=42\n\
+/// \n\
+/// ; Mathematical constants\n\
+/// [maths]\n\
+/// pi=3.14159\n";
+/// 
+/// let options = ParserOptions {
+///     keep_comments: true,
+///     ..ParserOptions::default()
+/// };
+/// let mut parser = Parser::with_options(options);
+/// for i in content.lines() {
+///     parser.parse_line(i).unwrap();
+/// }
+/// 
+/// assert_eq!(dump_tree(&parser.into_tree()), content);
+/// ```
+pub fn dump_tree(tree: &Tree) -> String {
+    dump_tree_with_options(tree, DumpOptions::default())
+}
+
+/// Dumps a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") into a `String` following `options`
+/// 
+/// # See
+/// [`dump_tree`](fn.dump_tree.html "dump::dump_tree") for the default behaviour and [`DumpOptions`](struct.DumpOptions.html "dump::DumpOptions") for the available options
+/// 
+/// # Panics
+/// Panics if `options.global_section_name` is an invalid identifier according to [`Identifier::is_valid`](../datas/struct.Identifier.html#method.is_valid "datas::Identifier::is_valid")
+pub fn dump_tree_with_options(tree: &Tree, options: DumpOptions) -> String {
+    dump_tree_filtered_with_options(tree, options, |_, _, _| true)
+}
+
+/// Dumps the keys of a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") for which `predicate` returns `true` into a `String`
+/// 
+/// `predicate` is given the section of each key (`None` for the global section), its identifier and its value. A section whose keys are all filtered out is not dumped, nor are the comments attached to the elements filtered out
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::Parser;
+/// use mininip::dump::dump_tree_filtered;
+/// 
+/// let mut parser = Parser::new();
+/// parser.parse_line("name = service").unwrap();
+/// parser.parse_line("[secrets]").unwrap();
+/// parser.parse_line("password = hunter2").unwrap();
+/// let tree = parser.into_tree();
+/// 
+/// let dumped = dump_tree_filtered(&tree, |section, _, _| section != Some("secrets"));
+/// assert_eq!(dumped, "name=service\n");
+/// ```
+pub fn dump_tree_filtered<F: Fn(Option<&str>, &Identifier, &Value) -> bool>(tree: &Tree, predicate: F) -> String {
+    dump_tree_filtered_with_options(tree, DumpOptions::default(), predicate)
+}
+
+/// Dumps the keys of a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") for which `predicate` returns `true` into a `String` following `options`
+/// 
+/// # See
+/// [`dump_tree_filtered`](fn.dump_tree_filtered.html "dump::dump_tree_filtered") for the filtering and [`dump_tree_with_options`](fn.dump_tree_with_options.html "dump::dump_tree_with_options") for the options
+/// 
+/// # Panics
+/// Panics if `options.global_section_name` is an invalid identifier according to [`Identifier::is_valid`](../datas/struct.Identifier.html#method.is_valid "datas::Identifier::is_valid")
+pub fn dump_tree_filtered_with_options<F: Fn(Option<&str>, &Identifier, &Value) -> bool>(tree: &Tree, options: DumpOptions, predicate: F) -> String {
+    let sections = sections_to_dump(tree, &options);
+    let sections = sections.iter()
+                           .map(|section| dump_section(tree, section, &options, &predicate));
+
+    let mut result = String::new();
+    let written: Result<(), Infallible> = write_sections(tree, sections, &options, |chunk| {
+        result.push_str(chunk);
+        Ok(())
+    });
+    match written {
+        Ok(())   => result,
+        Err(err) => match err {},
+    }
+}
+
+/// Dumps a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") into `writer` following `options`, without building the whole text in memory
+/// 
+/// The text written is the one returned by [`dump_tree_with_options`](fn.dump_tree_with_options.html "dump::dump_tree_with_options"), written section after section
+/// 
+/// # Return value
+/// `Ok(())` in case of success
+/// 
+/// `Err(error)` with `error` as the first I/O error returned by `writer`. Nothing more is written after it
+/// 
+/// # Example
+/// ```
+/// use mininip::datas::{tree::Tree, Identifier, Value};
+/// use mininip::dump::{dump_tree, dump_tree_to, DumpOptions};
+/// 
+/// let mut tree = Tree::new();
+/// tree.insert(Identifier::new(Some(String::from("server")), String::from("port")), Value::Int(8080));
+/// 
+/// let mut output = Vec::new();
+/// dump_tree_to(&tree, &mut output, DumpOptions::default()).unwrap();
+/// assert_eq!(output, dump_tree(&tree).into_bytes());
+/// ```
+/// 
+/// # Panics
+/// Panics if `options.global_section_name` is an invalid identifier according to [`Identifier::is_valid`](../datas/struct.Identifier.html#method.is_valid "datas::Identifier::is_valid")
+/// 
+/// # Note
+/// Requires the `std` feature
+#[cfg(feature = "std")]
+pub fn dump_tree_to<W: Write>(tree: &Tree, mut writer: W, options: DumpOptions) -> io::Result<()> {
+    let sections = sections_to_dump(tree, &options);
+    let sections = sections.iter()
+                           .map(|section| dump_section(tree, section, &options, &|_, _, _| true));
+
+    write_sections(tree, sections, &options, |chunk| writer.write_all(chunk.as_bytes()))?;
+    writer.flush()
+}
+
+/// Dumps a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") into a `String` like [`dump_tree`](fn.dump_tree.html "dump::dump_tree"), formatting its sections concurrently. Requires the `rayon` feature
+/// 
+/// The sections are formatted into separate buffers on the threads of `rayon` and concatenated in their order, so the output is exactly the one of `dump_tree`. It is only worth it for trees holding a lot of keys
+/// 
+/// # Example
+/// ```
+/// use mininip::datas::{tree::Tree, Identifier, Value};
+/// use mininip::dump::{dump_tree, dump_tree_parallel};
+/// 
+/// let mut tree = Tree::new();
+/// for i in 0..100 {
+///     let ident = Identifier::new(Some(format!("section{}", i % 10)), format!("key{}", i));
+///     tree.insert(ident, Value::Int(i));
+/// }
+/// 
+/// assert_eq!(dump_tree_parallel(&tree), dump_tree(&tree));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn dump_tree_parallel(tree: &Tree) -> String {
+    dump_tree_parallel_with_options(tree, DumpOptions::default())
+}
+
+/// Dumps a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") into a `String` following `options` like [`dump_tree_with_options`](fn.dump_tree_with_options.html "dump::dump_tree_with_options"), formatting its sections concurrently. Requires the `rayon` feature
+/// 
+/// # See
+/// [`dump_tree_parallel`](fn.dump_tree_parallel.html "dump::dump_tree_parallel")
+/// 
+/// # Panics
+/// Panics if `options.global_section_name` is an invalid identifier according to [`Identifier::is_valid`](../datas/struct.Identifier.html#method.is_valid "datas::Identifier::is_valid")
+#[cfg(feature = "rayon")]
+pub fn dump_tree_parallel_with_options(tree: &Tree, options: DumpOptions) -> String {
+    use rayon::prelude::*;
+
+    let sections = sections_to_dump(tree, &options);
+    // `collect` keeps the order of the sections
+    let sections: Vec<String> = sections.par_iter()
+                                        .map(|section| dump_section(tree, section, &options, &|_, _, _| true))
+                                        .collect();
+
+    let mut result = String::new();
+    let written: Result<(), Infallible> = write_sections(tree, sections.into_iter(), &options, |chunk| {
+        result.push_str(chunk);
+        Ok(())
+    });
+    match written {
+        Ok(())   => result,
+        Err(err) => match err {},
+    }
+}
+
+/// Returns the sections of `tree` in the order they are dumped following `options`
+/// 
+/// # Panics
+/// Panics if `options.global_section_name` is an invalid identifier according to [`Identifier::is_valid`](../datas/struct.Identifier.html#method.is_valid "datas::Identifier::is_valid")
+fn sections_to_dump<'a>(tree: &'a Tree, options: &DumpOptions) -> Vec<Section<'a>> {
+    if let Some(name) = &options.global_section_name {
+        assert!(Identifier::is_valid(name), "{} is not a valid section name", name);
+    }
+
+    let mut sections: Vec<_> = tree.sections().collect();
+    if options.sort {
+        // `None` is lower than any `Some` so the global section stays first
+        sections.sort_by(|a, b| a.name().cmp(&b.name()));
+    }
+
+    sections
+}
+
+/// Dumps the keys of `section` for which `predicate` returns `true`, with their comments, followed by the blank line separating it from the next section. The result is empty if all its keys are filtered out
+fn dump_section<F: Fn(Option<&str>, &Identifier, &Value) -> bool>(tree: &Tree, section: &Section, options: &DumpOptions, predicate: &F) -> String {
+    let mut result = String::new();
+
+    let mut keys: Vec<Identifier> = section.keys()
+                                           .filter(|i| predicate(i.section(), i, &tree.get_data()[i]))
+                                           .collect();
+    if keys.is_empty() {
+        return result;
+    } else if options.sort {
+        keys.sort();
+    }
+
+    if let Some(name) = section.name() {
+        push_comments(&mut result, tree.comments_for(Some(name), None));
+
+        result.push('[');
+        match tree.nested_path(name) {
+            Some(path) => {
+                let path: Vec<String> = path.iter()
+                                            .map(|i| i.replace('.', "\\."))
+                                            .collect();
+                result.push_str(&path.join("."));
+            },
+            None       => result.push_str(name),
+        }
+        if let Some(parent) = tree.parent_of(name) {
+            result.push_str(" : ");
+            result.push_str(parent);
+        }
+        result.push_str("]\n");
+    } else if let Some(name) = &options.global_section_name {
+        result.push('[');
+        result.push_str(name);
+        result.push_str("]\n");
+    }
+
+    // The identifiers are ASCII so their number of characters is their display width
+    let width = if options.align_values {
+        keys.iter()
+            .map(|i| i.name().chars().count())
+            .max()
+            .unwrap_or(0)
+    } else {
+        0
+    };
+
+    for key in keys {
+        push_comments(&mut result, tree.comments_for(key.section(), Some(key.name())));
+
+        // A `Null` is dumped as a bare key
+        if tree.get_data()[&key].is_null() {
+            result.push_str(key.name());
+        } else {
+            result.push_str(&format!("{:width$}", key.name(), width = width));
+            result.push(options.kv_separator);
+            let value = dump_value(tree, &key, options.bool_format);
+            match &options.escapes {
+                // The text of a triple-quoted value is verbatim
+                _ if value.starts_with("\"\"\"")     => result.push_str(&value),
+                _ if options.percent_escapes => result.push_str(&to_percent_escapes(&value)),
+                Some(escapes)                => result.push_str(&from_default_escapes(&value, escapes.table())),
+                None                         => result.push_str(&value),
+            }
+        }
+        result.push('\n');
+    }
+
+    // The retained blank lines already separate the sections as in the parsed file
+    if !tree.keeps_blank_lines() {
+        result.push('\n');
+    }
+
+    result
+}
+
+/// Passes the sections of `tree` dumped by `dump_section`, followed by the trailing comments of `tree`, to `write` chunk by chunk with the line ending of `options` applied
+/// 
+/// The blank line ending each section is only written before the next one, so that the text does not end with it
+/// 
+/// # Return value
+/// `Ok(())` in case of success
+/// 
+/// `Err(error)` with `error` as the first error returned by `write`. It is not called anymore after it
+fn write_sections<I, E, W>(tree: &Tree, sections: I, options: &DumpOptions, mut write: W) -> Result<(), E>
+where
+    I: Iterator<Item = String>,
+    W: FnMut(&str) -> Result<(), E>,
+{
+    // The values and the comments never contain a line break, except the verbatim text of a multi-line value whose lines are read the same way
+    let mut write = |chunk: &str| match options.line_ending.as_str() {
+        "\n"        => write(chunk),
+        line_ending => write(&chunk.replace('\n', line_ending)),
+    };
+
+    let mut pending_blank_line = false;
+    for section in sections.filter(|i| !i.is_empty()) {
+        if pending_blank_line {
+            write("\n")?;
+        }
+
+        // The retained blank lines already separate the sections as in the parsed file
+        if tree.keeps_blank_lines() {
+            write(&section)?;
+        } else {
+            write(&section[..section.len() - 1])?;
+            pending_blank_line = true;
+        }
+    }
+
+    let trailing = tree.trailing_comments();
+    if !trailing.is_empty() {
+        let mut comments = String::new();
+        if pending_blank_line {
+            comments.push('\n');
+        }
+        push_comments(&mut comments, Some(trailing));
+        write(&comments)?;
+    }
+
     Ok(())
 }
 
+/// Returns the value of `key` in `tree` formatted to be dumped. The text retained while parsing is preferred if it still denotes the same value, including a spelling of a `Bool` among the default [`BoolTokens`](../parse/struct.BoolTokens.html "parse::BoolTokens"), written in lowercase
+fn dump_value(tree: &Tree, key: &Identifier, bool_format: BoolFormat) -> String {
+    let value = &tree.get_data()[key];
+
+    match tree.format_of(key) {
+        Some(format) if Value::parse(format).ok().as_ref() == Some(value) => String::from(format),
+        Some(format) if is_bool_spelling(format, value)                   => format.trim().to_ascii_lowercase(),
+        _                                                                 => match (value, tree.quote_of(key.section(), key.name())) {
+            (Value::Str(string), Some(quote)) => dump_quoted(string, quote),
+            (value, _)                        => dump_multiline(&spell_bools(value, bool_format)),
+        },
+    }
+}
+
+/// Returns `value` with its `Bool`s, itself or its elements, replaced by their spelling in `bool_format` as `Raw` strings so that `Value::dump` writes them this way
+fn spell_bools(value: &Value, bool_format: BoolFormat) -> Cow<'_, Value> {
+    match value {
+        Value::Bool(boolean) if bool_format != BoolFormat::TrueFalse => Cow::Owned(Value::Raw(String::from(bool_format.as_str(*boolean)))),
+        Value::Array(array) if bool_format != BoolFormat::TrueFalse  => {
+            let array = array.iter()
+                             .map(|i| spell_bools(i, bool_format).into_owned())
+                             .collect();
+            Cow::Owned(Value::Array(array))
+        },
+        value                                                    => Cow::Borrowed(value),
+    }
+}
+
+/// Formats `string` between `quote` as read by a [`Parser`](../parse/struct.Parser.html "parse::Parser"). A `Str` which can not be written between triple quotes is dumped like [`Value::dump`](../datas/enum.Value.html#method.dump "datas::Value::dump")
+fn dump_quoted(string: &str, quote: Quote) -> String {
+    match quote {
+        Quote::Single                           => format!("'{}'", dump_str(string)),
+        Quote::Double                           => format!("\"{}\"", dump_str(string)),
+        Quote::Triple if string.contains('\n') => dump_multiline(&Value::Str(String::from(string))),
+        Quote::Triple if fits_in_block(string)  => format!("\"\"\"{}\"\"\"", string),
+        Quote::Triple                           => format!("'{}'", dump_str(string)),
+    }
+}
+
+/// Returns `true` if `string` can be written verbatim between triple quotes `"""`
+fn fits_in_block(string: &str) -> bool {
+    !string.contains("\"\"\"") && !string.contains('\r') && !string.ends_with('"')
+}
+
+/// Formats `value` like [`Value::dump`](../datas/enum.Value.html#method.dump "datas::Value::dump"), except a `Str` containing a line break which is dumped verbatim between triple quotes `"""` spanning several lines, as read by a [`Parser`](../parse/struct.Parser.html "parse::Parser")
+/// 
+/// A `Str` which can not be written this way, because it contains a `"""`, a carriage return or ends with a `"`, is dumped on a single line
+fn dump_multiline(value: &Value) -> String {
+    match value {
+        Value::Str(string) if string.contains('\n') && fits_in_block(string) => format!("\"\"\"\n{}\"\"\"", string),
+        value                                                              => value.dump(),
+    }
+}
+
+/// Returns `true` if `format` is a default spelling of the `Bool` `value`
+fn is_bool_spelling(format: &str, value: &Value) -> bool {
+    match value {
+        Value::Bool(boolean) => BoolTokens::default().parse(format.trim()) == Some(*boolean),
+        _                    => false,
+    }
+}
+
+/// Pushes each line of `comments` into `result`
+fn push_comments(result: &mut String, comments: Option<&[String]>) {
+    for i in comments.unwrap_or_default() {
+        result.push_str(i);
+        result.push('\n');
+    }
+}
+
 
 #[cfg(test)]
 mod tests;
@@ -1,5 +1,8 @@
+#![allow(clippy::approx_constant)]
+
 use crate::dump::dumper::*;
 use crate::datas::{Identifier, Value};
+use crate::dump::LineEnding;
 
 #[test]
 fn dumper_without_globals() {
@@ -26,10 +29,10 @@ fn dumper_without_globals() {
     [abc]\n\
     a=1\n\
     b=3.1415926535\n\
-    c=on\n\
+    c=true\n\
     \n\
     [def]\n\
-    d=off\n\
+    d=false\n\
     e='5'\n\
     f=abc\n";
 
@@ -59,10 +62,10 @@ fn dumper_with_globals() {
     let expected = "\
     a=1\n\
     b=3.1415926535\n\
-    c=on\n\
+    c=true\n\
     \n\
     [def]\n\
-    d=off\n\
+    d=false\n\
     e='5'\n\
     f=abc\n";
 
@@ -80,3 +83,491 @@ fn dumper_with_escape() {
 
     assert_eq!("ident=\\:D \\= \\x00263a\n", dumper.generate());
 }
+
+#[test]
+fn dump_tree_like_dumper() {
+    let mut data = std::collections::HashMap::new();
+
+    data.insert(Identifier::new(None, String::from("b")), Value::Int(2));
+    data.insert(Identifier::new(None, String::from("a")), Value::Int(1));
+    data.insert(Identifier::new(Some(String::from("def")), String::from("d")), Value::Bool(false));
+    data.insert(Identifier::new(Some(String::from("abc")), String::from("c")), Value::Str(String::from("5")));
+
+    let mut dumper = Dumper::new();
+    for (k, v) in data.clone() {
+        dumper.dump(k, v);
+    }
+
+    assert_eq!(dump_tree(&Tree::from(data)), dumper.generate());
+}
+
+#[test]
+fn dump_tree_with_comments() {
+    let content = "\
+    ; Global comment\n\
+    ident=val\n\
+    \n\
+    ; Section comment\n\
+    ; on two lines\n\
+    [section]\n\
+    ; Key comment\n\
+    key=true\n\
+    \n\
+    ; Trailing comment\n";
+
+    let options = crate::parse::ParserOptions {
+        keep_comments: true,
+        ..crate::parse::ParserOptions::default()
+    };
+    let mut parser = crate::parse::Parser::with_options(options);
+    for i in content.lines() {
+        parser.parse_line(i).unwrap();
+    }
+
+    assert_eq!(dump_tree(&parser.into_tree()), content);
+}
+
+#[test]
+fn dump_tree_keeps_formats() {
+    let content = "\
+    mask=0xFF\n\
+    mode=0o755\n\
+    flag=yes\n\
+    changed=0b11\n";
+
+    let options = crate::parse::ParserOptions {
+        keep_formats: true,
+        ..crate::parse::ParserOptions::default()
+    };
+    let mut parser = crate::parse::Parser::with_options(options);
+    for i in content.lines() {
+        parser.parse_line(i).unwrap();
+    }
+
+    let mut tree = parser.into_tree();
+    tree.insert(Identifier::new(None, String::from("changed")), Value::Int(4));
+
+    assert_eq!(dump_tree(&tree), "mask=0xFF\nmode=0o755\nflag=yes\nchanged=4\n");
+}
+
+#[test]
+fn dump_tree_forgets_formats_by_default() {
+    let mut parser = crate::parse::Parser::new();
+    parser.parse_line("mask=0xFF").unwrap();
+
+    assert_eq!(dump_tree(&parser.into_tree()), "mask=255\n");
+}
+
+#[test]
+fn dump_tree_sorted() {
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(Some(String::from("b")), String::from("z")), Value::Int(1));
+    tree.insert(Identifier::new(Some(String::from("b")), String::from("a")), Value::Int(2));
+    tree.insert(Identifier::new(Some(String::from("B")), String::from("key")), Value::Int(3));
+    tree.insert(Identifier::new(None, String::from("global")), Value::Int(4));
+    tree.insert(Identifier::new(Some(String::from("a")), String::from("key")), Value::Int(5));
+
+    let expected = "\
+    global=4\n\
+    \n\
+    [B]\n\
+    key=3\n\
+    \n\
+    [a]\n\
+    key=5\n\
+    \n\
+    [b]\n\
+    a=2\n\
+    z=1\n";
+
+    let options = DumpOptions {
+        sort: true,
+        ..DumpOptions::default()
+    };
+    assert_eq!(dump_tree_with_options(&tree, options), expected);
+}
+
+#[test]
+fn dump_tree_unsorted_by_default() {
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(Some(String::from("b")), String::from("z")), Value::Int(1));
+    tree.insert(Identifier::new(Some(String::from("a")), String::from("a")), Value::Int(2));
+
+    assert_eq!(dump_tree(&tree), "[b]\nz=1\n\n[a]\na=2\n");
+}
+
+#[test]
+fn dump_tree_empty() {
+    assert_eq!(dump_tree(&Tree::new()), "");
+}
+
+#[test]
+fn dump_tree_nested_sections() {
+    let options = crate::parse::ParserOptions {
+        nested_sections: true,
+        ..crate::parse::ParserOptions::default()
+    };
+    let mut parser = crate::parse::Parser::with_options(options.clone());
+    let content = "[server.tls]\nenabled=true\n\n[version\\.2]\nname=two\n";
+    for i in content.lines() {
+        parser.parse_line(i).unwrap();
+    }
+    let tree = parser.into_tree();
+
+    assert_eq!(dump_tree(&tree), content);
+}
+
+#[test]
+fn dump_tree_after_removal() {
+    let mut parser = crate::parse::Parser::new();
+    for i in "a=1\n[b]\nx=2\n[c]\ny=3\nz=4\n".lines() {
+        parser.parse_line(i).unwrap();
+    }
+    let mut tree = parser.into_tree();
+
+    tree.remove_key(Some("b"), "x");
+    tree.remove_key(Some("c"), "z");
+    tree.remove_section(None);
+
+    assert_eq!(dump_tree(&tree), "[c]\ny=3\n");
+}
+
+#[test]
+fn dump_tree_kv_separator() {
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(None, String::from("key")), Value::Int(1));
+
+    let options = DumpOptions {
+        kv_separator: ':',
+        ..DumpOptions::default()
+    };
+    assert_eq!(dump_tree_with_options(&tree, options), "key:1\n");
+}
+
+#[test]
+fn dump_tree_global_section_name() {
+    let content = "[DEFAULT]\nkey=1\n\n[other]\nkey=2\n";
+    let options = crate::parse::ParserOptions {
+        global_section_name: Some(String::from("DEFAULT")),
+        ..crate::parse::ParserOptions::default()
+    };
+    let mut parser = crate::parse::Parser::with_options(options);
+    for i in content.lines() {
+        parser.parse_line(i).unwrap();
+    }
+    let tree = parser.into_tree();
+    assert_eq!(tree.get_value(None, "key"), Some(&Value::Int(1)));
+
+    let options = DumpOptions {
+        global_section_name: Some(String::from("DEFAULT")),
+        ..DumpOptions::default()
+    };
+    assert_eq!(dump_tree_with_options(&tree, options), content);
+}
+
+#[test]
+#[should_panic]
+fn dump_tree_invalid_global_section_name() {
+    let options = DumpOptions {
+        global_section_name: Some(String::from("1nvalid")),
+        ..DumpOptions::default()
+    };
+    dump_tree_with_options(&Tree::new(), options);
+}
+
+#[test]
+fn dump_tree_align_values() {
+    let mut parser = crate::parse::Parser::new();
+    for i in "a=1\nlonger=2\n[s]\nkey=3\nk=4\n".lines() {
+        parser.parse_line(i).unwrap();
+    }
+    let tree = parser.into_tree();
+
+    let options = DumpOptions {
+        align_values: true,
+        ..DumpOptions::default()
+    };
+    assert_eq!(dump_tree_with_options(&tree, options), "a     =1\nlonger=2\n\n[s]\nkey=3\nk  =4\n");
+}
+
+#[test]
+fn dump_tree_bool_spellings() {
+    let options = crate::parse::ParserOptions {
+        keep_formats: true,
+        bool_tokens: Some(crate::parse::BoolTokens::default()),
+        ..crate::parse::ParserOptions::default()
+    };
+    let mut parser = crate::parse::Parser::with_options(options);
+    for i in "a=TRUE\nb=0\nc=Yes\n".lines() {
+        parser.parse_line(i).unwrap();
+    }
+    let mut tree = parser.into_tree();
+    // The spellings kept are written in lowercase
+    assert_eq!(dump_tree(&tree), "a=true\nb=0\nc=yes\n");
+
+    tree.insert(Identifier::new(None, String::from("a")), Value::Bool(false));
+    assert_eq!(dump_tree(&tree), "a=false\nb=0\nc=yes\n");
+}
+
+#[test]
+fn dump_tree_filtered_omits_empty_sections() {
+    let options = crate::parse::ParserOptions {
+        keep_comments: true,
+        ..crate::parse::ParserOptions::default()
+    };
+    let mut parser = crate::parse::Parser::with_options(options);
+    for i in "a=1\n; Secrets\n[s]\n; The password\npassword=x\n[t]\nuser=me\n; The token\ntoken=y\n".lines() {
+        parser.parse_line(i).unwrap();
+    }
+    let tree = parser.into_tree();
+
+    let dumped = dump_tree_filtered(&tree, |_, key, _| key.name() != "password" && key.name() != "token");
+    assert_eq!(dumped, "a=1\n\n[t]\nuser=me\n");
+}
+
+#[test]
+fn dump_tree_empty_values() {
+    let mut parser = crate::parse::Parser::new();
+    for i in "cleared =\ncommented = ;nothing\nquoted = ''\n".lines() {
+        parser.parse_line(i).unwrap();
+    }
+    let tree = parser.into_tree();
+    assert_eq!(tree.get_value(None, "cleared"), Some(&Value::Raw(String::new())));
+    assert_eq!(tree.get_value(None, "commented"), Some(&Value::Raw(String::new())));
+    assert_eq!(tree.get_value(None, "quoted"), Some(&Value::Str(String::new())));
+
+    let dumped = dump_tree(&tree);
+    assert_eq!(dumped, "cleared=\ncommented=\nquoted=''\n");
+
+    let mut parser = crate::parse::Parser::new();
+    for i in dumped.lines() {
+        parser.parse_line(i).unwrap();
+    }
+    assert_eq!(parser.into_tree(), tree);
+}
+
+#[test]
+fn dump_tree_multiline_values() {
+    let mut tree = Tree::new();
+    let script = Identifier::new(None, String::from("script"));
+    let quoted = Identifier::new(None, String::from("quoted"));
+    tree.insert(script, Value::Str(String::from("echo 'a = b'\n; not a comment\n")));
+    tree.insert(quoted, Value::Str(String::from("ends with\n\"")));
+
+    let dumped = dump_tree(&tree);
+    assert_eq!(dumped, "script=\"\"\"\necho 'a = b'\n; not a comment\n\"\"\"\nquoted='ends with\\n\\\"'\n");
+
+    let parsed = crate::parse::parse_content(&dumped).unwrap();
+    assert_eq!(parsed, tree);
+}
+
+#[test]
+fn dump_tree_line_endings() {
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(None, String::from("a")), Value::Int(1));
+    tree.insert(Identifier::new(Some(String::from("s")), String::from("b")), Value::Str(String::from("x\ny")));
+
+    let dump = |line_ending| dump_tree_with_options(&tree, DumpOptions {
+        line_ending,
+        ..DumpOptions::default()
+    });
+
+    let lf = dump(LineEnding::Lf);
+    let crlf = dump(LineEnding::CrLf);
+    assert_eq!(lf.as_bytes(), &b"a=1\n\n[s]\nb=\"\"\"\nx\ny\"\"\"\n"[..]);
+    assert_eq!(crlf.as_bytes(), &b"a=1\r\n\r\n[s]\r\nb=\"\"\"\r\nx\r\ny\"\"\"\r\n"[..]);
+    if cfg!(windows) {
+        assert_eq!(dump(LineEnding::Native), crlf);
+    } else {
+        assert_eq!(dump(LineEnding::Native), lf);
+    }
+
+    assert_eq!(LineEnding::detect(&crlf), LineEnding::CrLf);
+    assert_eq!(LineEnding::detect(&lf), LineEnding::Lf);
+    assert_eq!(crate::parse::parse_content(&crlf).unwrap(), tree);
+}
+
+#[test]
+fn dump_tree_blank_lines_round_trip() {
+    use crate::parse::{Parser, ParserOptions};
+
+    let content = "\
+    name=service\n\
+    \n\
+    \n\
+    ; The server\n\
+    [server]\n\
+    host=localhost\n\
+    \n\
+    ; In seconds\n\
+    timeout=30\n\
+    [client]\n\
+    retries=3\n\
+    \n\
+    ; The end\n";
+
+    let options = ParserOptions {
+        keep_comments: true,
+        keep_blank_lines: true,
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+    for i in content.lines() {
+        parser.parse_line(i).unwrap();
+    }
+
+    assert_eq!(dump_tree(&parser.into_tree()), content);
+}
+
+#[test]
+fn dump_tree_null_values() {
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(None, String::from("verbose")), Value::Null);
+    tree.insert(Identifier::new(None, String::from("empty")), Value::Str(String::new()));
+
+    let dump = dump_tree(&tree);
+    assert_eq!(dump, "verbose\nempty=''\n");
+
+    let options = crate::parse::ParserOptions {
+        bare_keys: true,
+        ..crate::parse::ParserOptions::default()
+    };
+    assert_eq!(crate::parse::parse_content_with_options(&dump, options).unwrap(), tree);
+
+    let mut dumper = Dumper::new();
+    dumper.dump(Identifier::new(None, String::from("verbose")), Value::Null);
+    assert_eq!(dumper.generate(), "verbose\n");
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn dump_tree_parallel_matches_dump_tree() {
+    let mut tree = Tree::new();
+    for i in 0..200 {
+        let section = if i % 7 == 0 { None } else { Some(format!("s{}", i % 13)) };
+        tree.insert(Identifier::new(section, format!("key{}", i)), Value::Int(i));
+    }
+    tree.insert(Identifier::new(None, String::from("null")), Value::Null);
+    assert_eq!(dump_tree_parallel(&tree), dump_tree(&tree));
+
+    let options = DumpOptions {
+        sort: true,
+        align_values: true,
+        global_section_name: Some(String::from("DEFAULT")),
+        line_ending: LineEnding::CrLf,
+        ..DumpOptions::default()
+    };
+    assert_eq!(dump_tree_parallel_with_options(&tree, options.clone()), dump_tree_with_options(&tree, options));
+}
+
+#[test]
+fn dump_tree_to_partial_writes() {
+    use std::io::{self, Write};
+
+    /// A writer accepting a single byte per call and failing once `limit` bytes are written
+    struct Trickle {
+        written: Vec<u8>,
+        limit: usize,
+    }
+
+    impl Write for Trickle {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.written.len() >= self.limit {
+                return Err(io::Error::other("disk full"));
+            }
+            self.written.extend(buf.iter().take(1));
+            Ok(buf.len().min(1))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(None, String::from("name")), Value::Raw(String::from("service")));
+    tree.insert(Identifier::new(Some(String::from("server")), String::from("port")), Value::Int(8080));
+    let options = DumpOptions {
+        line_ending: LineEnding::CrLf,
+        ..DumpOptions::default()
+    };
+    let expected = dump_tree_with_options(&tree, options.clone());
+
+    let mut writer = Trickle { written: Vec::new(), limit: usize::MAX };
+    dump_tree_to(&tree, &mut writer, options.clone()).unwrap();
+    assert_eq!(writer.written, expected.as_bytes());
+
+    let mut writer = Trickle { written: Vec::new(), limit: 10 };
+    let err = dump_tree_to(&tree, &mut writer, options).unwrap_err();
+    assert_eq!(err.to_string(), "disk full");
+    assert_eq!(writer.written, &expected.as_bytes()[..10]);
+}
+
+#[test]
+fn dump_tree_keeps_quotes() {
+    let content = "\
+    a=\"double\"\n\
+    b='single'\n\
+    c=\"\"\"triple\"\"\"\n";
+
+    let mut tree = crate::parse::parse_content(content).unwrap();
+    assert_eq!(dump_tree(&tree), content);
+
+    tree.insert(Identifier::new(None, String::from("a")), Value::Str(String::from("changed")));
+    tree.insert(Identifier::new(None, String::from("c")), Value::Str(String::from("two\nlines")));
+    assert_eq!(dump_tree(&tree), "a=\"changed\"\nb='single'\nc=\"\"\"\ntwo\nlines\"\"\"\n");
+}
+
+#[test]
+fn dump_tree_keeps_section_parents() {
+    let options = crate::parse::ParserOptions {
+        section_inheritance: true,
+        ..crate::parse::ParserOptions::default()
+    };
+    let content = "\
+    [default]\n\
+    port=80\n\
+    \n\
+    [dev : default]\n\
+    port=8080\n\
+    debug=true\n";
+
+    let tree = crate::parse::parse_content_with_options(content, options.clone()).unwrap();
+    assert_eq!(dump_tree(&tree), content);
+    assert_eq!(crate::parse::parse_content_with_options(&dump_tree(&tree), options).unwrap(), tree);
+}
+
+#[test]
+fn dump_tree_bool_format_round_trip() {
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(None, String::from("verbose")), Value::Bool(true));
+    tree.insert(Identifier::new(None, String::from("flags")), Value::Array(vec![Value::Bool(false), Value::Int(2)]));
+
+    let formats = [
+        (BoolFormat::OnOff, "on", "off"),
+        (BoolFormat::TrueFalse, "true", "false"),
+        (BoolFormat::TitleCase, "True", "False"),
+        (BoolFormat::YesNo, "yes", "no"),
+        (BoolFormat::OneZero, "1", "0"),
+    ];
+    for &(format, truthy, falsy) in &formats {
+        let options = DumpOptions {
+            bool_format: format,
+            ..DumpOptions::default()
+        };
+        let content = dump_tree_with_options(&tree, options);
+        assert_eq!(content, format!("verbose={}\nflags={},2\n", truthy, falsy));
+
+        let options = crate::parse::ParserOptions {
+            array_separator: Some(','),
+            bool_tokens: format.tokens(),
+            ..crate::parse::ParserOptions::default()
+        };
+        assert_eq!(crate::parse::parse_content_with_options(&content, options).unwrap().get_data(), tree.get_data());
+    }
+
+    // The default spelling is read back by the default parser
+    let content = dump_tree(&tree);
+    assert_eq!(content, "verbose=true\nflags=false,2\n");
+    assert_eq!(crate::parse::parse_content(&content).unwrap().get_data()[&Identifier::new(None, String::from("verbose"))], Value::Bool(true));
+}
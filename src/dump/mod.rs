@@ -1,5 +1,8 @@
 //! Provides tools to generate a INI file from any data
 
+use crate::prelude::*;
+use crate::parse::{EscapeTable, DefaultEscapes};
+
 /// Formats a `&str` by escaping special characters
 /// 
 /// # Return value
@@ -13,7 +16,7 @@
 /// More escaped characters may be found at [Wikipedia](https://en.wikipedia.org/wiki/INI_file#Escape_characters "INI file")
 /// 
 /// # The Unicode special case
-/// A non-ASCII character is escaped as a `\x??????` with exactly 6 hexadecimal digits even if a smaller number is suitable
+/// A non-ASCII or non-printable character is escaped as a `\x??????` with exactly 6 hexadecimal digits even if a smaller number is suitable
 /// 
 /// # Examples
 /// ```
@@ -26,27 +29,15 @@ pub fn dump_str(content: &str) -> String {
     let mut new = String::with_capacity(content.len());
 
     for i in content.chars() {
-        match i {
+        match DefaultEscapes.encode(i) {
             // Those characters have a special rule to be escaped
-            '\\'   => new.push_str(r"\\"),
-            '\''   => new.push_str("\\'"),
-            '"'    => new.push_str("\\\""),
-            '\0'   => new.push_str("\\0"),
-            '\x07' => new.push_str("\\a"),
-            '\x08' => new.push_str("\\b"),
-            '\t'   => new.push_str("\\t"),
-            '\r'   => new.push_str("\\r"),
-            '\n'   => new.push_str("\\n"),
-            ';'    => new.push_str("\\;"),
-            '#'    => new.push_str("\\#"),
-            '='    => new.push_str("\\="),
-            ':'    => new.push_str("\\:"),
-
-            // The ASCII characters are left unchanged
-            _ if i.is_ascii() => new.push_str(&format!("{}", i)),
-
-            // The non-ASCII characters are escaped with `\x??????`
-            _ => new.push_str(&format!("\\x{:06x}", i as u32)),
+            Some(escape) => new.push_str(&escape),
+
+            // The printable ASCII characters are left unchanged
+            None if i.is_ascii() && !i.is_ascii_control() => new.push(i),
+
+            // The non-ASCII and the non-printable characters are escaped with `\x??????`
+            None => new.push_str(&format!("\\x{:06x}", i as u32)),
         }
     }
 
@@ -54,6 +45,9 @@ pub fn dump_str(content: &str) -> String {
 }
 
 
+mod options;
+pub use options::*;
+
 mod dumper;
 pub use dumper::*;
 
@@ -0,0 +1,190 @@
+//! Contains the definition of [`DumpOptions`](struct.DumpOptions.html "dump::DumpOptions")
+
+use crate::prelude::*;
+use crate::parse::{Escapes, BoolTokens};
+
+/// The options changing the output of [`dump_tree_with_options`](fn.dump_tree_with_options.html "dump::dump_tree_with_options")
+/// 
+/// # Example
+/// ```
+/// use mininip::dump::{dump_tree_with_options, DumpOptions};
+/// use mininip::parse::Parser;
+/// 
+/// let mut parser = Parser::new();
+/// parser.parse_line("b = 2").unwrap();
+/// parser.parse_line("a = 1").unwrap();
+/// 
+/// let options = DumpOptions {
+///     sort: true,
+///     ..DumpOptions::default()
+/// };
+/// assert_eq!(dump_tree_with_options(&parser.into_tree(), options), "a=1\nb=2\n");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DumpOptions {
+    /// Dumps the sections, then the keys inside each section, sorted by the byte order of their names instead of the order of the [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree"). The global section always comes first. Defaults to `false`
+    pub sort: bool,
+    /// The character written between a key and its value, such as `:` to dump `key:value`. Defaults to `=`
+    pub kv_separator: char,
+    /// The name of a section header written before the keys of the global section, such as `DEFAULT` for the tools which require every key to be in a section. It must be a valid identifier (see [`Identifier::is_valid`](../datas/struct.Identifier.html#method.is_valid "datas::Identifier::is_valid")) and [`ParserOptions::global_section_name`](../parse/struct.ParserOptions.html#structfield.global_section_name "parse::ParserOptions::global_section_name") reads it back as the global section. Defaults to `None`
+    pub global_section_name: Option<String>,
+    /// Pads the keys with spaces so that the separators of a section are aligned in a column, as in `host   =a.com` above `timeout=5`. The spaces are ignored once parsed back unless [`ParserOptions::trim_whitespace`](../parse/struct.ParserOptions.html#structfield.trim_whitespace "parse::ParserOptions::trim_whitespace") is `false`. Defaults to `false`
+    pub align_values: bool,
+    /// The line break ending every line dumped, including the blank lines between the sections (see [`LineEnding`](enum.LineEnding.html "dump::LineEnding")). Defaults to `LineEnding::Lf`
+    pub line_ending: LineEnding,
+    /// The escape sequences made of a backslash followed by a single character written in the values, or `None` for the ones described in the README (see [`EscapeTable`](../parse/trait.EscapeTable.html "parse::EscapeTable")). A character which must be escaped but is unknown to the table is escaped with its code point. It should match [`ParserOptions::escapes`](../parse/struct.ParserOptions.html#structfield.escapes "parse::ParserOptions::escapes") to read the file back. Defaults to `None`
+    pub escapes: Option<Escapes>,
+    /// Writes every character which must be escaped in a value, and every `%`, as the percent escapes of the bytes of its UTF-8 encoding, such as `%0A` for a line break, instead of a backslash escape. It takes precedence over [`escapes`](struct.DumpOptions.html#structfield.escapes "dump::DumpOptions::escapes") and [`ParserOptions::percent_escapes`](../parse/struct.ParserOptions.html#structfield.percent_escapes "parse::ParserOptions::percent_escapes") reads the file back. Defaults to `false`
+    pub percent_escapes: bool,
+    /// The spelling of the `Bool` values, including the elements of an array (see [`BoolFormat`](enum.BoolFormat.html "dump::BoolFormat")). The text retained while parsing is still preferred if it spells the same value. Defaults to `BoolFormat::TrueFalse`
+    pub bool_format: BoolFormat,
+}
+
+impl Default for DumpOptions {
+    fn default() -> Self {
+        DumpOptions {
+            sort: false,
+            kv_separator: '=',
+            global_section_name: None,
+            align_values: false,
+            line_ending: LineEnding::Lf,
+            escapes: None,
+            percent_escapes: false,
+            bool_format: BoolFormat::TrueFalse,
+        }
+    }
+}
+
+impl DumpOptions {
+    /// Returns the options of the canonical form of a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") (see [`Tree::normalize`](../datas/tree/struct.Tree.html#method.normalize "datas::tree::Tree::normalize")): the sections and the keys are sorted, the lines end with `LineEnding::Lf` and the keys are neither aligned nor separated from their values by spaces
+    pub fn canonical() -> DumpOptions {
+        DumpOptions {
+            sort: true,
+            kv_separator: '=',
+            global_section_name: None,
+            align_values: false,
+            line_ending: LineEnding::Lf,
+            escapes: None,
+            percent_escapes: false,
+            bool_format: BoolFormat::TrueFalse,
+        }
+    }
+}
+
+/// The line break written by a dumper, used by [`DumpOptions::line_ending`](struct.DumpOptions.html#structfield.line_ending "dump::DumpOptions::line_ending"). A [`Parser`](../parse/struct.Parser.html "parse::Parser") reads both of them
+/// 
+/// # Example
+/// ```
+/// use mininip::dump::{dump_tree_with_options, DumpOptions, LineEnding};
+/// use mininip::parse::parse_content;
+/// 
+/// let content = "[server]\r\nport = 80\r\n";
+/// let options = DumpOptions {
+///     line_ending: LineEnding::detect(content),
+///     ..DumpOptions::default()
+/// };
+/// 
+/// assert_eq!(dump_tree_with_options(&parse_content(content).unwrap(), options), "[server]\r\nport=80\r\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`, as on Unix
+    Lf,
+    /// `\r\n`, as on Windows
+    CrLf,
+    /// The line break of the platform the program is compiled for, which is `CrLf` on Windows and `Lf` elsewhere
+    Native,
+}
+
+impl LineEnding {
+    /// Returns the line break written, `"\n"` or `"\r\n"`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf                      => "\n",
+            LineEnding::CrLf                    => "\r\n",
+            LineEnding::Native if cfg!(windows) => "\r\n",
+            LineEnding::Native                  => "\n",
+        }
+    }
+
+    /// Returns the line break used by most lines of `content`, `LineEnding::Lf` if there is not any line break
+    pub fn detect(content: &str) -> LineEnding {
+        let crlf = content.matches("\r\n").count();
+        let lf = content.matches('\n').count() - crlf;
+
+        if crlf > lf {
+            LineEnding::CrLf
+        } else {
+            LineEnding::Lf
+        }
+    }
+}
+
+/// The spelling of a [`Value::Bool`](../datas/enum.Value.html "datas::Value") written by a dumper, used by [`DumpOptions::bool_format`](struct.DumpOptions.html#structfield.bool_format "dump::DumpOptions::bool_format")
+/// 
+/// A [`Parser`](../parse/struct.Parser.html "parse::Parser") reads `TrueFalse`, `OnOff` and `YesNo` back with its default options. The other formats require [`ParserOptions::bool_tokens`](../parse/struct.ParserOptions.html#structfield.bool_tokens "parse::ParserOptions::bool_tokens"), as given by [`tokens`](enum.BoolFormat.html#method.tokens "dump::BoolFormat::tokens")
+/// 
+/// # Example
+/// ```
+/// use mininip::dump::{dump_tree_with_options, DumpOptions, BoolFormat};
+/// use mininip::parse::{parse_content, parse_content_with_options, ParserOptions};
+/// 
+/// let tree = parse_content("verbose = on\n").unwrap();
+/// let options = DumpOptions {
+///     bool_format: BoolFormat::TitleCase,
+///     ..DumpOptions::default()
+/// };
+/// let content = dump_tree_with_options(&tree, options);
+/// assert_eq!(content, "verbose=True\n");
+/// 
+/// let options = ParserOptions {
+///     bool_tokens: BoolFormat::TitleCase.tokens(),
+///     ..ParserOptions::default()
+/// };
+/// assert_eq!(parse_content_with_options(&content, options).unwrap(), tree);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoolFormat {
+    /// `on` and `off`
+    OnOff,
+    /// `true` and `false`, as written by [`Value::dump`](../datas/enum.Value.html#method.dump "datas::Value::dump")
+    TrueFalse,
+    /// `True` and `False`, as in Python
+    TitleCase,
+    /// `yes` and `no`
+    YesNo,
+    /// `1` and `0`
+    OneZero,
+}
+
+impl BoolFormat {
+    /// Returns the spelling of `boolean`
+    pub fn as_str(self, boolean: bool) -> &'static str {
+        match (self, boolean) {
+            (BoolFormat::OnOff, true)      => "on",
+            (BoolFormat::OnOff, false)     => "off",
+            (BoolFormat::TrueFalse, true)  => "true",
+            (BoolFormat::TrueFalse, false) => "false",
+            (BoolFormat::TitleCase, true)  => "True",
+            (BoolFormat::TitleCase, false) => "False",
+            (BoolFormat::YesNo, true)      => "yes",
+            (BoolFormat::YesNo, false)     => "no",
+            (BoolFormat::OneZero, true)    => "1",
+            (BoolFormat::OneZero, false)   => "0",
+        }
+    }
+
+    /// Returns the tokens a [`Parser`](../parse/struct.Parser.html "parse::Parser") needs in [`ParserOptions::bool_tokens`](../parse/struct.ParserOptions.html#structfield.bool_tokens "parse::ParserOptions::bool_tokens") to read `self` back, or `None` if the default options already read it
+    /// 
+    /// # Note
+    /// With the tokens of `BoolFormat::OneZero`, an `Int` written `1` or `0` is read as a `Bool` too
+    pub fn tokens(self) -> Option<BoolTokens> {
+        match self {
+            BoolFormat::TrueFalse | BoolFormat::OnOff | BoolFormat::YesNo => None,
+            format                                                    => Some(BoolTokens {
+                truthy: vec![String::from(format.as_str(true))],
+                falsy: vec![String::from(format.as_str(false))],
+            }),
+        }
+    }
+}
@@ -35,3 +35,15 @@ fn dump_str_complementary_test() {
     assert_eq!(dump_str("très_content=☺ ; the symbol of hapiness"), "tr\\x0000e8s_content\\=\\x00263a \\; the symbol of hapiness");
 }
 
+
+#[test]
+fn dump_str_control_characters() {
+    assert_eq!(dump_str("\x01\x1b[0m\x7f"), "\\x000001\\x00001b[0m\\x00007f");
+}
+
+#[test]
+fn dump_str_round_trip() {
+    for i in &["\u{1f600} emoji", "\x01\x02\x03abcdef", "\u{7f}\u{80}\u{ff}", "\u{10ffff}"] {
+        assert_eq!(crate::parse::parse_str(&dump_str(i)).unwrap(), *i);
+    }
+}
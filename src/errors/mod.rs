@@ -1,7 +1,10 @@
 //! This module contains several error error types and their implementations
 
+use crate::prelude::*;
+#[cfg(feature = "std")]
 use std::error;
-use std::fmt::{self, Display};
+use core::fmt::{self, Display};
+#[cfg(feature = "std")]
 use std::io;
 
 /// Represents a parsing error in the INI format
@@ -13,9 +16,34 @@ pub enum Error {
     UnexpectedToken(error_kinds::UnexpectedToken),
     InvalidEscape(error_kinds::InvalidEscape),
     InvalidIdentifier(error_kinds::InvalidIdentifier),
+    DuplicateKey(error_kinds::DuplicateKey),
+    RedefinedKey(error_kinds::RedefinedKey),
+    InvalidAnnotation(error_kinds::InvalidAnnotation),
+    DuplicateSection(error_kinds::DuplicateSection),
+    UndefinedVariable(error_kinds::UndefinedVariable),
+    IntegerOverflow(error_kinds::IntegerOverflow),
+    LimitExceeded(error_kinds::LimitExceeded),
+    IncludeCycle(error_kinds::IncludeCycle),
+    UnknownParent(error_kinds::UnknownParent),
+    InheritanceCycle(error_kinds::InheritanceCycle),
+    /// An error built by a program using this library (see [`Custom`](error_kinds/struct.Custom.html "errors::error_kinds::Custom"))
+    Custom(error_kinds::Custom),
+    /// A value of an unexpected type found while editing a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") (see [`TypeError`](struct.TypeError.html "errors::TypeError"))
+    Type(TypeError),
+    /// An I/O error of the source read, such as a reader failing. Requires the `std` feature
+    #[cfg(feature = "std")]
+    Io(io::Error),
 }
 
-impl error::Error for Error {}
+#[cfg(feature = "std")]
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _              => None,
+        }
+    }
+}
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -26,6 +54,102 @@ impl Display for Error {
             Error::UnexpectedToken(err)    => write!(f, "{}", err),
             Error::InvalidEscape(err)      => write!(f, "{}", err),
             Error::InvalidIdentifier(err)  => write!(f, "{}", err),
+            Error::DuplicateKey(err)       => write!(f, "{}", err),
+            Error::RedefinedKey(err)       => write!(f, "{}", err),
+            Error::InvalidAnnotation(err)  => write!(f, "{}", err),
+            Error::DuplicateSection(err)   => write!(f, "{}", err),
+            Error::UndefinedVariable(err)  => write!(f, "{}", err),
+            Error::IntegerOverflow(err)    => write!(f, "{}", err),
+            Error::LimitExceeded(err)      => write!(f, "{}", err),
+            Error::IncludeCycle(err)       => write!(f, "{}", err),
+            Error::UnknownParent(err)      => write!(f, "{}", err),
+            Error::InheritanceCycle(err)   => write!(f, "{}", err),
+            Error::Custom(err)             => write!(f, "{}", err),
+            Error::Type(err)               => write!(f, "{}", err),
+            #[cfg(feature = "std")]
+            Error::Io(err)                 => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error {
+    /// Returns the number (starting at 1) of the line where the error occured in the file parsed, or `None` if unknown
+    /// 
+    /// The line number is only known for the errors returned while parsing a file such as with [`parse_file`](../parse/fn.parse_file.html "parse::parse_file")
+    pub fn line_number(&self) -> Option<usize> {
+        match self {
+            Error::ExpectedIdentifier(err) => err.line_number(),
+            Error::ExpectedToken(err)      => err.line_number(),
+            Error::ExpectedEscape(err)     => err.line_number(),
+            Error::UnexpectedToken(err)    => err.line_number(),
+            Error::InvalidEscape(err)      => err.line_number(),
+            Error::InvalidIdentifier(err)  => err.line_number(),
+            Error::DuplicateKey(err)       => err.line_number(),
+            Error::RedefinedKey(err)       => err.line_number(),
+            Error::InvalidAnnotation(err)  => err.line_number(),
+            Error::DuplicateSection(err)   => err.line_number(),
+            Error::UndefinedVariable(err)  => err.line_number(),
+            Error::IntegerOverflow(err)    => err.line_number(),
+            Error::LimitExceeded(err)      => err.line_number(),
+            Error::IncludeCycle(err)       => err.line_number(),
+            Error::UnknownParent(err)      => err.line_number(),
+            Error::InheritanceCycle(err)   => err.line_number(),
+            Error::Custom(err)             => err.line_number(),
+            Error::Type(_)                 => None,
+            #[cfg(feature = "std")]
+            Error::Io(_)                   => None,
+        }
+    }
+
+    /// Returns the column (starting at 1 and counted in characters) where the error occured in its line, or `None` if the error is not bound to a position
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            Error::ExpectedIdentifier(err) => Some(err.column()),
+            Error::ExpectedToken(err)      => Some(err.column()),
+            Error::ExpectedEscape(err)     => Some(err.column()),
+            Error::UnexpectedToken(err)    => Some(err.column()),
+            Error::InvalidEscape(err)      => Some(err.column()),
+            Error::InvalidIdentifier(err)  => Some(err.column()),
+            Error::DuplicateKey(_)         => None,
+            Error::RedefinedKey(_)         => None,
+            Error::InvalidAnnotation(err)  => Some(err.column()),
+            Error::DuplicateSection(_)     => None,
+            Error::UndefinedVariable(err)  => Some(err.column()),
+            Error::IntegerOverflow(err)    => Some(err.column()),
+            Error::LimitExceeded(_)        => None,
+            Error::IncludeCycle(err)       => Some(err.column()),
+            Error::UnknownParent(err)      => Some(err.column()),
+            Error::InheritanceCycle(err)   => Some(err.column()),
+            Error::Custom(err)             => err.column(),
+            Error::Type(_)                 => None,
+            #[cfg(feature = "std")]
+            Error::Io(_)                   => None,
+        }
+    }
+
+    /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+    pub fn with_line_number(self, line_number: usize) -> Error {
+        match self {
+            Error::ExpectedIdentifier(err) => Error::from(err.with_line_number(line_number)),
+            Error::ExpectedToken(err)      => Error::from(err.with_line_number(line_number)),
+            Error::ExpectedEscape(err)     => Error::from(err.with_line_number(line_number)),
+            Error::UnexpectedToken(err)    => Error::from(err.with_line_number(line_number)),
+            Error::InvalidEscape(err)      => Error::from(err.with_line_number(line_number)),
+            Error::InvalidIdentifier(err)  => Error::from(err.with_line_number(line_number)),
+            Error::DuplicateKey(err)       => Error::from(err.with_line_number(line_number)),
+            Error::RedefinedKey(err)       => Error::from(err.with_line_number(line_number)),
+            Error::InvalidAnnotation(err)  => Error::from(err.with_line_number(line_number)),
+            Error::DuplicateSection(err)   => Error::from(err.with_line_number(line_number)),
+            Error::UndefinedVariable(err)  => Error::from(err.with_line_number(line_number)),
+            Error::IntegerOverflow(err)    => Error::from(err.with_line_number(line_number)),
+            Error::LimitExceeded(err)      => Error::from(err.with_line_number(line_number)),
+            Error::IncludeCycle(err)       => Error::from(err.with_line_number(line_number)),
+            Error::UnknownParent(err)      => Error::from(err.with_line_number(line_number)),
+            Error::InheritanceCycle(err)   => Error::from(err.with_line_number(line_number)),
+            Error::Custom(err)             => Error::from(err.with_line_number(line_number)),
+            Error::Type(err)               => Error::Type(err),
+            #[cfg(feature = "std")]
+            Error::Io(err)                 => Error::Io(err),
         }
     }
 }
@@ -66,23 +190,107 @@ impl From<error_kinds::InvalidIdentifier> for Error {
     }
 }
 
+impl From<error_kinds::DuplicateKey> for Error {
+    fn from(src: error_kinds::DuplicateKey) -> Error {
+        Error::DuplicateKey(src)
+    }
+}
+
+impl From<error_kinds::RedefinedKey> for Error {
+    fn from(src: error_kinds::RedefinedKey) -> Error {
+        Error::RedefinedKey(src)
+    }
+}
+
+impl From<error_kinds::InvalidAnnotation> for Error {
+    fn from(src: error_kinds::InvalidAnnotation) -> Error {
+        Error::InvalidAnnotation(src)
+    }
+}
+
+impl From<error_kinds::DuplicateSection> for Error {
+    fn from(src: error_kinds::DuplicateSection) -> Error {
+        Error::DuplicateSection(src)
+    }
+}
+
+impl From<error_kinds::UndefinedVariable> for Error {
+    fn from(src: error_kinds::UndefinedVariable) -> Error {
+        Error::UndefinedVariable(src)
+    }
+}
+
+impl From<error_kinds::IntegerOverflow> for Error {
+    fn from(src: error_kinds::IntegerOverflow) -> Error {
+        Error::IntegerOverflow(src)
+    }
+}
+
+impl From<error_kinds::LimitExceeded> for Error {
+    fn from(src: error_kinds::LimitExceeded) -> Error {
+        Error::LimitExceeded(src)
+    }
+}
+
+impl From<error_kinds::IncludeCycle> for Error {
+    fn from(src: error_kinds::IncludeCycle) -> Error {
+        Error::IncludeCycle(src)
+    }
+}
+
+impl From<error_kinds::UnknownParent> for Error {
+    fn from(src: error_kinds::UnknownParent) -> Error {
+        Error::UnknownParent(src)
+    }
+}
+
+impl From<error_kinds::InheritanceCycle> for Error {
+    fn from(src: error_kinds::InheritanceCycle) -> Error {
+        Error::InheritanceCycle(src)
+    }
+}
+
+impl From<error_kinds::Custom> for Error {
+    fn from(src: error_kinds::Custom) -> Error {
+        Error::Custom(src)
+    }
+}
+
+impl From<TypeError> for Error {
+    fn from(src: TypeError) -> Error {
+        Error::Type(src)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for Error {
+    fn from(src: io::Error) -> Error {
+        Error::Io(src)
+    }
+}
+
 /// Contains all the error types used in `Error`'s variants
 pub mod error_kinds {
+    use crate::prelude::*;
+    #[cfg(feature = "std")]
     use std::error;
-    use std::fmt::{self, Display};
+    use core::fmt::{self, Display};
 
     /// A parsing error happening when an identifier is expected but not found
     #[derive(Debug)]
     pub struct ExpectedIdentifier {
         index: usize,
         line: String,
+        line_number: Option<usize>,
     }
 
+    #[cfg(feature = "std")]
     impl error::Error for ExpectedIdentifier {}
 
     impl Display for ExpectedIdentifier {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            write!(f, "Expected identifier {}{{here}}{}", &self.line[..self.index], &self.line[self.index..])
+            write!(f, "Expected identifier {}{{here}}{}", &self.line[..self.index], &self.line[self.index..])?;
+            write_line_number(f, self.line_number)
         }
     }
 
@@ -92,18 +300,36 @@ pub mod error_kinds {
         /// # Parameters
         /// `line`: the line where the error occured. Should be complete
         /// 
-        /// `index`: the index where the identifier is expected
-        /// 
-        /// # Panics
-        /// Panics if index is too big
+        /// `index`: the index where the identifier is expected. It is moved back to the end of `line` if too big or to the beginning of its character if inside one
         pub fn new(line: String, index: usize) -> ExpectedIdentifier {
-            assert!(line.len() >= index, "`index` must be a valid index in `line`");
-
             ExpectedIdentifier {
+                index: super::char_boundary(&line, index),
                 line,
-                index,
+                line_number: None,
             }
         }
+
+        /// Creates a new `ExpectedIdentifier` error like [`new`](struct.ExpectedIdentifier.html#method.new "errors::error_kinds::ExpectedIdentifier::new") but located by `column`, starting at 1 and counted in characters. A column after the end of `line` is moved back to its end
+        pub fn from_column(line: String, column: usize) -> ExpectedIdentifier {
+            let index = super::column_index(&line, column);
+            ExpectedIdentifier::new(line, index)
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> ExpectedIdentifier {
+            self.line_number = Some(line_number);
+            self
+        }
+
+        /// Returns the column (starting at 1 and counted in characters) where the error occured in its line
+        pub fn column(&self) -> usize {
+            column(&self.line, self.index)
+        }
     }
 
     /// A parsing error happening when an arbitrary token is expected but not found
@@ -112,13 +338,16 @@ pub mod error_kinds {
         index: usize,
         line: String,
         tokens: String,
+        line_number: Option<usize>,
     }
 
+    #[cfg(feature = "std")]
     impl error::Error for ExpectedToken {}
 
     impl Display for ExpectedToken {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            write!(f, "Expected {} {}{{here}}{}", self.tokens, &self.line[..self.index], &self.line[self.index..])
+            write!(f, "Expected {} {}{{here}}{}", self.tokens, &self.line[..self.index], &self.line[self.index..])?;
+            write_line_number(f, self.line_number)
         }
     }
 
@@ -128,21 +357,39 @@ pub mod error_kinds {
         /// # Parameters
         /// `line`: the line where the error occured. Should be complete
         /// 
-        /// `index`: the index where the token is expected
+        /// `index`: the index where the token is expected. It is moved back to the end of `line` if too big or to the beginning of its character if inside one
         /// 
         /// `tokens`: the possible tokens. There is no rule to format it, you just should be aware this will be printed directly to the end user
-        /// 
-        /// # Panics
-        /// Panics if `index` is too big
         pub fn new(line: String, index: usize, tokens: String) -> ExpectedToken {
-            assert!(line.len() >= index, "`index` must be a valid index");
-
             ExpectedToken {
+                index: super::char_boundary(&line, index),
                 line,
-                index,
                 tokens,
+                line_number: None,
             }
         }
+
+        /// Creates a new `ExpectedToken` error like [`new`](struct.ExpectedToken.html#method.new "errors::error_kinds::ExpectedToken::new") but located by `column`, starting at 1 and counted in characters. A column after the end of `line` is moved back to its end
+        pub fn from_column(line: String, column: usize, tokens: String) -> ExpectedToken {
+            let index = super::column_index(&line, column);
+            ExpectedToken::new(line, index, tokens)
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> ExpectedToken {
+            self.line_number = Some(line_number);
+            self
+        }
+
+        /// Returns the column (starting at 1 and counted in characters) where the error occured in its line
+        pub fn column(&self) -> usize {
+            column(&self.line, self.index)
+        }
     }
 
     /// A parsing error happening when a character should be escaped but is not
@@ -154,18 +401,25 @@ pub mod error_kinds {
         index: usize,
         line: String,
         replace: String,
-        token: char,
+        /// The character which should be escaped, `None` if `index` is at the end of `line`
+        token: Option<char>,
+        line_number: Option<usize>,
     }
 
+    #[cfg(feature = "std")]
     impl error::Error for ExpectedEscape {}
 
     impl Display for ExpectedEscape {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            write!(f, "Expected escape sequence {} instead of {} in {}{{here}}{}", 
-                       self.replace,
-                       self.token,
-                       &self.line[..self.index],
-                       &self.line[self.index + self.token.len_utf8()..])
+            match self.token {
+                Some(token) => write!(f, "Expected escape sequence {} instead of {} in {}{{here}}{}", 
+                                          self.replace,
+                                          token,
+                                          &self.line[..self.index],
+                                          &self.line[self.index + token.len_utf8()..])?,
+                None        => write!(f, "Expected escape sequence {} in {}{{here}}", self.replace, self.line)?,
+            }
+            write_line_number(f, self.line_number)
         }
     }
 
@@ -175,20 +429,42 @@ pub mod error_kinds {
         /// # Parameters
         /// `line`: the line where the error occured
         /// 
-        /// `index`: the index of the error
+        /// `index`: the index of the error. It is moved back to the end of `line` if too big or to the beginning of its character if inside one
         /// 
         /// `replace`: the escape sequence which should be used instead
-        /// 
-        /// # Panics
-        /// Panics if `index` is too big or is at an invalid position
         pub fn new(line: String, index: usize, replace: String) -> ExpectedEscape {
+            let index = super::char_boundary(&line, index);
+
             ExpectedEscape {
                 token: super::nth_char(&line, index),
                 line,
                 replace,
                 index,
+                line_number: None,
             }
         }
+
+        /// Creates a new `ExpectedEscape` error like [`new`](struct.ExpectedEscape.html#method.new "errors::error_kinds::ExpectedEscape::new") but located by `column`, starting at 1 and counted in characters. A column after the end of `line` is moved back to its end
+        pub fn from_column(line: String, column: usize, replace: String) -> ExpectedEscape {
+            let index = super::column_index(&line, column);
+            ExpectedEscape::new(line, index, replace)
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> ExpectedEscape {
+            self.line_number = Some(line_number);
+            self
+        }
+
+        /// Returns the column (starting at 1 and counted in characters) where the error occured in its line
+        pub fn column(&self) -> usize {
+            column(&self.line, self.index)
+        }
     }
 
     /// A parsing error happening when an arbitrary token is found where it should not
@@ -196,16 +472,21 @@ pub mod error_kinds {
     pub struct UnexpectedToken {
         index: usize,
         line: String,
-        token: char,
+        /// The character found, `None` if `index` is at the end of `line`
+        token: Option<char>,
+        line_number: Option<usize>,
     }
 
+    #[cfg(feature = "std")]
     impl error::Error for UnexpectedToken {}
 
     impl Display for UnexpectedToken {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            write!(f, "Unexpected token {} {}{{here}}",
-                       self.token,
-                       &self.line[..self.index])
+            match self.token {
+                Some(token) => write!(f, "Unexpected token {} {}{{here}}", token, &self.line[..self.index])?,
+                None        => write!(f, "Unexpected end of line {}{{here}}", self.line)?,
+            }
+            write_line_number(f, self.line_number)
         }
     }
 
@@ -215,17 +496,39 @@ pub mod error_kinds {
         /// # Parameters
         /// `line`: the line where the error occured
         /// 
-        /// `index`: the index where a token was not expected
-        /// 
-        /// # Panics
-        /// Panics if `index` is too big or is at an invalid position
+        /// `index`: the index where a token was not expected. It is moved back to the end of `line` if too big or to the beginning of its character if inside one
         pub fn new(line: String, index: usize) -> UnexpectedToken {
+            let index = super::char_boundary(&line, index);
+
             UnexpectedToken {
                 index,
                 token: super::nth_char(&line, index),
                 line,
+                line_number: None,
             }
         }
+
+        /// Creates a new `UnexpectedToken` error like [`new`](struct.UnexpectedToken.html#method.new "errors::error_kinds::UnexpectedToken::new") but located by `column`, starting at 1 and counted in characters. A column after the end of `line` is moved back to its end
+        pub fn from_column(line: String, column: usize) -> UnexpectedToken {
+            let index = super::column_index(&line, column);
+            UnexpectedToken::new(line, index)
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> UnexpectedToken {
+            self.line_number = Some(line_number);
+            self
+        }
+
+        /// Returns the column (starting at 1 and counted in characters) where the error occured in its line
+        pub fn column(&self) -> usize {
+            column(&self.line, self.index)
+        }
     }
 
     /// A parsing error happening when an escape sequence is not recognised
@@ -236,13 +539,21 @@ pub mod error_kinds {
     pub struct InvalidEscape {
         line: String,
         escape: String,
+        /// The index of `escape` in `line`, `None` if `line` does not contain it
+        index: Option<usize>,
+        line_number: Option<usize>,
     }
 
+    #[cfg(feature = "std")]
     impl error::Error for InvalidEscape {}
 
     impl Display for InvalidEscape {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            write!(f, "Invalid escape sequence {} in {}", self.escape, self.line)
+            match self.index {
+                Some(index) => write!(f, "Invalid escape sequence {} in {}{{here}}{}", self.escape, &self.line[..index], &self.line[index..])?,
+                None        => write!(f, "Invalid escape sequence {} in {}", self.escape, self.line)?,
+            }
+            write_line_number(f, self.line_number)
         }
     }
 
@@ -252,18 +563,41 @@ pub mod error_kinds {
         /// # Parameters
         /// `line`: the line where the error occured
         /// 
-        /// `escape`: the escape sequence which is invalid
-        /// 
-        /// # Panics
-        /// Panics if `escape` is not in `line`
+        /// `escape`: the escape sequence which is invalid. It should be in `line`, its first occurrence being the position of the error
         pub fn new(line: String, escape: String) -> InvalidEscape {
-            assert!(line.find(&escape).is_some(), "`line` must contain `escape`");
-
             InvalidEscape {
+                index: line.find(&escape),
                 line,
                 escape,
+                line_number: None,
             }
         }
+
+        /// Returns the escape sequence which is invalid
+        pub fn escape(&self) -> &str {
+            &self.escape
+        }
+
+        /// Returns the index (as bytes) of the escape sequence in its line, or `None` if the line does not contain it
+        pub fn index(&self) -> Option<usize> {
+            self.index
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> InvalidEscape {
+            self.line_number = Some(line_number);
+            self
+        }
+
+        /// Returns the column (starting at 1 and counted in characters) where the error occured in its line, which is 1 if the escape sequence is not in the line
+        pub fn column(&self) -> usize {
+            column(&self.line, self.index.unwrap_or(0))
+        }
     }
 
     /// A parsing error happening when an identifier is expected but the expression found is not a valid identifier
@@ -274,13 +608,16 @@ pub mod error_kinds {
     pub struct InvalidIdentifier {
         line: String,
         ident: String,
+        line_number: Option<usize>,
     }
 
+    #[cfg(feature = "std")]
     impl error::Error for InvalidIdentifier {}
 
     impl Display for InvalidIdentifier {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            write!(f, "Invalid identifier {} in {}", self.ident, self.line)
+            write!(f, "Invalid identifier {} in {}", self.ident, self.line)?;
+            write_line_number(f, self.line_number)
         }
     }
 
@@ -290,80 +627,949 @@ pub mod error_kinds {
         /// # Parameters
         /// `line`: the line where the error occured
         /// 
-        /// `identifier`: the identifier found. It must be invalid
-        /// 
-        /// # Panics
-        /// Panics
-        /// - if `identifier` is valid
-        /// - if `identifier` is not in `line`
+        /// `identifier`: the identifier found. It should be invalid and in `line`
         pub fn new(line: String, identifier: String) -> InvalidIdentifier {
-            assert!(line.find(&identifier).is_some(), "`line` must contain `identifier`");
-            assert!(!crate::datas::Identifier::is_valid(&identifier), "`identifier` must be an invalid identifier");
-
             InvalidIdentifier {
                 line,
                 ident: identifier,
+                line_number: None,
+            }
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> InvalidIdentifier {
+            self.line_number = Some(line_number);
+            self
+        }
+
+        /// Returns the column (starting at 1 and counted in characters) where the error occured in its line
+        pub fn column(&self) -> usize {
+            let index = self.line.find(&self.ident)
+                                 .expect("`line` contains `ident`");
+            column(&self.line, index)
+        }
+    }
+
+    /// An error happening when two keys of the same section are considered as the same key, for instance when they differ only by their case in a case-insensitive [`Tree`](../../datas/tree/struct.Tree.html "datas::tree::Tree")
+    #[derive(Debug)]
+    pub struct DuplicateKey {
+        section: Option<String>,
+        first: String,
+        second: String,
+        line_number: Option<usize>,
+    }
+
+    #[cfg(feature = "std")]
+    impl error::Error for DuplicateKey {}
+
+    impl Display for DuplicateKey {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match &self.section {
+                Some(section) => write!(f, "Duplicate key {} in section {} (already defined as {})", self.second, section, self.first),
+                None          => write!(f, "Duplicate key {} in the global section (already defined as {})", self.second, self.first),
+            }?;
+            write_line_number(f, self.line_number)
+        }
+    }
+
+    impl DuplicateKey {
+        /// Creates a new `DuplicateKey` error
+        /// 
+        /// # Parameters
+        /// `section`: the section containing both keys (`None` for the global section)
+        /// 
+        /// `first`: the key already defined
+        /// 
+        /// `second`: the key conflicting with `first`
+        pub fn new(section: Option<String>, first: String, second: String) -> DuplicateKey {
+            DuplicateKey {
+                section,
+                first,
+                second,
+                line_number: None,
+            }
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> DuplicateKey {
+            self.line_number = Some(line_number);
+            self
+        }
+    }
+
+    /// An error happening when a key is assigned twice in the same section while parsing with [`DuplicateKeyPolicy::Error`](../../parse/enum.DuplicateKeyPolicy.html "parse::DuplicateKeyPolicy")
+    #[derive(Debug)]
+    pub struct RedefinedKey {
+        section: Option<String>,
+        key: String,
+        first_line: usize,
+        line_number: Option<usize>,
+    }
+
+    #[cfg(feature = "std")]
+    impl error::Error for RedefinedKey {}
+
+    impl Display for RedefinedKey {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match &self.section {
+                Some(section) => write!(f, "Key {} redefined in section {} (first defined at line {})", self.key, section, self.first_line),
+                None          => write!(f, "Key {} redefined in the global section (first defined at line {})", self.key, self.first_line),
+            }?;
+            write_line_number(f, self.line_number)
+        }
+    }
+
+    impl RedefinedKey {
+        /// Creates a new `RedefinedKey` error
+        /// 
+        /// # Parameters
+        /// `section`: the section containing the key (`None` for the global section)
+        /// 
+        /// `key`: the key assigned twice
+        /// 
+        /// `first_line`: the number (starting at 1) of the line of the first assignment
+        /// 
+        /// `line_number`: the number (starting at 1) of the line of the second assignment
+        pub fn new(section: Option<String>, key: String, first_line: usize, line_number: usize) -> RedefinedKey {
+            RedefinedKey {
+                section,
+                key,
+                first_line,
+                line_number: Some(line_number),
+            }
+        }
+
+        /// Returns the section containing the key, `None` for the global section
+        pub fn section(&self) -> Option<&str> {
+            self.section.as_deref()
+        }
+
+        /// Returns the key assigned twice
+        pub fn key(&self) -> &str {
+            &self.key
+        }
+
+        /// Returns the number (starting at 1) of the line of the first assignment
+        pub fn first_line(&self) -> usize {
+            self.first_line
+        }
+
+        /// Returns the number (starting at 1) of the line of the second assignment, where the error occured
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> RedefinedKey {
+            self.line_number = Some(line_number);
+            self
+        }
+    }
+
+    /// A parsing error happening when a value does not match its type annotation, such as `abc:int`, or when this annotation is not a known type
+    /// 
+    /// # See
+    /// [`ParserOptions::type_annotations`](../../parse/struct.ParserOptions.html#structfield.type_annotations "parse::ParserOptions::type_annotations") to learn more about the type annotations
+    #[derive(Debug)]
+    pub struct InvalidAnnotation {
+        line: String,
+        value: String,
+        annotation: String,
+        line_number: Option<usize>,
+    }
+
+    #[cfg(feature = "std")]
+    impl error::Error for InvalidAnnotation {}
+
+    impl Display for InvalidAnnotation {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "Value {} does not match its type annotation {} in {}", self.value, self.annotation, self.line)?;
+            write_line_number(f, self.line_number)
+        }
+    }
+
+    impl InvalidAnnotation {
+        /// Creates a new `InvalidAnnotation` error
+        /// 
+        /// # Parameters
+        /// `line`: the line where the error occured
+        /// 
+        /// `value`: the value annotated
+        /// 
+        /// `annotation`: the type annotation, written after the last `:` of `line`
+        pub fn new(line: String, value: String, annotation: String) -> InvalidAnnotation {
+            InvalidAnnotation {
+                line,
+                value,
+                annotation,
+                line_number: None,
+            }
+        }
+
+        /// Returns the value annotated
+        pub fn value(&self) -> &str {
+            &self.value
+        }
+
+        /// Returns the type annotation
+        pub fn annotation(&self) -> &str {
+            &self.annotation
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> InvalidAnnotation {
+            self.line_number = Some(line_number);
+            self
+        }
+
+        /// Returns the column (starting at 1 and counted in characters) of the type annotation in its line
+        pub fn column(&self) -> usize {
+            let index = self.line.rfind(':')
+                                 .map_or(0, |n| n + 1);
+            column(&self.line, index)
+        }
+    }
+
+    /// An error happening when a section is given the name of another section, for instance by [`Tree::rename_section`](../../datas/tree/struct.Tree.html#method.rename_section "datas::tree::Tree::rename_section")
+    #[derive(Debug)]
+    pub struct DuplicateSection {
+        section: String,
+        line_number: Option<usize>,
+    }
+
+    #[cfg(feature = "std")]
+    impl error::Error for DuplicateSection {}
+
+    impl Display for DuplicateSection {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "Section {} already exists", self.section)?;
+            write_line_number(f, self.line_number)
+        }
+    }
+
+    impl DuplicateSection {
+        /// Creates a new `DuplicateSection` error
+        /// 
+        /// # Parameters
+        /// `section`: the name of the section already existing
+        pub fn new(section: String) -> DuplicateSection {
+            DuplicateSection {
+                section,
+                line_number: None,
+            }
+        }
+
+        /// Returns the name of the section already existing
+        pub fn section(&self) -> &str {
+            &self.section
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> DuplicateSection {
+            self.line_number = Some(line_number);
+            self
+        }
+    }
+
+    /// A parsing error happening when a value refers to an environment variable which is not defined, with [`ParserOptions::expand_env`](../../parse/struct.ParserOptions.html#structfield.expand_env "parse::ParserOptions::expand_env")
+    #[derive(Debug)]
+    pub struct UndefinedVariable {
+        line: String,
+        variable: String,
+        line_number: Option<usize>,
+    }
+
+    #[cfg(feature = "std")]
+    impl error::Error for UndefinedVariable {}
+
+    impl Display for UndefinedVariable {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "Undefined variable {} in {}", self.variable, self.line)?;
+            write_line_number(f, self.line_number)
+        }
+    }
+
+    impl UndefinedVariable {
+        /// Creates a new `UndefinedVariable` error
+        /// 
+        /// # Parameters
+        /// `line`: the line where the error occured
+        /// 
+        /// `variable`: the name of the variable which is not defined. It should be in `line`
+        pub fn new(line: String, variable: String) -> UndefinedVariable {
+            UndefinedVariable {
+                line,
+                variable,
+                line_number: None,
+            }
+        }
+
+        /// Returns the name of the variable which is not defined
+        pub fn variable(&self) -> &str {
+            &self.variable
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> UndefinedVariable {
+            self.line_number = Some(line_number);
+            self
+        }
+
+        /// Returns the column (starting at 1 and counted in characters) where the error occured in its line, which is the one of its `${`
+        pub fn column(&self) -> usize {
+            let index = self.line.find(&format!("${{{}}}", self.variable)).unwrap_or(0);
+            column(&self.line, index)
+        }
+    }
+
+    /// A parsing error happening when a value is an integer which does not fit in an `i64`, such as `99999999999999999999`
+    #[derive(Debug)]
+    pub struct IntegerOverflow {
+        line: String,
+        literal: String,
+        line_number: Option<usize>,
+    }
+
+    #[cfg(feature = "std")]
+    impl error::Error for IntegerOverflow {}
+
+    impl Display for IntegerOverflow {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "Integer {} out of range in {}", self.literal, self.line)?;
+            write_line_number(f, self.line_number)
+        }
+    }
+
+    impl IntegerOverflow {
+        /// Creates a new `IntegerOverflow` error
+        /// 
+        /// # Parameters
+        /// `line`: the line where the error occured
+        /// 
+        /// `literal`: the text of the integer which does not fit in an `i64`. It should be in `line`
+        pub fn new(line: String, literal: String) -> IntegerOverflow {
+            IntegerOverflow {
+                line,
+                literal,
+                line_number: None,
+            }
+        }
+
+        /// Returns the text of the integer which does not fit in an `i64`
+        pub fn literal(&self) -> &str {
+            &self.literal
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> IntegerOverflow {
+            self.line_number = Some(line_number);
+            self
+        }
+
+        /// Returns the column (starting at 1 and counted in characters) where the error occured in its line, which is the one of the first character of the integer
+        pub fn column(&self) -> usize {
+            let index = self.line.find(&self.literal).unwrap_or(0);
+            column(&self.line, index)
+        }
+    }
+
+    /// A limit of [`ParserOptions`](../../parse/struct.ParserOptions.html "parse::ParserOptions"), as reported by a [`LimitExceeded`](struct.LimitExceeded.html "errors::error_kinds::LimitExceeded") error
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Limit {
+        /// [`ParserOptions::max_line_len`](../../parse/struct.ParserOptions.html#structfield.max_line_len "parse::ParserOptions::max_line_len")
+        LineLength,
+        /// [`ParserOptions::max_sections`](../../parse/struct.ParserOptions.html#structfield.max_sections "parse::ParserOptions::max_sections")
+        Sections,
+        /// [`ParserOptions::max_keys`](../../parse/struct.ParserOptions.html#structfield.max_keys "parse::ParserOptions::max_keys")
+        Keys,
+    }
+
+    /// A parsing error happening when the input goes beyond a limit of [`ParserOptions`](../../parse/struct.ParserOptions.html "parse::ParserOptions"), such as a line longer than [`max_line_len`](../../parse/struct.ParserOptions.html#structfield.max_line_len "parse::ParserOptions::max_line_len")
+    /// 
+    /// Unlike the other errors, it does not retain its line which may be huge
+    #[derive(Debug)]
+    pub struct LimitExceeded {
+        limit: Limit,
+        max: usize,
+        line_number: Option<usize>,
+    }
+
+    #[cfg(feature = "std")]
+    impl error::Error for LimitExceeded {}
+
+    impl Display for LimitExceeded {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.limit {
+                Limit::LineLength => write!(f, "Line longer than {} bytes", self.max)?,
+                Limit::Sections   => write!(f, "More than {} sections", self.max)?,
+                Limit::Keys       => write!(f, "More than {} keys", self.max)?,
+            }
+            write_line_number(f, self.line_number)
+        }
+    }
+
+    impl LimitExceeded {
+        /// Creates a new `LimitExceeded` error
+        /// 
+        /// # Parameters
+        /// `limit`: the limit exceeded
+        /// 
+        /// `max`: the value of this limit
+        pub fn new(limit: Limit, max: usize) -> LimitExceeded {
+            LimitExceeded {
+                limit,
+                max,
+                line_number: None,
+            }
+        }
+
+        /// Returns the limit exceeded
+        pub fn limit(&self) -> Limit {
+            self.limit
+        }
+
+        /// Returns the value of the limit exceeded
+        pub fn max(&self) -> usize {
+            self.max
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> LimitExceeded {
+            self.line_number = Some(line_number);
+            self
+        }
+    }
+
+    /// A parsing error happening when a file included with [`ParserOptions::include_directive`](../../parse/struct.ParserOptions.html#structfield.include_directive "parse::ParserOptions::include_directive") is already being parsed, which would include it endlessly
+    #[derive(Debug)]
+    pub struct IncludeCycle {
+        line: String,
+        path: String,
+        line_number: Option<usize>,
+    }
+
+    #[cfg(feature = "std")]
+    impl error::Error for IncludeCycle {}
+
+    impl Display for IncludeCycle {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "File {} included recursively in {}", self.path, self.line)?;
+            write_line_number(f, self.line_number)
+        }
+    }
+
+    impl IncludeCycle {
+        /// Creates a new `IncludeCycle` error
+        /// 
+        /// # Parameters
+        /// `line`: the include directive where the error occured
+        /// 
+        /// `path`: the path of the file included, as written in `line`
+        pub fn new(line: String, path: String) -> IncludeCycle {
+            IncludeCycle {
+                line,
+                path,
+                line_number: None,
+            }
+        }
+
+        /// Returns the path of the file included, as written in its line
+        pub fn path(&self) -> &str {
+            &self.path
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> IncludeCycle {
+            self.line_number = Some(line_number);
+            self
+        }
+
+        /// Returns the column (starting at 1 and counted in characters) where the error occured in its line, which is the one of the path
+        pub fn column(&self) -> usize {
+            let index = self.line.find(&self.path).unwrap_or(0);
+            column(&self.line, index)
+        }
+    }
+
+    /// A parsing error happening when a section inherits from a section which is not declared before it with [`ParserOptions::section_inheritance`](../../parse/struct.ParserOptions.html#structfield.section_inheritance "parse::ParserOptions::section_inheritance"), as in `[dev : default]` without any section `default` above
+    #[derive(Debug)]
+    pub struct UnknownParent {
+        line: String,
+        parent: String,
+        line_number: Option<usize>,
+    }
+
+    #[cfg(feature = "std")]
+    impl error::Error for UnknownParent {}
+
+    impl Display for UnknownParent {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "Unknown parent section {} in {}", self.parent, self.line)?;
+            write_line_number(f, self.line_number)
+        }
+    }
+
+    impl UnknownParent {
+        /// Creates a new `UnknownParent` error
+        /// 
+        /// # Parameters
+        /// `line`: the section declaration where the error occured
+        /// 
+        /// `parent`: the name of the parent section, as written in `line`
+        pub fn new(line: String, parent: String) -> UnknownParent {
+            UnknownParent {
+                line,
+                parent,
+                line_number: None,
+            }
+        }
+
+        /// Returns the name of the parent section, as written in its line
+        pub fn parent(&self) -> &str {
+            &self.parent
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> UnknownParent {
+            self.line_number = Some(line_number);
+            self
+        }
+
+        /// Returns the column (starting at 1 and counted in characters) where the error occured in its line, which is the one of the parent section
+        pub fn column(&self) -> usize {
+            let index = self.line.rfind(&self.parent).unwrap_or(0);
+            column(&self.line, index)
+        }
+    }
+
+    /// A parsing error happening when a section inherits, directly or not, from itself with [`ParserOptions::section_inheritance`](../../parse/struct.ParserOptions.html#structfield.section_inheritance "parse::ParserOptions::section_inheritance"), as in `[default : dev]` once `[dev : default]` is declared
+    #[derive(Debug)]
+    pub struct InheritanceCycle {
+        line: String,
+        parent: String,
+        line_number: Option<usize>,
+    }
+
+    #[cfg(feature = "std")]
+    impl error::Error for InheritanceCycle {}
+
+    impl Display for InheritanceCycle {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "Section {} inherited recursively in {}", self.parent, self.line)?;
+            write_line_number(f, self.line_number)
+        }
+    }
+
+    impl InheritanceCycle {
+        /// Creates a new `InheritanceCycle` error
+        /// 
+        /// # Parameters
+        /// `line`: the section declaration where the error occured
+        /// 
+        /// `parent`: the name of the parent section, as written in `line`
+        pub fn new(line: String, parent: String) -> InheritanceCycle {
+            InheritanceCycle {
+                line,
+                parent,
+                line_number: None,
             }
         }
+
+        /// Returns the name of the parent section, as written in its line
+        pub fn parent(&self) -> &str {
+            &self.parent
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> InheritanceCycle {
+            self.line_number = Some(line_number);
+            self
+        }
+
+        /// Returns the column (starting at 1 and counted in characters) where the error occured in its line, which is the one of the parent section
+        pub fn column(&self) -> usize {
+            let index = self.line.rfind(&self.parent).unwrap_or(0);
+            column(&self.line, index)
+        }
+    }
+
+    /// An error reported by a program using this library with its own message, for instance about a value found invalid after the parsing. It is displayed like the other errors
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::errors::{Error, error_kinds::Custom};
+    /// 
+    /// let err = Custom::new(String::from("Port out of range"))
+    ///                  .with_line(String::from("port = 99999"))
+    ///                  .with_column(8)
+    ///                  .with_line_number(3);
+    /// let err = Error::from(err);
+    /// 
+    /// assert_eq!(format!("{}", err), "Port out of range in port = {here}99999 at line 3");
+    /// assert_eq!(err.column(), Some(8));
+    /// assert_eq!(err.line_number(), Some(3));
+    /// ```
+    #[derive(Debug)]
+    pub struct Custom {
+        message: String,
+        line: Option<String>,
+        /// The index (in bytes) of the error in `line`
+        index: Option<usize>,
+        line_number: Option<usize>,
+    }
+
+    #[cfg(feature = "std")]
+    impl error::Error for Custom {}
+
+    impl Display for Custom {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(&self.message)?;
+            match (&self.line, self.index) {
+                (Some(line), Some(index)) => write!(f, " in {}{{here}}{}", &line[..index], &line[index..])?,
+                (Some(line), None)        => write!(f, " in {}", line)?,
+                (None, _)                 => {},
+            }
+            write_line_number(f, self.line_number)
+        }
+    }
+
+    impl Custom {
+        /// Creates a new `Custom` error displaying `message`, bound to no line
+        pub fn new(message: String) -> Custom {
+            Custom {
+                message,
+                line: None,
+                index: None,
+                line_number: None,
+            }
+        }
+
+        /// Returns `self` with `line` as the line where it occured, which should be complete. A column given before is forgotten
+        pub fn with_line(mut self, line: String) -> Custom {
+            self.line = Some(line);
+            self.index = None;
+            self
+        }
+
+        /// Returns `self` with `column` (starting at 1 and counted in characters) as the column where it occured in its line. A column after the end of the line is moved back to its end
+        /// 
+        /// # Note
+        /// The column is ignored if the line is unknown (see [`with_line`](struct.Custom.html#method.with_line "errors::error_kinds::Custom::with_line"))
+        pub fn with_column(mut self, column: usize) -> Custom {
+            self.index = self.line.as_ref()
+                                  .map(|line| super::column_index(line, column));
+            self
+        }
+
+        /// Returns `self` with `line_number` (starting at 1) as the number of the line where it occured
+        pub fn with_line_number(mut self, line_number: usize) -> Custom {
+            self.line_number = Some(line_number);
+            self
+        }
+
+        /// Returns the message of the error
+        pub fn message(&self) -> &str {
+            &self.message
+        }
+
+        /// Returns the line where the error occured, or `None` if unknown
+        pub fn line(&self) -> Option<&str> {
+            self.line.as_deref()
+        }
+
+        /// Returns the number (starting at 1) of the line where the error occured, or `None` if unknown
+        pub fn line_number(&self) -> Option<usize> {
+            self.line_number
+        }
+
+        /// Returns the column (starting at 1 and counted in characters) where the error occured in its line, or `None` if unknown
+        pub fn column(&self) -> Option<usize> {
+            let line = self.line.as_ref()?;
+            Some(column(line, self.index?))
+        }
+    }
+
+    /// Writes ` at line {line_number}` if `line_number` is known
+    fn write_line_number(f: &mut fmt::Formatter, line_number: Option<usize>) -> fmt::Result {
+        match line_number {
+            Some(n) => write!(f, " at line {}", n),
+            None    => Ok(()),
+        }
+    }
+
+    /// Returns the column (starting at 1 and counted in characters) of the byte index `index` in `line`
+    fn column(line: &str, index: usize) -> usize {
+        line[..index].chars().count() + 1
     }
 }
 
 /// Represents either an IO error or a parsing error
 /// 
-/// Is used by this library in [`parse_file`](../parse/fn.parse_file.html "parse::parse_file") which may encounter an error with the file to parse or with its content
+/// Is used by this library in [`parse_file`](../parse/fn.parse_file.html "parse::parse_file") which may encounter an error with the file to parse or with its content. Requires the `std` feature
+#[cfg(feature = "std")]
 #[derive(Debug)]
 pub enum ParseFileError {
     IOError(io::Error),
     ParseError(Error),
+    /// The file starts with the byte-order mark of an encoding other than UTF-8, whose name is given
+    UnsupportedEncoding(&'static str),
 }
 
+#[cfg(feature = "std")]
 impl error::Error for ParseFileError {}
 
+#[cfg(feature = "std")]
 impl Display for ParseFileError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            ParseFileError::IOError(err)    => write!(f, "{}", err),
-            ParseFileError::ParseError(err) => write!(f, "{}", err),
+            ParseFileError::IOError(err)              => write!(f, "{}", err),
+            ParseFileError::ParseError(err)           => write!(f, "{}", err),
+            ParseFileError::UnsupportedEncoding(name) => write!(f, "Unsupported encoding {}, expected UTF-8", name),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl From<io::Error> for ParseFileError {
     fn from(err: io::Error) -> ParseFileError {
         ParseFileError::IOError(err)
     }
 }
 
+#[cfg(feature = "std")]
 impl From<Error> for ParseFileError {
     fn from(err: Error) -> ParseFileError {
-        ParseFileError::ParseError(err)
+        match err {
+            #[cfg(feature = "std")]
+            Error::Io(err) => ParseFileError::IOError(err),
+            err            => ParseFileError::ParseError(err),
+        }
     }
 }
 
-/// Returns the character at the `index`th index (`index` is in bytes) in `string`
+/// An error happening when a value read from a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") does not have the type expected
 /// 
-/// # Panics
-/// Panics if `index` is out of range or between two bytes of the same character
-fn nth_char(string: &str, index: usize) -> char {
-    assert!(string.len() >= index, "`index` must be a valid index in `string`");
+/// Is used by the typed getters of `Tree` such as [`get_int`](../datas/tree/struct.Tree.html#method.get_int "datas::tree::Tree::get_int")
+#[derive(Debug)]
+pub struct TypeError {
+    section: Option<String>,
+    key: String,
+    expected: &'static str,
+    found: &'static str,
+}
+
+#[cfg(feature = "std")]
+impl error::Error for TypeError {}
+
+impl Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.section {
+            Some(section) => write!(f, "Expected {} for key {} in section {}, found {}", self.expected, self.key, section, self.found),
+            None          => write!(f, "Expected {} for key {} in the global section, found {}", self.expected, self.key, self.found),
+        }
+    }
+}
+
+impl TypeError {
+    /// Creates a new `TypeError`
+    /// 
+    /// # Parameters
+    /// `section`: the section of the key read (`None` for the global section)
+    /// 
+    /// `key`: the key read
+    /// 
+    /// `expected`: the name of the type expected
+    /// 
+    /// `found`: the name of the type of the value found
+    pub fn new(section: Option<String>, key: String, expected: &'static str, found: &'static str) -> TypeError {
+        TypeError {
+            section,
+            key,
+            expected,
+            found,
+        }
+    }
+
+    /// Returns the section of the key read (`None` for the global section)
+    pub fn section(&self) -> Option<&str> {
+        self.section.as_deref()
+    }
+
+    /// Returns the key read
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the name of the type expected
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    /// Returns the name of the type of the value found
+    pub fn found(&self) -> &'static str {
+        self.found
+    }
+}
+
+/// An error happening while deserializing a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") with [`de::from_tree`](../de/fn.from_tree.html "de::from_tree") or while serializing a value with [`ser::to_string`](../ser/fn.to_string.html "ser::to_string")
+/// 
+/// When it is due to a key, for instance if it is missing or if its value does not have the type expected, the error knows the key and its section
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub struct SerdeError {
+    message: String,
+    section: Option<String>,
+    key: Option<String>,
+    /// Set to `true` once `section` and `key` are the final location of the error
+    located: bool,
+}
 
-    let mut token = '\0';
-    let mut last_n = 0;
+#[cfg(feature = "serde")]
+impl error::Error for SerdeError {}
 
-    for (n, i) in string.char_indices() {
-        last_n = n;
+#[cfg(feature = "serde")]
+impl Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.key, &self.section) {
+            (Some(key), Some(section)) => write!(f, "{} for key {} in section {}", self.message, key, section),
+            (Some(key), None)          => write!(f, "{} for key {} in the global section", self.message, key),
+            (None, Some(section))      => write!(f, "{} in section {}", self.message, section),
+            (None, None)               => write!(f, "{}", self.message),
+        }
+    }
+}
 
-        if n == index {
-            token = i;
-            break;
-        } else if n > index {
-            panic!("`index` is not a valid index in `string` (`index` = {:?}, `string` = {:?})", index, string);
+#[cfg(feature = "serde")]
+impl serde::de::Error for SerdeError {
+    fn custom<T: Display>(msg: T) -> SerdeError {
+        SerdeError::new(format!("{}", msg))
+    }
+
+    fn missing_field(field: &'static str) -> SerdeError {
+        let mut err = SerdeError::new(String::from("Missing value"));
+        err.key = Some(String::from(field));
+        err
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for SerdeError {
+    fn custom<T: Display>(msg: T) -> SerdeError {
+        SerdeError::new(format!("{}", msg))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl SerdeError {
+    /// Creates a new `SerdeError` which is not bound to any key
+    pub fn new(message: String) -> SerdeError {
+        SerdeError {
+            message,
+            section: None,
+            key: None,
+            located: false,
         }
     }
 
-    assert_eq!(last_n, index, "`index` is not a valid index in `string` (`index` = {:?}, `string` = {:?})", index, string);
+    /// Returns the section of the key which caused the error (`None` for the global section or if the error is not due to a key)
+    pub fn section(&self) -> Option<&str> {
+        self.section.as_deref()
+    }
+
+    /// Returns the key which caused the error, if any
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Binds `self` to `section` and to `key` if it is not bound to a key yet. Does nothing if `self` is already located
+    pub(crate) fn locate(mut self, section: Option<&str>, key: Option<&str>) -> SerdeError {
+        if !self.located {
+            self.located = true;
+            self.section = section.map(String::from);
+            if self.key.is_none() {
+                self.key = key.map(String::from);
+            }
+        }
+
+        self
+    }
+}
+
+/// Returns the character at the `index`th index (`index` is in bytes) in `string`, or `None` if `index` is out of range or between two bytes of the same character
+fn nth_char(string: &str, index: usize) -> Option<char> {
+    string.get(index..)?.chars().next()
+}
+
+/// Returns `index` (in bytes) moved back to the end of `string` if too big or to the beginning of its character in `string` if inside one
+fn char_boundary(string: &str, index: usize) -> usize {
+    let mut index = index.min(string.len());
+    while !string.is_char_boundary(index) {
+        index -= 1;
+    }
+
+    index
+}
+
+/// Returns the index (in bytes) of the character at `column` (starting at 1 and counted in characters) in `string`, or the length of `string` if `column` is after its end. A column `0` is the first one
+fn column_index(string: &str, column: usize) -> usize {
+    if string.is_ascii() {
+        return column.saturating_sub(1).min(string.len());
+    }
 
-    token
+    string.char_indices()
+          .nth(column.saturating_sub(1))
+          .map(|(n, _)| n)
+          .unwrap_or(string.len())
 }
 
 
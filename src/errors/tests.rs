@@ -42,79 +42,169 @@ fn invalid_escape_format() {
     let err = error_kinds::InvalidEscape::new(line, String::from("\\xyzabcd"));
 
     let fmt = format!("{}", err);
-    assert_eq!(fmt, "Invalid escape sequence \\xyzabcd in ident=\\xyzabcd");
+    assert_eq!(fmt, "Invalid escape sequence \\xyzabcd in ident={here}\\xyzabcd");
+    assert_eq!(err.index(), Some(6));
+    assert_eq!(err.column(), 7);
+}
+
+#[test]
+fn duplicate_key_format() {
+    let err = error_kinds::DuplicateKey::new(Some(String::from("Settings")), String::from("theme"), String::from("Theme"));
+
+    let fmt = format!("{}", err);
+    assert_eq!(fmt, "Duplicate key Theme in section Settings (already defined as theme)");
+}
+
+#[test]
+fn type_error_format() {
+    let err = TypeError::new(Some(String::from("server")), String::from("port"), "int", "str");
+    assert_eq!(format!("{}", err), "Expected int for key port in section server, found str");
+
+    let err = TypeError::new(None, String::from("debug"), "bool", "raw");
+    assert_eq!(format!("{}", err), "Expected bool for key debug in the global section, found raw");
+}
+
+#[test]
+fn line_number_format() {
+    let line = String::from("hello world");
+    let err = error_kinds::ExpectedToken::new(line, 5, String::from("="));
+    assert_eq!(err.line_number(), None);
+
+    let err = Error::from(err).with_line_number(12);
+    assert_eq!(err.line_number(), Some(12));
+    assert_eq!(format!("{}", err), "Expected = hello{here} world at line 12");
+}
+
+#[test]
+fn column_counts_characters() {
+    let line = String::from("\u{263a}\u{263a} = \\x00263a");
+    let err = Error::from(error_kinds::UnexpectedToken::new(line.clone(), 7));
+    assert_eq!(err.column(), Some(4));
+
+    let err = Error::from(error_kinds::InvalidEscape::new(line, String::from("\\x00263a")));
+    assert_eq!(err.column(), Some(6));
+
+    let err = Error::from(error_kinds::DuplicateKey::new(None, String::from("a"), String::from("A")));
+    assert_eq!(err.column(), None);
+}
+
+#[test]
+fn duplicate_section_display() {
+    let err = Error::from(error_kinds::DuplicateSection::new(String::from("server")));
+    assert_eq!(format!("{}", err), "Section server already exists");
+    assert_eq!(err.column(), None);
+    assert_eq!(format!("{}", err.with_line_number(3)), "Section server already exists at line 3");
 }
 
 #[test]
-#[should_panic]
 fn expected_identifier_overflow() {
     let line = String::from("[]; a non-named section");
-    let _err = error_kinds::ExpectedIdentifier::new(line, 1_000_000);
+    let err = error_kinds::ExpectedIdentifier::new(line, 1_000_000);
+
+    assert_eq!(format!("{}", err), "Expected identifier []; a non-named section{here}");
 }
 
 #[test]
-#[should_panic]
 fn expected_token_overflow() {
     let line = String::from("hello world");
-    let _err = error_kinds::ExpectedToken::new(line, 1_000_000, String::from("="));
+    let err = error_kinds::ExpectedToken::new(line, 1_000_000, String::from("="));
+
+    assert_eq!(format!("{}", err), "Expected = hello world{here}");
 }
 
 #[test]
-#[should_panic]
 fn expected_escape_overflow() {
     let line = String::from("hello world");
-    let _err = error_kinds::ExpectedEscape::new(line, 1_000_000, String::from("\\x00263a"));
+    let err = error_kinds::ExpectedEscape::new(line, 1_000_000, String::from("\\x00263a"));
+
+    assert_eq!(format!("{}", err), "Expected escape sequence \\x00263a in hello world{here}");
 }
 
 #[test]
-#[should_panic]
 fn expected_escape_alignment_error() {
     let line = String::from("greet = hello \u{263a}");
     // 15 is not an overflow but it's the second byte of ☺ (the last character)
-    let _err = error_kinds::ExpectedEscape::new(line, 15, String::from("\\x00263a"));
+    let err = error_kinds::ExpectedEscape::new(line, 15, String::from("\\x00263a"));
+
+    assert_eq!(format!("{}", err), "Expected escape sequence \\x00263a instead of \u{263a} in greet = hello {here}");
+    assert_eq!(err.column(), 15);
 }
 
 #[test]
-#[should_panic]
 fn unexpected_token_overflow() {
     let line = String::from("hello world");
-    let _err = error_kinds::UnexpectedToken::new(line, 1000_000);
+    let err = error_kinds::UnexpectedToken::new(line, 1_000_000);
+
+    assert_eq!(format!("{}", err), "Unexpected end of line hello world{here}");
 }
 
 #[test]
-#[should_panic]
 fn unexpected_token_alignment_error() {
     let line = String::from("greet = hello \u{263a}");
     // 15 is not an overflow but it's the second byte of ☺ (the last character)
-    let _err = error_kinds::UnexpectedToken::new(line, 15);
+    let err = error_kinds::UnexpectedToken::new(line, 15);
+
+    assert_eq!(format!("{}", err), "Unexpected token \u{263a} greet = hello {here}");
 }
 
 #[test]
-#[should_panic]
 fn invalid_escape_not_included() {
     let line = String::from("ident=\\xyzabcd");
-    let _err = error_kinds::InvalidEscape::new(line, String::from("\\{"));
+    let err = error_kinds::InvalidEscape::new(line, String::from("\\{"));
+
+    assert_eq!(format!("{}", err), "Invalid escape sequence \\{ in ident=\\xyzabcd");
+    assert_eq!(err.index(), None);
+    assert_eq!(err.column(), 1);
 }
 
 #[test]
 fn nth_char_works_well() {
-    assert_eq!(nth_char("abcdefg", 1), 'b');
+    assert_eq!(nth_char("abcdefg", 1), Some('b'));
 }
 
 #[test]
-#[should_panic]
 fn nth_char_alignment_error_middle() {
-    let _char = nth_char("hello \u{263a} world", 7);
+    assert_eq!(nth_char("hello \u{263a} world", 7), None);
 }
 
 #[test]
-#[should_panic]
 fn nth_char_alignment_error_end() {
-    let _char = nth_char("hello \u{263a}", 7);
+    assert_eq!(nth_char("hello \u{263a}", 7), None);
 }
 
 #[test]
-#[should_panic]
 fn nth_char_overflow() {
-    let _char = nth_char("hello", 1_000_000);
+    assert_eq!(nth_char("hello", 1_000_000), None);
+}
+
+#[test]
+fn char_boundary_snaps() {
+    assert_eq!(char_boundary("hello \u{263a}", 7), 6);
+    assert_eq!(char_boundary("hello \u{263a}", 6), 6);
+    assert_eq!(char_boundary("hello", 1_000_000), 5);
+}
+
+#[test]
+fn column_index_counts_characters() {
+    assert_eq!(column_index("\u{263a} = x", 3), 4);
+    assert_eq!(column_index("hello", 0), 0);
+    assert_eq!(column_index("hello", 1_000_000), 5);
+
+    let err = error_kinds::ExpectedIdentifier::from_column(String::from("\u{263a} = "), 3);
+    assert_eq!(err.column(), 3);
+}
+
+#[test]
+fn io_error_chaining() {
+    use std::error::Error as _;
+
+    let err = Error::from(io::Error::other("broken reader"));
+    assert_eq!(format!("{}", err), "broken reader");
+    assert_eq!(err.line_number(), None);
+    assert_eq!(format!("{}", err.source().expect("An I/O error has a source")), "broken reader");
+
+    match ParseFileError::from(err) {
+        ParseFileError::IOError(_) => {},
+        other                      => panic!("Wrong return value: {:?}", other),
+    }
 }
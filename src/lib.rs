@@ -1,9 +1,35 @@
 //! An minimalist ini file parser (MinIniP stands for Minimalist Ini Parser). It is written in Rust but I would export its API to the C programming language in order to make various bindings
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+/// The items of the standard prelude missing from the `core` one, imported by each module so that the crate builds without `std`
+mod prelude {
+    pub(crate) use alloc::format;
+    pub(crate) use alloc::string::String;
+    pub(crate) use alloc::vec;
+    pub(crate) use alloc::vec::Vec;
+    pub(crate) use crate::collections::{HashMap, HashSet};
+}
+
+/// The maps used by the crate: the hash maps of `std` or, without `std`, the ordered maps of `alloc` under the same names
+mod collections {
+    #[cfg(feature = "std")]
+    pub(crate) use std::collections::{hash_map, HashMap, HashSet};
+    #[cfg(not(feature = "std"))]
+    pub(crate) use alloc::collections::{btree_map as hash_map, BTreeMap as HashMap, BTreeSet as HashSet};
+}
+
 pub mod datas;
 pub mod dump;
 pub mod parse;
 pub mod errors;
 
+#[cfg(feature = "serde")]
+pub mod de;
+#[cfg(feature = "serde")]
+pub mod ser;
+
 #[cfg(test)]
 mod tests;
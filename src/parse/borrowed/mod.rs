@@ -0,0 +1,254 @@
+//! Contains the definition of [`parse_borrowed`](fn.parse_borrowed.html "parse::parse_borrowed")
+
+use crate::prelude::*;
+use crate::datas::{Identifier, Value, plain_text};
+use crate::datas::tree::Tree;
+use crate::errors::Error;
+use super::{parse_content, find_separator, ignore_comment, TRIPLE_QUOTE};
+use alloc::borrow::Cow;
+
+/// A value read by [`parse_borrowed`](fn.parse_borrowed.html "parse::parse_borrowed"), whose text borrows from the content parsed when it is written as is
+/// 
+/// A string is `Cow::Borrowed` unless its escape sequences had to be decoded
+#[derive(Debug, Clone, PartialEq)]
+pub enum BorrowedValue<'a> {
+    /// A non-quoted string, as a [`Value::Raw`](../datas/enum.Value.html "datas::Value")
+    Raw(Cow<'a, str>),
+    /// A quoted string, as a [`Value::Str`](../datas/enum.Value.html "datas::Value")
+    Str(Cow<'a, str>),
+    /// Any other value, such as a number
+    Other(Value),
+}
+
+impl<'a> BorrowedValue<'a> {
+    /// Returns the text of `self` if it is a string, `None` otherwise
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            BorrowedValue::Raw(text) | BorrowedValue::Str(text) => Some(text),
+            BorrowedValue::Other(_)                             => None,
+        }
+    }
+
+    /// Returns `true` if `self` is a string borrowing from the content parsed
+    pub fn is_borrowed(&self) -> bool {
+        match self {
+            BorrowedValue::Raw(text) | BorrowedValue::Str(text) => matches!(text, Cow::Borrowed(_)),
+            BorrowedValue::Other(_)                             => false,
+        }
+    }
+
+    /// Consumes `self` and returns the `Value` it stands for
+    pub fn into_value(self) -> Value {
+        match self {
+            BorrowedValue::Raw(text)    => Value::Raw(text.into_owned()),
+            BorrowedValue::Str(text)    => Value::Str(text.into_owned()),
+            BorrowedValue::Other(value) => value,
+        }
+    }
+}
+
+impl From<Value> for BorrowedValue<'_> {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Raw(text) => BorrowedValue::Raw(Cow::Owned(text)),
+            Value::Str(text) => BorrowedValue::Str(Cow::Owned(text)),
+            value            => BorrowedValue::Other(value),
+        }
+    }
+}
+
+/// The section (`None` for the global section), the name and the value of a key of a [`BorrowedTree`](struct.BorrowedTree.html "parse::BorrowedTree")
+pub type BorrowedEntry<'a> = (Option<Cow<'a, str>>, Cow<'a, str>, BorrowedValue<'a>);
+
+/// The data read by [`parse_borrowed`](fn.parse_borrowed.html "parse::parse_borrowed"), whose section names, key names and strings borrow from the content parsed
+/// 
+/// A `BorrowedTree` keeps the order of the keys in the content. A key assigned several times keeps its first place and its last value
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BorrowedTree<'a> {
+    /// The section (`None` for the global section), the name and the value of each key, in their order in the content
+    entries: Vec<BorrowedEntry<'a>>,
+    /// The index of each key of the global section in `entries`
+    global: HashMap<Cow<'a, str>, usize>,
+    /// The index of each key of the other sections in `entries`
+    sections: HashMap<Cow<'a, str>, HashMap<Cow<'a, str>, usize>>,
+}
+
+impl<'a> BorrowedTree<'a> {
+    /// Creates an empty `BorrowedTree`
+    pub fn new() -> BorrowedTree<'a> {
+        BorrowedTree::default()
+    }
+
+    /// Returns the value of the key `key` of the section `section` (`None` for the global section)
+    pub fn get(&self, section: Option<&str>, key: &str) -> Option<&BorrowedValue<'a>> {
+        let keys = match section {
+            Some(section) => self.sections.get(section)?,
+            None          => &self.global,
+        };
+
+        keys.get(key)
+            .map(|&n| &self.entries[n].2)
+    }
+
+    /// Returns `true` if the section `section` (`None` for the global section) has at least one key
+    pub fn contains_section(&self, section: Option<&str>) -> bool {
+        match section {
+            Some(section) => self.sections.contains_key(section),
+            None          => !self.global.is_empty(),
+        }
+    }
+
+    /// Sets the value of the key `key` of the section `section` to `value`. A new key is put after the existing ones
+    /// 
+    /// # Return value
+    /// `Some(old)` with `old` as the value the key had before
+    /// 
+    /// `None` if the key was not in `self`
+    pub fn insert(&mut self, section: Option<Cow<'a, str>>, key: Cow<'a, str>, value: BorrowedValue<'a>) -> Option<BorrowedValue<'a>> {
+        let keys = match &section {
+            Some(section) => self.sections.entry(section.clone()).or_default(),
+            None          => &mut self.global,
+        };
+
+        match keys.get(&key) {
+            Some(&n) => Some(core::mem::replace(&mut self.entries[n].2, value)),
+            None     => {
+                keys.insert(key.clone(), self.entries.len());
+                self.entries.push((section, key, value));
+                None
+            },
+        }
+    }
+
+    /// Returns the number of keys in `self`
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if `self` contains no key
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the section, the name and the value of each key, in their order in the content
+    pub fn entries(&self) -> &[BorrowedEntry<'a>] {
+        &self.entries
+    }
+
+    /// Consumes `self` and returns a `Tree` holding the same keys and values, copying the text borrowed
+    pub fn into_tree(self) -> Tree {
+        let mut tree = Tree::new();
+        for (section, key, value) in self.entries {
+            let ident = Identifier::new_unchecked(section.map(Cow::into_owned), key.into_owned());
+            tree.insert(ident, value.into_value());
+        }
+
+        tree
+    }
+}
+
+impl From<Tree> for BorrowedTree<'_> {
+    fn from(tree: Tree) -> Self {
+        let mut borrowed = BorrowedTree::new();
+        for (_, ident, value) in tree.iter() {
+            let section = ident.section().map(|i| Cow::Owned(String::from(i)));
+            borrowed.insert(section, Cow::Owned(String::from(ident.name())), BorrowedValue::from(value.clone()));
+        }
+
+        borrowed
+    }
+}
+
+/// Parses `content` like [`parse_content`](fn.parse_content.html "parse::parse_content") but without copying the section names, the key names nor the strings written as is
+/// 
+/// A string is `Cow::Owned` when its escape sequences had to be decoded. The lines are read with the default [`ParserOptions`](struct.ParserOptions.html "parse::ParserOptions") and a leading byte-order mark is ignored
+/// 
+/// # Return value
+/// `Ok(tree)` with `tree` as the data read
+/// 
+/// `Err(error)` in case of failure with `error` as the error code, knowing the number of its line, as returned by `parse_content`
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{parse_borrowed, BorrowedValue};
+/// use std::borrow::Cow;
+/// 
+/// let content = String::from("name = service\n[server]\nhost = 'example.com'\nmotd = 'Hello\\nworld'\n");
+/// let tree = parse_borrowed(&content).unwrap();
+/// 
+/// assert_eq!(tree.get(None, "name"), Some(&BorrowedValue::Raw(Cow::Borrowed("service"))));
+/// assert!(tree.get(Some("server"), "host").unwrap().is_borrowed());
+/// 
+/// // The escape sequence is decoded into a new string
+/// let motd = tree.get(Some("server"), "motd").unwrap();
+/// assert_eq!(motd.as_str(), Some("Hello\nworld"));
+/// assert!(!motd.is_borrowed());
+/// ```
+/// 
+/// # Note
+/// The content using a syntax other than plain sections and assignments, such as a value continued on the next line or a triple-quoted value, is read by `parse_content` and its strings are then all `Cow::Owned`
+pub fn parse_borrowed(content: &str) -> Result<BorrowedTree<'_>, Error> {
+    match read_borrowed(content) {
+        Some(tree) => Ok(tree),
+        None       => parse_content(content).map(BorrowedTree::from),
+    }
+}
+
+/// Reads `content` made of plain sections, assignments and comments without copying their text
+/// 
+/// # Return value
+/// `Some(tree)` with `tree` as the data read
+/// 
+/// `None` if a line is not valid or uses another syntax, so that `content` must be read by the parser instead
+fn read_borrowed(content: &str) -> Option<BorrowedTree<'_>> {
+    static COMMENT_CHARS: [char; 1] = [';'];
+
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut tree = BorrowedTree::new();
+    let mut section = None;
+    for line in content.split_terminator('\n') {
+        // A continued line is read by the parser
+        if line.strip_suffix('\r').unwrap_or(line).ends_with('\\') {
+            return None;
+        }
+
+        let effective = line.trim();
+        if effective.is_empty() || effective.starts_with(&COMMENT_CHARS[..]) {
+            continue;
+        }
+
+        if effective.starts_with('[') {
+            let declaration = ignore_comment(effective, &COMMENT_CHARS).trim_end();
+            let name = declaration.strip_prefix('[')?
+                                  .strip_suffix(']')?
+                                  .trim();
+            if name.contains(']') || !Identifier::is_valid(name) {
+                return None;
+            }
+
+            section = Some(name);
+            continue;
+        }
+
+        let (equal, _) = find_separator(line, &['='])?;
+        let key = line[..equal].trim();
+        let value = ignore_comment(&line[equal + 1..], &COMMENT_CHARS).trim();
+        // A triple-quoted value may span several lines
+        if !Identifier::is_valid(key) || value.starts_with(TRIPLE_QUOTE) {
+            return None;
+        }
+
+        let value = match plain_text(value) {
+            Some((text, true))  => BorrowedValue::Str(Cow::Borrowed(text)),
+            Some((text, false)) => BorrowedValue::Raw(Cow::Borrowed(text)),
+            None                => BorrowedValue::from(Value::parse(value).ok()?),
+        };
+        tree.insert(section.map(Cow::Borrowed), Cow::Borrowed(key), value);
+    }
+
+    Some(tree)
+}
+
+
+#[cfg(test)]
+mod tests;
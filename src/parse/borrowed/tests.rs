@@ -0,0 +1,38 @@
+use crate::parse::*;
+use crate::datas::Value;
+use std::borrow::Cow;
+
+#[test]
+fn parse_borrowed_borrows_plain_text() {
+    let content = "\u{feff}; comment\r\nname = service ; trailing\n\n[server]\r\nhost = 'example.com'\nport = 8080\nmotd = \"Hello\\x20world\"\nhost = localhost\n";
+    let tree = parse_borrowed(content).unwrap();
+
+    assert_eq!(tree.len(), 4);
+    assert_eq!(tree.get(None, "name"), Some(&BorrowedValue::Raw(Cow::Borrowed("service"))));
+    assert_eq!(tree.get(Some("server"), "host"), Some(&BorrowedValue::Raw(Cow::Borrowed("localhost"))));
+    assert_eq!(tree.get(Some("server"), "port"), Some(&BorrowedValue::Other(Value::Int(8080))));
+    assert_eq!(tree.get(Some("server"), "motd"), Some(&BorrowedValue::Str(Cow::Owned(String::from("Hello world")))));
+    assert!(tree.entries().iter().all(|(section, key, _)| {
+        matches!(section, None | Some(Cow::Borrowed(_))) && matches!(key, Cow::Borrowed(_))
+    }));
+
+    // The borrowed names point into `content`
+    let range = content.as_bytes().as_ptr_range();
+    assert!(range.contains(&tree.entries()[0].1.as_ptr()));
+
+    assert_eq!(tree.into_tree().get_data(), parse_content(content).unwrap().get_data());
+}
+
+#[test]
+fn parse_borrowed_falls_back_to_the_parser() {
+    let content = "text = \"\"\"first\nsecond\"\"\"\nlong = a\\\n    b\n";
+    let tree = parse_borrowed(content).unwrap();
+    assert_eq!(tree.get(None, "text").and_then(BorrowedValue::as_str), Some("first\nsecond"));
+    assert!(!tree.get(None, "long").unwrap().is_borrowed());
+    assert_eq!(tree.into_tree().get_data(), parse_content(content).unwrap().get_data());
+
+    for content in &["a = 1\n[bad!]\n", "a = 'unclosed\n", "a == b\n", "= 1\n"] {
+        let err = parse_borrowed(content).unwrap_err();
+        assert_eq!(format!("{}", err), format!("{}", parse_content(content).unwrap_err()));
+    }
+}
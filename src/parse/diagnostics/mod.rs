@@ -0,0 +1,88 @@
+//! Contains the definition of [`Diagnostic`](struct.Diagnostic.html "parse::Diagnostic")
+
+use crate::prelude::*;
+use core::fmt::{self, Display};
+
+/// The importance of a [`Diagnostic`](struct.Diagnostic.html "parse::Diagnostic")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// A matter of style, such as two blank lines in a row
+    Note,
+    /// A line which is valid but may not mean what its author expects
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Note    => f.write_str("note"),
+            Severity::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+/// A notice about a valid line, as opposed to an [`Error`](../errors/enum.Error.html "errors::Error") which makes the parsing fail
+/// 
+/// The diagnostics are collected by a [`Parser`](struct.Parser.html "parse::Parser") following [`ParserOptions::diagnostics`](struct.ParserOptions.html#structfield.diagnostics "parse::ParserOptions::diagnostics") (see [`Parser::diagnostics`](struct.Parser.html#method.diagnostics "parse::Parser::diagnostics")) and returned by [`parse_file_with_diagnostics`](fn.parse_file_with_diagnostics.html "parse::parse_file_with_diagnostics"). They are currently
+/// - a `Warning` for an unquoted value which looks like a number but is read as a `Raw` because [`ParserOptions::infer_types`](struct.ParserOptions.html#structfield.infer_types "parse::ParserOptions::infer_types") is `false`
+/// - a `Warning` for a section declared once again
+/// - a `Note` for a blank line following another one
+/// 
+/// More of them may be added in any version
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{Parser, ParserOptions, Severity};
+/// 
+/// let options = ParserOptions {
+///     diagnostics: true,
+///     ..ParserOptions::default()
+/// };
+/// let mut parser = Parser::with_options(options);
+/// for i in "[a]\nx = 1\n\n\n[a]\n".lines() {
+///     parser.parse_line(i).unwrap();
+/// }
+/// 
+/// let diagnostics = parser.diagnostics();
+/// assert_eq!(diagnostics[0].severity(), Severity::Note);
+/// assert_eq!(diagnostics[0].line_number(), 4);
+/// assert_eq!(format!("{}", diagnostics[1]), "warning at line 5: Section a already declared at line 1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    severity: Severity,
+    line_number: usize,
+    message: String,
+}
+
+impl Diagnostic {
+    /// Creates a new `Diagnostic` about the line `line_number` (starting at 1) displaying `message`
+    pub fn new(severity: Severity, line_number: usize, message: String) -> Diagnostic {
+        Diagnostic {
+            severity,
+            line_number,
+            message,
+        }
+    }
+
+    /// Returns the importance of `self`
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Returns the number (starting at 1) of the line `self` is about
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Returns the message of `self`, meant to be read by a human
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at line {}: {}", self.severity, self.line_number, self.message)
+    }
+}
@@ -0,0 +1,301 @@
+//! Contains the definition of [`EscapeTable`](trait.EscapeTable.html "parse::EscapeTable")
+
+use crate::prelude::*;
+use crate::errors::{Error, error_kinds::InvalidEscape};
+use super::{Token, TokenIterator};
+use alloc::borrow::Cow;
+use core::fmt;
+use alloc::sync::Arc;
+
+/// The escape sequences made of a backslash followed by a single character, such as `\n`, known by a dialect of INI
+/// 
+/// An `EscapeTable` is given to [`ParserOptions::escapes`](struct.ParserOptions.html#structfield.escapes "parse::ParserOptions::escapes") and [`DumpOptions::escapes`](../dump/struct.DumpOptions.html#structfield.escapes "dump::DumpOptions::escapes") through an [`Escapes`](struct.Escapes.html "parse::Escapes"). The escapes of a code point (`\x??????`, `\xHH` and `\u{?}`) are always available and are not part of the table
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{EscapeTable, DefaultEscapes, Escapes, Parser, ParserOptions};
+/// use mininip::datas::Value;
+/// 
+/// /// The default escapes, except `\t` which is not allowed and `\e` which is the escape character
+/// struct Dialect;
+/// 
+/// impl EscapeTable for Dialect {
+///     fn decode(&self, escape: &str) -> Option<char> {
+///         match escape {
+///             "\\t" => None,
+///             "\\e" => Some('\x1b'),
+///             _     => DefaultEscapes.decode(escape),
+///         }
+///     }
+/// 
+///     fn encode(&self, c: char) -> Option<String> {
+///         match c {
+///             '\t'   => None,
+///             '\x1b' => Some(String::from("\\e")),
+///             _      => DefaultEscapes.encode(c),
+///         }
+///     }
+/// }
+/// 
+/// let options = ParserOptions {
+///     escapes: Some(Escapes::new(Dialect)),
+///     ..ParserOptions::default()
+/// };
+/// let mut parser = Parser::with_options(options);
+/// parser.parse_line("prompt = \\e[1m").unwrap();
+/// assert!(parser.parse_line("indent = \\t").is_err());
+/// 
+/// let tree = parser.into_tree();
+/// assert_eq!(tree.get_value(None, "prompt"), Some(&Value::Raw(String::from("\x1b[1m"))));
+/// ```
+pub trait EscapeTable {
+    /// Returns the character written `escape`, a backslash followed by a single character, or `None` if this escape sequence is not valid. A `None` is reported as an [`InvalidEscape`](../errors/error_kinds/struct.InvalidEscape.html "errors::error_kinds::InvalidEscape")
+    fn decode(&self, escape: &str) -> Option<char>;
+
+    /// Returns the escape sequence writing `c`, a character which must be escaped in a value, or `None` if `c` has no such sequence and is written with its code point as `\x??????`
+    fn encode(&self, c: char) -> Option<String>;
+}
+
+/// The escape sequences described in the README, used when no [`EscapeTable`](trait.EscapeTable.html "parse::EscapeTable") is given
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{EscapeTable, DefaultEscapes};
+/// 
+/// assert_eq!(DefaultEscapes.decode("\\n"), Some('\n'));
+/// assert_eq!(DefaultEscapes.decode("\\q"), None);
+/// assert_eq!(DefaultEscapes.encode(';').as_deref(), Some("\\;"));
+/// assert_eq!(DefaultEscapes.encode('a'), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DefaultEscapes;
+
+impl EscapeTable for DefaultEscapes {
+    fn decode(&self, escape: &str) -> Option<char> {
+        match escape {
+            "\\a"  => Some('\x07'),
+            "\\b"  => Some('\x08'),
+            "\\t"  => Some('\t'),
+            "\\r"  => Some('\r'),
+            "\\n"  => Some('\n'),
+            "\\0"  => Some('\0'),
+            r"\\"  => Some('\\'),
+            "\\'"  => Some('\''),
+            "\\\"" => Some('\"'),
+            "\\;"  => Some(';'),
+            "\\:"  => Some(':'),
+            "\\="  => Some('='),
+            "\\#"  => Some('#'),
+            "\\$"  => Some('$'),
+            _      => None,
+        }
+    }
+
+    fn encode(&self, c: char) -> Option<String> {
+        let escape = match c {
+            '\\'   => r"\\",
+            '\''   => "\\'",
+            '"'    => "\\\"",
+            '\0'   => "\\0",
+            '\x07' => "\\a",
+            '\x08' => "\\b",
+            '\t'   => "\\t",
+            '\r'   => "\\r",
+            '\n'   => "\\n",
+            ';'    => "\\;",
+            '#'    => "\\#",
+            '='    => "\\=",
+            ':'    => "\\:",
+            _      => return None,
+        };
+
+        Some(String::from(escape))
+    }
+}
+
+/// A shared [`EscapeTable`](trait.EscapeTable.html "parse::EscapeTable"), as stored in [`ParserOptions::escapes`](struct.ParserOptions.html#structfield.escapes "parse::ParserOptions::escapes") and [`DumpOptions::escapes`](../dump/struct.DumpOptions.html#structfield.escapes "dump::DumpOptions::escapes")
+/// 
+/// Two `Escapes` are equal if they are clones of each other
+#[derive(Clone)]
+pub struct Escapes(Arc<dyn EscapeTable + Send + Sync>);
+
+impl Escapes {
+    /// Creates an `Escapes` sharing `table`
+    pub fn new<T: EscapeTable + Send + Sync + 'static>(table: T) -> Escapes {
+        Escapes(Arc::new(table))
+    }
+
+    /// Returns the table shared by `self`
+    pub fn table(&self) -> &(dyn EscapeTable + Send + Sync) {
+        &*self.0
+    }
+}
+
+impl fmt::Debug for Escapes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Escapes")
+    }
+}
+
+impl PartialEq for Escapes {
+    fn eq(&self, other: &Escapes) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Returns `true` if `escape` is made of a backslash followed by a single character, other than the beginning of an unfinished code point escape
+fn is_simple(escape: &str) -> bool {
+    escape.chars().count() == 2 && escape != "\\x" && escape != "\\u"
+}
+
+/// Returns the escape sequence writing `c` with the default escapes, falling back to its code point so that `c` is never written as is
+fn default_escape(c: char) -> String {
+    DefaultEscapes.encode(c)
+                  .unwrap_or_else(|| format!("\\x{:06x}", c as u32))
+}
+
+/// Rewrites the escape sequences of `value`, written with `table`, as default escape sequences so that the default parsing functions can read it. `line` is the line `value` is part of, used to report an error, and `kept` is an escape sequence which is left as is
+/// 
+/// # Return value
+/// `Ok(text)` with `text` as the rewritten value
+/// 
+/// `Err(error)` with `error` as an [`InvalidEscape`](../errors/error_kinds/struct.InvalidEscape.html "errors::error_kinds::InvalidEscape") error if `table` does not know an escape sequence of `value`
+pub(crate) fn to_default_escapes<'a>(line: &str, value: &'a str, table: &dyn EscapeTable, kept: Option<&str>) -> Result<Cow<'a, str>, Error> {
+    if !value.contains('\\') {
+        return Ok(Cow::Borrowed(value));
+    }
+
+    let mut text = String::with_capacity(value.len());
+    for i in TokenIterator::from(value.chars()) {
+        match i {
+            Token::Escape(escape) if is_simple(&escape) && Some(escape.as_str()) != kept => match table.decode(&escape) {
+                Some(c) => text.push_str(&default_escape(c)),
+                None    => return Err(Error::from(InvalidEscape::new(String::from(line), escape))),
+            },
+            Token::Escape(escape)                                                         => text.push_str(&escape),
+            Token::Char(c)                                                                => text.push(c),
+        }
+    }
+
+    Ok(Cow::Owned(text))
+}
+
+/// Rewrites the default escape sequences of `text`, as dumped by the default dumping functions, with the escape sequences of `table`
+pub(crate) fn from_default_escapes(text: &str, table: &dyn EscapeTable) -> String {
+    if !text.contains('\\') {
+        return String::from(text);
+    }
+
+    let mut result = String::with_capacity(text.len());
+    for i in TokenIterator::from(text.chars()) {
+        match i {
+            Token::Char(c)        => result.push(c),
+            Token::Escape(escape) => match DefaultEscapes.decode(&escape) {
+                Some(c) => {
+                    let escape = table.encode(c)
+                                      .unwrap_or_else(|| format!("\\x{:06x}", c as u32));
+                    result.push_str(&escape);
+                },
+                None    => result.push_str(&escape),
+            },
+        }
+    }
+
+    result
+}
+
+/// Rewrites the percent escapes of `value`, such as `%20` for a space, as default escape sequences so that the default parsing functions can read it. `line` is the line `value` is part of, used to report an error
+/// 
+/// The bytes of consecutive percent escapes are decoded together as UTF-8, such as `%C3%A9` for `é`. A decoded character is always escaped so that it is read as part of a string, even if it is a character of the INI syntax. The other characters, including the backslash escapes, are left as is
+/// 
+/// # Return value
+/// `Ok(text)` with `text` as the rewritten value
+/// 
+/// `Err(error)` with `error` as an [`InvalidEscape`](../errors/error_kinds/struct.InvalidEscape.html "errors::error_kinds::InvalidEscape") error if a `%` is not followed by two hexadecimal digits, as in `%G1`, or if the bytes escaped are not valid UTF-8
+pub(crate) fn from_percent_escapes<'a>(line: &str, value: &'a str) -> Result<Cow<'a, str>, Error> {
+    if !value.contains('%') {
+        return Ok(Cow::Borrowed(value));
+    }
+
+    let mut text = String::with_capacity(value.len());
+    // The bytes escaped not decoded yet and the text of their escape sequences
+    let mut bytes = Vec::new();
+    let mut escape = String::new();
+
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        if c != '%' {
+            push_percent_bytes(line, &mut bytes, &mut escape, &mut text)?;
+            text.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        let digits = rest.get(1..3)
+                         .filter(|digits| digits.bytes().all(|i| i.is_ascii_hexdigit()));
+        match digits {
+            Some(digits) => {
+                bytes.push(u8::from_str_radix(digits, 16).expect("Only hexadecimal digits are accepted"));
+                escape.push_str(&rest[..3]);
+                rest = &rest[3..];
+            },
+            None         => {
+                let end = rest.char_indices()
+                              .nth(3)
+                              .map_or(rest.len(), |(n, _)| n);
+                return Err(Error::from(InvalidEscape::new(String::from(line), String::from(&rest[..end]))));
+            },
+        }
+    }
+    push_percent_bytes(line, &mut bytes, &mut escape, &mut text)?;
+
+    Ok(Cow::Owned(text))
+}
+
+/// Decodes `bytes` as UTF-8 into `text` as default escape sequences and clears `bytes` and `escape`, the text of their percent escapes in `line`
+fn push_percent_bytes(line: &str, bytes: &mut Vec<u8>, escape: &mut String, text: &mut String) -> Result<(), Error> {
+    if bytes.is_empty() {
+        return Ok(());
+    }
+
+    match core::str::from_utf8(bytes) {
+        Ok(decoded) => text.extend(decoded.chars().map(default_escape)),
+        Err(_)      => return Err(Error::from(InvalidEscape::new(String::from(line), core::mem::take(escape)))),
+    }
+
+    bytes.clear();
+    escape.clear();
+    Ok(())
+}
+
+/// Rewrites the default escape sequences of `text`, as dumped by the default dumping functions, as percent escapes of the bytes of their UTF-8 encoding. A `%` is written `%25`
+pub(crate) fn to_percent_escapes(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut buf = [0; 4];
+    for i in TokenIterator::from(text.chars()) {
+        let c = match i {
+            Token::Char('%')      => '%',
+            Token::Char(c)        => {
+                result.push(c);
+                continue;
+            },
+            Token::Escape(escape) => match super::parse_str(&escape) {
+                Ok(decoded) if decoded.chars().count() == 1 => decoded.chars().next().expect("There is a single character"),
+                _                                          => {
+                    result.push_str(&escape);
+                    continue;
+                },
+            },
+        };
+
+        for byte in c.encode_utf8(&mut buf).bytes() {
+            result.push_str(&format!("%{:02X}", byte));
+        }
+    }
+
+    result
+}
+
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,91 @@
+use crate::parse::*;
+use crate::datas::{tree::Tree, Identifier, Value};
+use crate::dump::{dump_tree_with_options, DumpOptions};
+use crate::errors::Error;
+
+/// A dialect writing a space as `\s` and refusing `\t`
+struct Spaces;
+
+impl EscapeTable for Spaces {
+    fn decode(&self, escape: &str) -> Option<char> {
+        match escape {
+            "\\s" => Some(' '),
+            "\\t" => None,
+            _     => DefaultEscapes.decode(escape),
+        }
+    }
+
+    fn encode(&self, c: char) -> Option<String> {
+        match c {
+            ' '  => Some(String::from("\\s")),
+            '\t' => None,
+            _    => DefaultEscapes.encode(c),
+        }
+    }
+}
+
+#[test]
+fn escape_table_parse() {
+    let options = ParserOptions {
+        escapes: Some(Escapes::new(Spaces)),
+        array_separator: Some(','),
+        ..ParserOptions::default()
+    };
+    let tree = parse_content_with_options("a = x\\sy\nb = 'c\\;d'\nc = 1\\,5, 2\n", options.clone()).unwrap();
+    assert_eq!(tree.get_value(None, "a"), Some(&Value::Raw(String::from("x y"))));
+    assert_eq!(tree.get_value(None, "b"), Some(&Value::Str(String::from("c;d"))));
+    assert_eq!(tree.get_value(None, "c"), Some(&Value::Array(vec![Value::Raw(String::from("1,5")), Value::Int(2)])));
+
+    match parse_content_with_options("d = a\\tb\n", options) {
+        Err(Error::InvalidEscape(err)) => assert_eq!(err.escape(), "\\t"),
+        other                          => panic!("Wrong return value: {:?}", other),
+    }
+
+    // The default table is the one of the README
+    assert_eq!(parse_content("e = a\\sb\n").unwrap_err().line_number(), Some(1));
+}
+
+#[test]
+fn escape_table_dump_round_trip() {
+    let escapes = Escapes::new(Spaces);
+    let mut tree = Tree::new();
+    tree.insert(Identifier::new(None, String::from("greeting")), Value::Str(String::from("hello world;\t!")));
+
+    let options = DumpOptions {
+        escapes: Some(escapes.clone()),
+        ..DumpOptions::default()
+    };
+    let dumped = dump_tree_with_options(&tree, options);
+    assert_eq!(dumped, "greeting='hello world\\;\\x000009!'\n");
+
+    let options = ParserOptions {
+        escapes: Some(escapes),
+        ..ParserOptions::default()
+    };
+    assert_eq!(parse_content_with_options(&dumped, options).unwrap(), tree);
+}
+
+#[test]
+fn percent_escapes_round_trip() {
+    let options = ParserOptions {
+        percent_escapes: true,
+        ..ParserOptions::default()
+    };
+    let tree = parse_content_with_options("msg = hello%20world%0Aagain%3B 100%25 %C3%A9\n", options.clone()).unwrap();
+    assert_eq!(tree.get_value(None, "msg"), Some(&Value::Raw(String::from("hello world\nagain; 100% \u{e9}"))));
+
+    let dump_options = DumpOptions {
+        percent_escapes: true,
+        ..DumpOptions::default()
+    };
+    let dumped = dump_tree_with_options(&tree, dump_options);
+    assert_eq!(dumped, "msg=hello world%0Aagain%3B 100%25 %C3%A9\n");
+    assert_eq!(parse_content_with_options(&dumped, options.clone()).unwrap(), tree);
+
+    for (content, escape) in &[("a = %G1\n", "%G1"), ("a = 50%\n", "%"), ("a = %C3\n", "%C3")] {
+        match parse_content_with_options(content, options.clone()) {
+            Err(Error::InvalidEscape(err)) => assert_eq!(err.escape(), *escape),
+            other                          => panic!("Wrong return value: {:?}", other),
+        }
+    }
+}
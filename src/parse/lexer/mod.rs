@@ -0,0 +1,111 @@
+//! Contains the definition of [`tokenize_line`](fn.tokenize_line.html "parse::tokenize_line")
+
+use crate::prelude::*;
+use crate::errors::Error;
+use super::{Parser, ParserOptions};
+use super::parser::{find_separator, ignore_comment, ignore_comment_with};
+
+/// A token of a line of an INI file, as returned by [`tokenize_line`](fn.tokenize_line.html "parse::tokenize_line"). Each token borrows its text from the line, the whitespaces around being dropped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineToken<'a> {
+    /// The `[` opening a section declaration
+    SectionOpen,
+    /// The `]` closing a section declaration
+    SectionClose,
+    /// The name of a section or of a key
+    Identifier(&'a str),
+    /// The character separating a key from its value
+    Separator(char),
+    /// The value of a key as written in the line, neither unquoted nor unescaped
+    Value(&'a str),
+    /// A comment, either on its own line or at the end of the line, starting with its comment character
+    Comment(&'a str),
+}
+
+/// Splits `line` into its tokens following the default options of a [`Parser`](struct.Parser.html "parse::Parser")
+/// 
+/// # See
+/// See [`tokenize_line_with_options`](fn.tokenize_line_with_options.html "parse::tokenize_line_with_options") for the details
+pub fn tokenize_line(line: &str) -> Result<Vec<LineToken<'_>>, Error> {
+    tokenize_line_with_options(line, &ParserOptions::default())
+}
+
+/// Splits `line` into its tokens following `options`
+/// 
+/// The line is read on its own, so a line continuation or a triple-quoted value spanning several lines is not followed and no file is read for an include directive (see [`ParserOptions::include_directive`](struct.ParserOptions.html#structfield.include_directive "parse::ParserOptions::include_directive")), its line being checked like any other. A blank line has no token and a bare key (see [`ParserOptions::bare_keys`](struct.ParserOptions.html#structfield.bare_keys "parse::ParserOptions::bare_keys")) is a single `Identifier`
+/// 
+/// # Return value
+/// `Ok(tokens)` with `tokens` as the tokens of `line` in their order
+/// 
+/// `Err(error)` with `error` as the error a [`Parser`](struct.Parser.html "parse::Parser") would return for `line`, the values included. This function never panics, whatever `line`
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{tokenize_line, LineToken};
+/// 
+/// assert_eq!(tokenize_line("[server] ; The server").unwrap(), [
+///     LineToken::SectionOpen,
+///     LineToken::Identifier("server"),
+///     LineToken::SectionClose,
+///     LineToken::Comment("; The server"),
+/// ]);
+/// assert_eq!(tokenize_line("port = 8080").unwrap(), [
+///     LineToken::Identifier("port"),
+///     LineToken::Separator('='),
+///     LineToken::Value("8080"),
+/// ]);
+/// assert!(tokenize_line("port 8080").is_err());
+/// ```
+pub fn tokenize_line_with_options<'a>(line: &'a str, options: &ParserOptions) -> Result<Vec<LineToken<'a>>, Error> {
+    // The parser checks the line so that it is only split here, without following an include directive which would read another file
+    let mut checked = options.clone();
+    checked.include_directive = None;
+    Parser::with_options(checked).parse_line(line)?;
+
+    let effective_line = line.trim_start();
+    let mut tokens = Vec::new();
+    match effective_line.chars().next() {
+        None                                           => {},
+        Some(c) if options.comment_chars.contains(&c) => tokens.push(LineToken::Comment(effective_line.trim_end())),
+        Some('[')                                      => {
+            if let Some(end) = effective_line.find(']') {
+                tokens.push(LineToken::SectionOpen);
+                let name = &effective_line[1..end];
+                tokens.push(LineToken::Identifier(if options.trim_whitespace { name.trim() } else { name }));
+                tokens.push(LineToken::SectionClose);
+                push_comment(&mut tokens, &effective_line[end + 1..]);
+            }
+        },
+        Some(_)                                        => {
+            if let Some((equal, separator)) = find_separator(effective_line, &options.kv_separators) {
+                let key = &effective_line[..equal];
+                tokens.push(LineToken::Identifier(if options.trim_whitespace { key.trim_end() } else { key }));
+                tokens.push(LineToken::Separator(separator));
+
+                let rest = &effective_line[equal + separator.len_utf8()..];
+                let value = ignore_comment_with(rest, &options.comment_chars, options.spaced_comments);
+                tokens.push(LineToken::Value(if options.trim_whitespace { value.trim() } else { value.strip_suffix('\r').unwrap_or(value) }));
+                push_comment(&mut tokens, &rest[value.len()..]);
+            } else {
+                // A bare key, since the parser accepted the line
+                let key = ignore_comment(effective_line, &options.comment_chars);
+                tokens.push(LineToken::Identifier(key.trim_end()));
+                push_comment(&mut tokens, &effective_line[key.len()..]);
+            }
+        },
+    }
+
+    Ok(tokens)
+}
+
+/// Pushes the comment ending a line into `tokens`, `rest` being the end of this line, if there is one
+fn push_comment<'a>(tokens: &mut Vec<LineToken<'a>>, rest: &'a str) {
+    let comment = rest.trim();
+    if !comment.is_empty() {
+        tokens.push(LineToken::Comment(comment));
+    }
+}
+
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,87 @@
+use crate::parse::lexer::*;
+use crate::errors::Error;
+
+#[test]
+fn tokenize_line_splits_tokens() {
+    assert_eq!(tokenize_line("").unwrap(), []);
+    assert_eq!(tokenize_line("  ; comment  ").unwrap(), [LineToken::Comment("; comment")]);
+    assert_eq!(tokenize_line("[section]").unwrap(), [
+        LineToken::SectionOpen,
+        LineToken::Identifier("section"),
+        LineToken::SectionClose,
+    ]);
+    assert_eq!(tokenize_line("  key = 'a ; b' ; comment").unwrap(), [
+        LineToken::Identifier("key"),
+        LineToken::Separator('='),
+        LineToken::Value("'a ; b'"),
+        LineToken::Comment("; comment"),
+    ]);
+    assert_eq!(tokenize_line("empty =").unwrap(), [
+        LineToken::Identifier("empty"),
+        LineToken::Separator('='),
+        LineToken::Value(""),
+    ]);
+
+    let options = ParserOptions {
+        kv_separators: vec![':'],
+        trim_whitespace: false,
+        ..ParserOptions::default()
+    };
+    assert_eq!(tokenize_line_with_options("key : value ", &options).unwrap(), [
+        LineToken::Identifier("key "),
+        LineToken::Separator(':'),
+        LineToken::Value(" value "),
+    ]);
+
+    // The section name is trimmed like the parser does
+    assert_eq!(tokenize_line("[ a ]").unwrap(), [
+        LineToken::SectionOpen,
+        LineToken::Identifier("a"),
+        LineToken::SectionClose,
+    ]);
+}
+
+#[test]
+fn tokenize_line_does_not_include_files() {
+    let options = ParserOptions {
+        include_directive: Some(String::from("@include")),
+        ..ParserOptions::default()
+    };
+
+    // The file is not opened, which would be an I/O error since it does not exist, the line is read as a key without a value
+    assert!(matches!(tokenize_line_with_options("@include missing.ini", &options), Err(Error::ExpectedToken(_))));
+}
+
+#[test]
+fn tokenize_line_reports_errors() {
+    assert!(matches!(tokenize_line("[section"), Err(Error::ExpectedToken(_))));
+    assert!(matches!(tokenize_line("[section] key"), Err(Error::UnexpectedToken(_))));
+    assert!(matches!(tokenize_line("1key = 1"), Err(Error::InvalidIdentifier(_))));
+    assert!(matches!(tokenize_line("key = \\q"), Err(Error::InvalidEscape(_))));
+}
+
+#[test]
+fn tokenize_line_never_panics() {
+    const ALPHABET: [char; 16] = ['[', ']', '=', ';', '\\', '\'', '"', 'x', 'u', '{', '}', '0', 'a', ' ', '.', '\u{e9}'];
+
+    // A linear congruential generator is enough to build arbitrary lines reproducibly
+    let mut state: u32 = 42;
+    let mut next = || {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        (state >> 16) as usize
+    };
+
+    let options = ParserOptions {
+        nested_sections: true,
+        array_separator: Some(','),
+        type_annotations: true,
+        ..ParserOptions::default()
+    };
+    for _ in 0..5000 {
+        let len = next() % 12;
+        let line: String = (0..len).map(|_| ALPHABET[next() % ALPHABET.len()]).collect();
+
+        let _ = tokenize_line(&line);
+        let _ = tokenize_line_with_options(&line, &options);
+    }
+}
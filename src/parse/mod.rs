@@ -1,6 +1,7 @@
 //! Provides tools to parse an INI file
 
-use std::iter::Fuse;
+use crate::prelude::*;
+use core::iter::{Fuse, Peekable};
 use crate::errors::{Error, error_kinds::*};
 
 /// Reads a string formatted by [`dump_str`](../dump/fn.dump_str.html "dump::dump_str") and unescapes the escaped characters
@@ -13,28 +14,65 @@ use crate::errors::{Error, error_kinds::*};
 /// # Encoding issues
 /// Only allows ASCII because Unicode or other encodings musn't appear in an INI file (except in comments but this function is not intended to parse whole files)
 /// 
+/// The control characters must be escaped, except the tab which may also be written as is
+/// 
+/// Any character may be escaped by its code point, either as `\x??????` with exactly 6 hexadecimal digits or as `\u{?}` with 1 to 6 hexadecimal digits. A byte may also be escaped as `\xHH` with exactly 2 hexadecimal digits not followed by 4 other ones, and the consecutive bytes escaped this way are decoded as UTF-8. An invalid code point (such as a surrogate) or an invalid UTF-8 sequence (such as an overlong encoding) is an [`InvalidEscape`](../errors/error_kinds/struct.InvalidEscape.html "errors::error_kinds::InvalidEscape")
+/// 
 /// # Examples
 /// ```
 /// use mininip::parse::parse_str;
 /// 
 /// assert!(parse_str("Bad because ends with a ;").is_err());
+/// assert_eq!(parse_str("tabs\tare\tkept").unwrap(), "tabs\tare\tkept");
 /// assert_eq!(parse_str(r"abc\=123\; \x00263a").unwrap(), "abc=123; \u{263a}");
+/// assert_eq!(parse_str(r"\x41\xc3\xa9 \u{1F600}").unwrap(), "A\u{e9} \u{1f600}");
 /// ```
 pub fn parse_str(content: &str) -> Result<String, Error> {
+    unescape(content, &FORBIDDEN)
+}
+
+/// The characters which must be escaped in an unquoted value, besides the non-ASCII ones
+pub(crate) static FORBIDDEN: [char; 12] = ['\x07', '\x08', '\r', '\n', '\0', '\\', '\'', '\"', ';', ':', '=', '#'];
+
+/// The characters which must be escaped in a quoted value, besides the non-ASCII ones
+pub(crate) static QUOTED_FORBIDDEN: [char; 8] = ['\x07', '\x08', '\r', '\n', '\0', '\\', '\'', '\"'];
+
+/// Like [`parse_str`](fn.parse_str.html "parse::parse_str") but for the content of a quoted string, where the characters of the INI syntax `;`, `:`, `=` and `#` may be written as is
+pub(crate) fn parse_quoted_str(content: &str) -> Result<String, Error> {
+    unescape(content, &QUOTED_FORBIDDEN)
+}
+
+/// Unescapes `content`, returning an error if a character of `forbidden` or a non-ASCII character is not escaped
+fn unescape(content: &str, forbidden: &[char]) -> Result<String, Error> {
+    // Without any escape sequence nor character to reject, an ASCII content is its own text. Otherwise it is read below, reporting the error if any
+    if content.is_ascii() && !content.bytes().any(|c| c == b'\\' || forbidden.contains(&char::from(c))) {
+        return Ok(String::from(content));
+    }
+
     // new will never be wider than content
     let mut new = String::with_capacity(content.len());
 
-    static FORBIDDEN: [char; 13] = ['\x07', '\x08', '\t', '\r', '\n', '\0', '\\', '\'', '\"', ';', ':', '=', '#'];
+    // The bytes escaped as `\xHH` not decoded yet and the text of their escape sequences
+    let mut bytes = Vec::new();
+    let mut bytes_escape = String::new();
 
     // `next` is the index (as bytes) of the next escape sequence in content
     let mut next = 0;
     for i in TokenIterator::from(content.chars()) {
+        let is_byte = match &i {
+            Token::Escape(escape) => escape.starts_with("\\x") && escape.len() >= 4 && escape.len() < 8,
+            Token::Char(_)        => false,
+        };
+        if !is_byte && !bytes.is_empty() {
+            decode_bytes(content, &mut bytes, &mut bytes_escape, &mut new)?;
+        }
+
         let escape = match i {
             Token::Char(c) => {
                 let n = next;
                 next += 1;
 
-                if FORBIDDEN.contains(&c) || !c.is_ascii() {
+                if forbidden.contains(&c) || !c.is_ascii() {
                     let escape = crate::dump::dump_str(&format!("{}", c));
                     let err = Error::from(ExpectedEscape::new(String::from(content), n, escape));
                     return Err(err);
@@ -48,43 +86,89 @@ pub fn parse_str(content: &str) -> Result<String, Error> {
 
         next += escape.len();
 
+        if let Some(c) = DefaultEscapes.decode(&escape) {
+            new.push(c);
+            continue;
+        }
+
         match escape.as_str() {
-            "\\a"  => new.push('\x07'),
-            "\\b"  => new.push('\x08'),
-            "\\t"  => new.push('\t'),
-            "\\r"  => new.push('\r'),
-            "\\n"  => new.push('\n'),
-            "\\0"  => new.push('\0'),
-            r"\\"  => new.push('\\'),
-            "\\'"  => new.push('\''),
-            "\\\"" => new.push('\"'),
-            "\\;"  => new.push(';'),
-            "\\:"  => new.push(':'),
-            "\\="  => new.push('='),
-            "\\#"  => new.push('#'),
-
-            _ if escape.len() == 8 => {
-                debug_assert!(escape.starts_with("\\x"));
-
-                let values = &escape[2..];
-                let code = match u32::from_str_radix(values, 16) {
-                    Ok(val) => val,
-                    Err(_)  => return Err(Error::from(InvalidEscape::new(String::from(content), escape))),
-                };
-                let character = match std::char::from_u32(code) {
-                    Some(val) => val,
-                    None      => return Err(Error::from(InvalidEscape::new(String::from(content), escape))),
-                };
-                new.push(character);
+            _ if escape.starts_with("\\x") && escape.len() == 8 => new.push(code_point(content, &escape, &escape[2..])?),
+
+            // `\xHH` and the following hexadecimal digits which are not part of the escape
+            _ if is_byte => {
+                let byte = u8::from_str_radix(&escape[2..4], 16).expect("The tokenizer only yields hexadecimal digits");
+                bytes.push(byte);
+                bytes_escape.push_str(&escape[..4]);
+
+                if escape.len() > 4 {
+                    decode_bytes(content, &mut bytes, &mut bytes_escape, &mut new)?;
+                    new.push_str(&escape[4..]);
+                }
             },
 
+            _ if escape.starts_with("\\u{") && escape.ends_with('}') && escape.len() > 4 => new.push(code_point(content, &escape, &escape[3..escape.len() - 1])?),
+
             _ => return Err(Error::from(InvalidEscape::new(String::from(content), escape))),
         }
     }
 
+    if !bytes.is_empty() {
+        decode_bytes(content, &mut bytes, &mut bytes_escape, &mut new)?;
+    }
+
     Ok(new)
 }
 
+/// Returns the character whose code point is written in hexadecimal in `digits`, part of the escape sequence `escape` of `content`
+fn code_point(content: &str, escape: &str, digits: &str) -> Result<char, Error> {
+    u32::from_str_radix(digits, 16).ok()
+                                   .and_then(core::char::from_u32)
+                                   .ok_or_else(|| Error::from(InvalidEscape::new(String::from(content), String::from(escape))))
+}
+
+/// Decodes `bytes` as UTF-8 into `new` and clears `bytes` and `escape`, the text of their escape sequences in `content`
+fn decode_bytes(content: &str, bytes: &mut Vec<u8>, escape: &mut String, new: &mut String) -> Result<(), Error> {
+    match core::str::from_utf8(bytes) {
+        Ok(decoded) => new.push_str(decoded),
+        Err(_)      => return Err(Error::from(InvalidEscape::new(String::from(content), core::mem::take(escape)))),
+    }
+
+    bytes.clear();
+    escape.clear();
+    Ok(())
+}
+
+/// Splits `content` on each occurrence of `separator` which is neither escaped nor inside quotes
+/// 
+/// An escaped separator is replaced by the separator itself in the element containing it, unless it is already an escape sequence of the INI syntax (like `\;`) which is left as is to be unescaped later
+pub(crate) fn split_unescaped(content: &str, separator: char) -> Vec<String> {
+    let escaped_separator = format!("\\{}", separator);
+    let is_ini_escape = crate::dump::dump_str(&format!("{}", separator)) == escaped_separator;
+
+    let mut elements = Vec::new();
+    let mut current = String::with_capacity(content.len());
+    let mut quote = None;
+    for i in TokenIterator::from(content.chars()) {
+        match i {
+            Token::Char(c) if quote == Some(c)                            => {
+                quote = None;
+                current.push(c);
+            },
+            Token::Char(c) if quote.is_none() && (c == '\'' || c == '"') => {
+                quote = Some(c);
+                current.push(c);
+            },
+            Token::Char(c) if quote.is_none() && c == separator          => elements.push(core::mem::take(&mut current)),
+            Token::Char(c)                                                => current.push(c),
+            Token::Escape(s) if s == escaped_separator && !is_ini_escape  => current.push(separator),
+            Token::Escape(s)                                              => current.push_str(&s),
+        }
+    }
+    elements.push(current);
+
+    elements
+}
+
 
 /// A token which is either a single character or an escape sequence starting with `\`
 #[derive(PartialEq, Debug)]
@@ -101,14 +185,28 @@ enum Token {
 /// These characters are NOT TRUSTED, for example, you may receive a `\é` sequence wich is illegal in INI
 /// 
 /// If an escape sequence is left unfinished, it is returned as is in a `Token::Escape` object, even though it is invalid
-struct TokenIterator<T> {
-    iterator: Fuse<T>,
+/// 
+/// An escape sequence starting with `\x` holds the up to 6 hexadecimal digits following it and one starting with `\u` holds the following braces and the up to 6 hexadecimal digits inside
+struct TokenIterator<T: Iterator> {
+    iterator: Peekable<Fuse<T>>,
 }
 
 impl<T: Iterator> From<T> for TokenIterator<T> {
     fn from(iterator: T) -> TokenIterator<T> {
         TokenIterator {
-            iterator: iterator.fuse(),
+            iterator: iterator.fuse().peekable(),
+        }
+    }
+}
+
+impl<T: Iterator<Item = char>> TokenIterator<T> {
+    /// Pushes into `escape` the next characters while they are hexadecimal digits, up to `max` of them
+    fn push_hex_digits(&mut self, escape: &mut String, max: usize) {
+        for _ in 0..max {
+            match self.iterator.next_if(char::is_ascii_hexdigit) {
+                Some(c) => escape.push(c),
+                None    => break,
+            }
         }
     }
 }
@@ -137,8 +235,14 @@ impl<T: Iterator<Item = char>> Iterator for TokenIterator<T> {
                 return Some(Token::Char(i));
             }
 
-            if escape_seq.starts_with(r"\x") && escape_seq.len() < 8 {
-                continue;
+            if escape_seq == r"\x" {
+                self.push_hex_digits(&mut escape_seq, 6);
+            } else if escape_seq == r"\u" && self.iterator.next_if_eq(&'{').is_some() {
+                escape_seq.push('{');
+                self.push_hex_digits(&mut escape_seq, 6);
+                if self.iterator.next_if_eq(&'}').is_some() {
+                    escape_seq.push('}');
+                }
             }
 
             return Some(Token::Escape(escape_seq));
@@ -158,7 +262,7 @@ pub fn find_unescaped(string: &str, pattern: char) -> Option<usize> {
     // possible values of `escape`
     // -1   : the last character parsed is a '\\'
     // 0    : this character must be read because it's unescaped
-    // 1..6 : this character must be ignored because it belongs to an escape sequence
+    // 1..6 : this character must be ignored if it is an hexadecimal digit because it belongs to an escape sequence
     let mut escape = 0;
     for (n, i) in string.char_indices() {
         if escape == -1 {
@@ -167,14 +271,17 @@ pub fn find_unescaped(string: &str, pattern: char) -> Option<usize> {
             } else {
                 0
             };
+            continue;
         }
 
-        else if escape > 0 {
+        if escape > 0 && i.is_ascii_hexdigit() {
             escape -= 1;
+            continue;
         }
+        escape = 0;
 
         // Since here, escape = 0 so the character must be parsed
-        else if i == '\\' {
+        if i == '\\' {
             escape = -1;
         } else if i == pattern {
             return Some(n);
@@ -185,9 +292,30 @@ pub fn find_unescaped(string: &str, pattern: char) -> Option<usize> {
 }
 
 
+mod options;
+pub use options::*;
+
 mod parser;
 pub use parser::*;
 
+mod stream;
+pub use stream::*;
+
+mod lexer;
+pub use lexer::*;
+
+mod escapes;
+pub use escapes::*;
+
+mod visitor;
+pub use visitor::*;
+
+mod diagnostics;
+pub use diagnostics::*;
+
+mod borrowed;
+pub use borrowed::*;
+
 
 #[cfg(test)]
 mod tests;
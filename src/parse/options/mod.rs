@@ -0,0 +1,277 @@
+//! Contains the definition of [`ParserOptions`](struct.ParserOptions.html "parse::ParserOptions")
+
+use crate::prelude::*;
+use core::fmt;
+use alloc::sync::Arc;
+use super::Escapes;
+
+/// The options changing the behaviour of a [`Parser`](struct.Parser.html "parse::Parser")
+/// 
+/// The default options follow the specification described in the README
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{Parser, ParserOptions};
+/// 
+/// let options = ParserOptions {
+///     keep_comments: true,
+///     comment_chars: vec![';', '#'],
+///     ..ParserOptions::default()
+/// };
+/// let parser = Parser::with_options(options);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParserOptions {
+    /// Retains the comment lines in the [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") built, attached to the key or section declared right after them. Defaults to `false`
+    pub keep_comments: bool,
+    /// The characters starting a comment, either on its own line or after an assignment or a section declaration. A comment character inside a quoted value does not start a comment. Defaults to `[';']`
+    pub comment_chars: Vec<char>,
+    /// Only starts a comment after a value at a comment character preceded by a whitespace, so that `url = a;b ; c` is the value `a;b` followed by the comment `; c`. The comment characters left in a value are read as if they were escaped. Defaults to `false`
+    pub spaced_comments: bool,
+    /// Retains the text of each value as written in the file so that [`dump_tree`](../dump/fn.dump_tree.html "dump::dump_tree") writes it back the same way, for instance an integer in hexadecimal. Defaults to `false`
+    pub keep_formats: bool,
+    /// The separator of the elements of a [`Value::Array`](../datas/enum.Value.html "datas::Value"), as in `servers = a.com, b.com`, or `None` not to parse arrays. Defaults to `None`
+    pub array_separator: Option<char>,
+    /// Ignores a separator ending an array, so that `hosts = a.com, b.com,` holds two elements and `hosts = a.com,` is an array of a single element. A separator starting an array is never ignored and gives an empty first element, as in `, a.com` which holds `''` and `a.com`. Without this option, a trailing separator gives an empty last element. Only used with [`array_separator`](struct.ParserOptions.html#structfield.array_separator "parse::ParserOptions::array_separator"). Defaults to `true`
+    pub allow_trailing_comma: bool,
+    /// Reads a section declaration like `[server.tls]` as the section `tls` nested in the section `server` (see [`Tree::get_subsection`](../datas/tree/struct.Tree.html#method.get_subsection "datas::tree::Tree::get_subsection")). A literal dot is escaped as in `[version\\.2]` and stays escaped in the name of the section, `version\\.2`, so that it never collides with the nested `[version.2]`. Defaults to `false`
+    pub nested_sections: bool,
+    /// Reads a section declaration like `[dev : default]` as the section `dev` inheriting from the section `default`: the keys of `default` not assigned in `dev` yet are copied into `dev`, whose own assignments override them whatever [`duplicate_keys`](struct.ParserOptions.html#structfield.duplicate_keys "parse::ParserOptions::duplicate_keys") is. A section name can then not contain a `:`. The parent must be declared before its child, otherwise this is an [`UnknownParent`](../errors/error_kinds/struct.UnknownParent.html "errors::error_kinds::UnknownParent") error, and a section inheriting from itself, directly or not, is an [`InheritanceCycle`](../errors/error_kinds/struct.InheritanceCycle.html "errors::error_kinds::InheritanceCycle") error. The parent is retained (see [`Tree::parent_of`](../datas/tree/struct.Tree.html#method.parent_of "datas::tree::Tree::parent_of")) so that the declaration is dumped back as is, with the keys inherited. Defaults to `false`
+    pub section_inheritance: bool,
+    /// The behaviour of the parser when a key is assigned twice in the same section. Defaults to `DuplicateKeyPolicy::LastWins`
+    pub duplicate_keys: DuplicateKeyPolicy,
+    /// Infers the type of each unquoted value as described in the README. Otherwise, every value is a [`Value::Raw`](../datas/enum.Value.html "datas::Value") once unquoted and unescaped (see [`Value::parse_untyped`](../datas/enum.Value.html#method.parse_untyped "datas::Value::parse_untyped")). Defaults to `true`
+    pub infer_types: bool,
+    /// The characters separating a key from its value, such as `:` in `key: value`. The first of them outside of quotes splits the line. Defaults to `['=']`
+    pub kv_separators: Vec<char>,
+    /// Strips the whitespaces around the keys, the unquoted values and the section names inside their brackets, as in `[ database ]`. Otherwise, `key = value` declares the key `key ` with the value ` value`, only the indentation before the key and the line ending being ignored. The content of a quoted value is never trimmed while the whitespaces outside of its quotes always are, whatever this option. The elements of an array are always trimmed too. Defaults to `true`
+    pub trim_whitespace: bool,
+    /// The name of a section whose keys belong to the global section, such as `DEFAULT` to read the keys following `[DEFAULT]` as global keys. It matches the output of [`DumpOptions::global_section_name`](../dump/struct.DumpOptions.html#structfield.global_section_name "dump::DumpOptions::global_section_name"). An invalid identifier never matches any section. Defaults to `None`
+    pub global_section_name: Option<String>,
+    /// Additional spellings of a [`Value::Bool`](../datas/enum.Value.html "datas::Value"), matched regardless of their case before any other type is inferred, such as `TRUE` or `1`. The spellings of the README are still read as a `Bool`. Ignored if [`infer_types`](struct.ParserOptions.html#structfield.infer_types "parse::ParserOptions::infer_types") is `false`. Defaults to `None`
+    pub bool_tokens: Option<BoolTokens>,
+    /// Reads an unquoted value starting with `#` as a [`Value::Color`](../datas/enum.Value.html "datas::Value") (see [`Value::parse_color`](../datas/enum.Value.html#method.parse_color "datas::Value::parse_color")), such as `color = #1a2b3c`. A malformed color is a `Value::Str`. Ignored if `#` is one of the [`comment_chars`](struct.ParserOptions.html#structfield.comment_chars "parse::ParserOptions::comment_chars") or if [`infer_types`](struct.ParserOptions.html#structfield.infer_types "parse::ParserOptions::infer_types") is `false`. Defaults to `false`
+    pub parse_colors: bool,
+    /// Reads the text following the last unescaped `:` of a value outside of quotes as its type, as in `port = 8080:int`. The types are `int`, `float` (which accepts an integer), `bool`, `str` and `raw`, and a value which does not match its type is an [`InvalidAnnotation`](../errors/error_kinds/struct.InvalidAnnotation.html "errors::error_kinds::InvalidAnnotation") error. The annotations are honored even if [`infer_types`](struct.ParserOptions.html#structfield.infer_types "parse::ParserOptions::infer_types") is `false`. Otherwise, a `:` must be escaped in a value as described in the README. Defaults to `false`
+    pub type_annotations: bool,
+    /// Matches the keywords of the INI syntax regardless of their case, which are the spellings of a [`Value::Bool`](../datas/enum.Value.html "datas::Value") described in the README (such as `YES` or `Off`) and the [`type_annotations`](struct.ParserOptions.html#structfield.type_annotations "parse::ParserOptions::type_annotations") (such as `8080:INT`). The section and key names are not affected. A `Bool` is dumped in lowercase anyway. Defaults to `false`
+    pub case_insensitive_keywords: bool,
+    /// Reports a backslash in a key which does not start a known escape sequence, such as `\q` in `key\q = 1`, as an [`InvalidEscape`](../errors/error_kinds/struct.InvalidEscape.html "errors::error_kinds::InvalidEscape") error naming this sequence. Otherwise, since the escapes are not available in the keys, such a key is reported as an [`InvalidIdentifier`](../errors/error_kinds/struct.InvalidIdentifier.html "errors::error_kinds::InvalidIdentifier") like any key containing a backslash. Defaults to `false`
+    pub strict_escapes: bool,
+    /// Retains the blank lines in the [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") built as empty comments, interleaved with the other comments, so that [`dump_tree`](../dump/fn.dump_tree.html "dump::dump_tree") reproduces the layout of the file instead of separating the sections with a single blank line. A [`StreamParser`](struct.StreamParser.html "parse::StreamParser") yields them as an empty [`Entry::Comment`](enum.Entry.html "parse::Entry"). Defaults to `false`
+    pub keep_blank_lines: bool,
+    /// Replaces each `${VAR}` of a value by the value of the environment variable `VAR` before reading this value, so that `port = ${PORT}` may be an integer. A literal `$` is escaped as `\$`. The triple-quoted values, being verbatim, are never expanded. Defaults to `false`
+    pub expand_env: bool,
+    /// The behaviour of the parser when a variable expanded with [`expand_env`](struct.ParserOptions.html#structfield.expand_env "parse::ParserOptions::expand_env") is not defined. Defaults to `UndefinedEnvPolicy::Error`
+    pub undefined_env: UndefinedEnvPolicy,
+    /// The source of the variables expanded with [`expand_env`](struct.ParserOptions.html#structfield.expand_env "parse::ParserOptions::expand_env"), or `None` to read the environment of the process (which requires the `std` feature, every variable being undefined otherwise). Defaults to `None`
+    pub env_resolver: Option<EnvResolver>,
+    /// Reads a key written alone on its line, without any separator nor value, as in `verbose`, as a [`Value::Null`](../datas/enum.Value.html "datas::Value"). Otherwise, such a line is an [`ExpectedToken`](../errors/error_kinds/struct.ExpectedToken.html "errors::error_kinds::ExpectedToken") error. Defaults to `false`
+    pub bare_keys: bool,
+    /// Reads the PHP-style array keys: each assignment of a key written `key[]`, as in `hosts[] = a.com` followed by `hosts[] = b.com`, appends its value to the [`Value::Array`](../datas/enum.Value.html "datas::Value") of the key `key`, whatever [`duplicate_keys`](struct.ParserOptions.html#structfield.duplicate_keys "parse::ParserOptions::duplicate_keys") is. A key written `key[name]` assigns the key `key.name` of the current section, such as `hosts.main` for `hosts[main] = a.com`. The other keys are not affected. Defaults to `false`
+    pub array_keys: bool,
+    /// An unquoted value read as a [`Value::Null`](../datas/enum.Value.html "datas::Value"), matched regardless of its case, such as `null` in `key = null`. Defaults to `None`
+    pub null_token: Option<String>,
+    /// The escape sequences made of a backslash followed by a single character read in the values, or `None` for the ones described in the README (see [`EscapeTable`](trait.EscapeTable.html "parse::EscapeTable")). An escape sequence unknown to the table is an [`InvalidEscape`](../errors/error_kinds/struct.InvalidEscape.html "errors::error_kinds::InvalidEscape") error. The escapes of a code point are always available. Defaults to `None`
+    pub escapes: Option<Escapes>,
+    /// Reads the percent escapes of the values, made of a `%` followed by the two hexadecimal digits of a byte, such as `%20` for a space or `%3B` for a semicolon which does not start a comment. The bytes of consecutive escapes are decoded as UTF-8, such as `%C3%A9` for `é`, and a decoded character is always part of a string. The percent escapes are decoded after the backslash escapes, which are still read, so a `%` is always written `%25`. A `%` which is not followed by two hexadecimal digits, as in `%G1`, or bytes which are not valid UTF-8 are an [`InvalidEscape`](../errors/error_kinds/struct.InvalidEscape.html "errors::error_kinds::InvalidEscape") error. Defaults to `false`
+    pub percent_escapes: bool,
+    /// The maximal length in bytes of a line, line ending excluded, or `None` for no limit. A line continued with a backslash or a triple-quoted value counts as a single line. A longer line is a [`LimitExceeded`](../errors/error_kinds/struct.LimitExceeded.html "errors::error_kinds::LimitExceeded") error. A line read from a file or a reader is never stored beyond the limit: [`parse_reader`](fn.parse_reader.html "parse::parse_reader") and [`parse_file`](fn.parse_file.html "parse::parse_file") stop at such a line while a [`StreamParser`](struct.StreamParser.html "parse::StreamParser") and [`parse_file_lossy`](fn.parse_file_lossy.html "parse::parse_file_lossy") skip it. Defaults to `None`
+    pub max_line_len: Option<usize>,
+    /// The maximal number of sections declared, a section declared twice counting once, or `None` for no limit. Declaring one more section is a [`LimitExceeded`](../errors/error_kinds/struct.LimitExceeded.html "errors::error_kinds::LimitExceeded") error. Defaults to `None`
+    pub max_sections: Option<usize>,
+    /// The maximal number of keys stored by a [`Parser`](struct.Parser.html "parse::Parser"), all sections included, or `None` for no limit. Assigning one more key is a [`LimitExceeded`](../errors/error_kinds/struct.LimitExceeded.html "errors::error_kinds::LimitExceeded") error while assigning an existing key again is not. Defaults to `None`
+    pub max_keys: Option<usize>,
+    /// The directive including another file, such as `@include` to read `@include tls.ini` as the lines of `tls.ini`, as if they were written instead of the directive. A relative path is resolved from the directory of the including file, or from the current directory for the lines given to [`Parser::parse_line`](struct.Parser.html#method.parse_line "parse::Parser::parse_line"). An error in an included file is reported at the line of the directive and a file including itself, directly or not, is an [`IncludeCycle`](../errors/error_kinds/struct.IncludeCycle.html "errors::error_kinds::IncludeCycle") error. Only honored by the functions storing the data with the `std` feature, not by a [`StreamParser`](struct.StreamParser.html "parse::StreamParser"). Defaults to `None`
+    pub include_directive: Option<String>,
+    /// Collects the notices about the valid lines parsed, read with [`Parser::diagnostics`](struct.Parser.html#method.diagnostics "parse::Parser::diagnostics"). They are not retained otherwise, so that a long input does not fill the memory with them. Always set by [`parse_file_with_diagnostics`](fn.parse_file_with_diagnostics.html "parse::parse_file_with_diagnostics"). Defaults to `false`
+    pub diagnostics: bool,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            keep_comments: false,
+            comment_chars: vec![';'],
+            spaced_comments: false,
+            keep_formats: false,
+            array_separator: None,
+            allow_trailing_comma: true,
+            nested_sections: false,
+            section_inheritance: false,
+            duplicate_keys: DuplicateKeyPolicy::LastWins,
+            infer_types: true,
+            kv_separators: vec!['='],
+            trim_whitespace: true,
+            global_section_name: None,
+            bool_tokens: None,
+            parse_colors: false,
+            type_annotations: false,
+            case_insensitive_keywords: false,
+            strict_escapes: false,
+            keep_blank_lines: false,
+            expand_env: false,
+            undefined_env: UndefinedEnvPolicy::Error,
+            env_resolver: None,
+            bare_keys: false,
+            array_keys: false,
+            null_token: None,
+            escapes: None,
+            percent_escapes: false,
+            max_line_len: None,
+            max_sections: None,
+            max_keys: None,
+            include_directive: None,
+            diagnostics: false,
+        }
+    }
+}
+
+/// The spellings of the two values of a [`Value::Bool`](../datas/enum.Value.html "datas::Value"), used by [`ParserOptions::bool_tokens`](struct.ParserOptions.html#structfield.bool_tokens "parse::ParserOptions::bool_tokens")
+/// 
+/// The default tokens are `yes`, `on`, `1` and `true` for `true` and `no`, `off`, `0` and `false` for `false`
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{Parser, ParserOptions, BoolTokens};
+/// use mininip::datas::Value;
+/// 
+/// let options = ParserOptions {
+///     bool_tokens: Some(BoolTokens::default()),
+///     ..ParserOptions::default()
+/// };
+/// let mut parser = Parser::with_options(options);
+/// parser.parse_line("verbose = TRUE").unwrap();
+/// parser.parse_line("debug = 0").unwrap();
+/// 
+/// let tree = parser.into_tree();
+/// assert_eq!(tree.get_value(None, "verbose"), Some(&Value::Bool(true)));
+/// assert_eq!(tree.get_value(None, "debug"), Some(&Value::Bool(false)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoolTokens {
+    /// The spellings of `true`
+    pub truthy: Vec<String>,
+    /// The spellings of `false`
+    pub falsy: Vec<String>,
+}
+
+impl BoolTokens {
+    /// Returns the boolean spelled `token` regardless of its case, or `None` if `token` is none of the spellings of `self`
+    pub fn parse(&self, token: &str) -> Option<bool> {
+        if self.truthy.iter().any(|i| i.eq_ignore_ascii_case(token)) {
+            Some(true)
+        } else if self.falsy.iter().any(|i| i.eq_ignore_ascii_case(token)) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for BoolTokens {
+    fn default() -> Self {
+        let tokens = |list: &[&str]| list.iter().map(|&i| String::from(i)).collect();
+
+        BoolTokens {
+            truthy: tokens(&["yes", "on", "1", "true"]),
+            falsy: tokens(&["no", "off", "0", "false"]),
+        }
+    }
+}
+
+/// The behaviour of a [`Parser`](struct.Parser.html "parse::Parser") when a key is assigned twice in the same section
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{Parser, ParserOptions, DuplicateKeyPolicy};
+/// use mininip::datas::Value;
+/// 
+/// let options = ParserOptions {
+///     duplicate_keys: DuplicateKeyPolicy::Collect,
+///     ..ParserOptions::default()
+/// };
+/// let mut parser = Parser::with_options(options);
+/// parser.parse_line("server = a.com").unwrap();
+/// parser.parse_line("server = b.com").unwrap();
+/// 
+/// let tree = parser.into_tree();
+/// let servers = Value::Array(vec![Value::Raw(String::from("a.com")), Value::Raw(String::from("b.com"))]);
+/// assert_eq!(tree.get_value(None, "server"), Some(&servers));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateKeyPolicy {
+    /// The last value assigned replaces the previous ones
+    LastWins,
+    /// The first value assigned is kept and the following ones are ignored
+    FirstWins,
+    /// The parsing fails with a [`RedefinedKey`](../errors/error_kinds/struct.RedefinedKey.html "errors::error_kinds::RedefinedKey") error
+    Error,
+    /// The values are collected into a [`Value::Array`](../datas/enum.Value.html "datas::Value") in their order of assignment. The elements of an array assigned are added one by one
+    Collect,
+}
+
+/// The behaviour of a [`Parser`](struct.Parser.html "parse::Parser") when a variable expanded with [`ParserOptions::expand_env`](struct.ParserOptions.html#structfield.expand_env "parse::ParserOptions::expand_env") is not defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndefinedEnvPolicy {
+    /// The parsing fails with an [`UndefinedVariable`](../errors/error_kinds/struct.UndefinedVariable.html "errors::error_kinds::UndefinedVariable") error
+    Error,
+    /// The variable expands to an empty string
+    Empty,
+}
+
+/// A function giving the value of a variable expanded with [`ParserOptions::expand_env`](struct.ParserOptions.html#structfield.expand_env "parse::ParserOptions::expand_env") instead of the environment of the process, for instance to sandbox a configuration or to test it
+/// 
+/// Two `EnvResolver`s are equal if they are clones of each other
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{Parser, ParserOptions, EnvResolver};
+/// use mininip::datas::Value;
+/// 
+/// let options = ParserOptions {
+///     expand_env: true,
+///     env_resolver: Some(EnvResolver::new(|name| match name {
+///         "PORT" => Some(String::from("8080")),
+///         _      => None,
+///     })),
+///     ..ParserOptions::default()
+/// };
+/// let mut parser = Parser::with_options(options);
+/// parser.parse_line("port = ${PORT}").unwrap();
+/// parser.parse_line("price = 5\\$").unwrap();
+/// assert!(parser.parse_line("home = ${HOME}").is_err());
+/// 
+/// let tree = parser.into_tree();
+/// assert_eq!(tree.get_value(None, "port"), Some(&Value::Int(8080)));
+/// assert_eq!(tree.get_value(None, "price"), Some(&Value::Raw(String::from("5$"))));
+/// ```
+#[derive(Clone)]
+pub struct EnvResolver(Arc<ResolverFn>);
+
+/// The function wrapped by an `EnvResolver`
+type ResolverFn = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+impl EnvResolver {
+    /// Creates an `EnvResolver` calling `resolver` with the name of each variable, which returns its value or `None` if it is not defined
+    pub fn new<F: Fn(&str) -> Option<String> + Send + Sync + 'static>(resolver: F) -> EnvResolver {
+        EnvResolver(Arc::new(resolver))
+    }
+
+    /// Returns the value of the variable `name`, or `None` if it is not defined
+    pub fn resolve(&self, name: &str) -> Option<String> {
+        (self.0)(name)
+    }
+}
+
+impl fmt::Debug for EnvResolver {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("EnvResolver")
+    }
+}
+
+impl PartialEq for EnvResolver {
+    fn eq(&self, other: &EnvResolver) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
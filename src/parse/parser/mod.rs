@@ -1,11 +1,27 @@
 //! Contains the definition of [`Parser`](struct.Parser.html "parse::Parser")
 
-use std::collections::HashMap;
-use crate::datas::{Identifier, Value};
-use crate::errors::{Error, error_kinds::*, ParseFileError};
-use std::path::Path;
+use crate::prelude::*;
+use alloc::borrow::Cow;
+use crate::datas::{Identifier, Value, tree::{Tree, Quote}, parse_bool_keyword};
+use crate::errors::{Error, error_kinds::*};
+use crate::dump::dump_str;
+#[cfg(feature = "std")]
+use crate::errors::ParseFileError;
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "std")]
 use std::fs::File;
-use std::io::Read;
+#[cfg(feature = "std")]
+use std::io::{self, BufReader, Read};
+#[cfg(feature = "std")]
+use super::stream::{read_bounded_line, skip_line};
+use super::{Entry, Diagnostic, Severity, ParserOptions, DuplicateKeyPolicy, UndefinedEnvPolicy, split_unescaped, parse_str, to_default_escapes, from_percent_escapes, Token, TokenIterator};
+
+/// The sections declared more than once with the numbers of the lines declaring them, as returned by [`Parser::duplicate_sections`](struct.Parser.html#method.duplicate_sections "parse::Parser::duplicate_sections")
+pub type DuplicateSections = Vec<(String, Vec<usize>)>;
+
+/// An entry read by `Parser::read_line` with the text of its value if retained and the quotes around this value if any
+type ReadEntry = (Entry, Option<String>, Option<Quote>);
 
 /// A parser with a local state. Use it by passing it the text to parse line after line
 /// 
@@ -42,26 +58,147 @@ use std::io::Read;
 /// ```
 #[derive(Debug, Clone)]
 pub struct Parser {
-    variables: HashMap<Identifier, Value>,
+    variables: Tree,
     cur_section: Option<String>,
+    /// The path of the current section if it was declared as a nested section
+    cur_path: Option<Vec<String>>,
+    /// The parent of the current section if it was declared as `[child : parent]`
+    cur_parent: Option<String>,
+    /// The keys copied from a parent section and not assigned since, only with `ParserOptions::section_inheritance`
+    inherited: HashSet<Identifier>,
+    /// The beginning of a logical line continued on the next physical line, backslash included
+    pending: Option<String>,
+    /// The triple-quoted value being read, if its closing `"""` is not reached yet
+    block: Option<Block>,
+    /// The comments read since the last key or section declaration
+    comments: Vec<String>,
+    /// The number of lines read
+    line_number: usize,
+    /// The line of the assignment of each key, only retained with `DuplicateKeyPolicy::Error`
+    assignment_lines: HashMap<Identifier, usize>,
+    /// The lines declaring each section, in the order of their first declaration
+    section_lines: Vec<(String, Vec<usize>)>,
+    /// The notices about the valid lines parsed
+    diagnostics: Vec<Diagnostic>,
+    /// Set to `true` if the last line parsed is blank
+    last_blank: bool,
+    /// Set to `true` by `parse_key` if the last key read is written `key[]`, only with `ParserOptions::array_keys`
+    appending: bool,
+    /// The files being parsed, the including ones first, only retained with `ParserOptions::include_directive`
+    #[cfg(feature = "std")]
+    files: Vec<PathBuf>,
+    options: ParserOptions,
 }
 
 impl Parser {
     /// Creates a new `Parser`, which didn't parsed any line
     pub fn new() -> Parser {
+        Parser::with_options(ParserOptions::default())
+    }
+
+    /// Creates a new `Parser` following `options`, which didn't parsed any line
+    pub fn with_options(options: ParserOptions) -> Parser {
+        let mut variables = Tree::new();
+        if options.keep_blank_lines {
+            variables.retain_blank_lines();
+        }
+
         Parser {
-            variables: HashMap::new(),
+            variables,
             cur_section: None,
+            cur_path: None,
+            cur_parent: None,
+            inherited: HashSet::new(),
+            pending: None,
+            block: None,
+            comments: Vec::new(),
+            line_number: 0,
+            assignment_lines: HashMap::new(),
+            section_lines: Vec::new(),
+            diagnostics: Vec::new(),
+            last_blank: false,
+            appending: false,
+            #[cfg(feature = "std")]
+            files: Vec::new(),
+            options,
         }
     }
 
     /// Consumes the parser and returns its data which is an `HashMap<Identifier, Value>` linking an identifier to its value
+    /// 
+    /// # Note
+    /// A line left unfinished by a trailing backslash is ignored. Call [`finish`](struct.Parser.html#method.finish "parse::Parser::finish") before to report it as an error
     pub fn data(self) -> HashMap<Identifier, Value> {
+        self.variables.into_data()
+    }
+
+    /// Consumes the parser and returns its data as a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") keeping the order of declaration of the keys and, if asked in the options, the comments
+    /// 
+    /// # Note
+    /// A line left unfinished by a trailing backslash is ignored. Call [`finish`](struct.Parser.html#method.finish "parse::Parser::finish") before to report it as an error
+    pub fn into_tree(mut self) -> Tree {
+        let comments = core::mem::take(&mut self.comments);
+        self.variables.comment_end(comments);
+
         self.variables
     }
 
+    /// Returns the sections declared more than once in the lines parsed, in the order of their first declaration, with the numbers of the lines declaring them
+    /// 
+    /// The keys of each declaration are still merged into the same section. The first line given to [`parse_line`](struct.Parser.html#method.parse_line "parse::Parser::parse_line") is the line 1
+    /// 
+    /// # Example
+    /// ```
+    /// use mininip::parse::Parser;
+    /// 
+    /// let mut parser = Parser::new();
+    /// for i in "[a]\nx = 1\n[b]\n[a]\ny = 2\n".lines() {
+    ///     parser.parse_line(i).unwrap();
+    /// }
+    /// 
+    /// assert_eq!(parser.duplicate_sections(), vec![(String::from("a"), vec![1, 4])]);
+    /// ```
+    pub fn duplicate_sections(&self) -> DuplicateSections {
+        self.section_lines.iter()
+                          .filter(|(_, lines)| lines.len() > 1)
+                          .cloned()
+                          .collect()
+    }
+
+    /// Returns the notices about the valid lines parsed, in their order (see [`Diagnostic`](struct.Diagnostic.html "parse::Diagnostic")). The first line given to [`parse_line`](struct.Parser.html#method.parse_line "parse::Parser::parse_line") is the line 1
+    /// 
+    /// The notices are only collected with [`ParserOptions::diagnostics`](struct.ParserOptions.html#structfield.diagnostics "parse::ParserOptions::diagnostics"), none is returned otherwise
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Tells the parser there is no more line to parse
+    /// 
+    /// # Return value
+    /// `Ok(())` in case of success
+    /// 
+    /// `Err(error)` with `error` as an [`ExpectedToken`](../errors/error_kinds/struct.ExpectedToken.html "errors::error_kinds::ExpectedToken") error if the last line parsed ends with a line continuation or if a triple-quoted value is not closed
+    pub fn finish(&mut self) -> Result<(), Error> {
+        if let Some(block) = self.block.take() {
+            let end = block.line.len();
+            return Err(Error::from(ExpectedToken::new(block.line, end, String::from(TRIPLE_QUOTE))));
+        }
+
+        match self.pending.take() {
+            Some(line) => {
+                let end = line.len();
+                Err(Error::from(ExpectedToken::new(line, end, String::from("continuation line"))))
+            },
+            None => Ok(()),
+        }
+    }
+
     /// Parses a line
     /// 
+    /// A line ending with a non-escaped backslash `\` is continued on the next one, which is appended without its leading whitespaces. A comment line is never continued
+    /// 
+    /// A value starting with `"""` is a `Value::Str` spanning the following lines until the next `"""`. Its text is kept verbatim, without any escape, comment or continuation, and the line break right after the opening `"""` is ignored
+    /// 
     /// # Parameters
     /// `line` the line to parse
     /// 
@@ -90,12 +227,429 @@ impl Parser {
     /// }
     /// ```
     pub fn parse_line(&mut self, line: &str) -> Result<(), Error> {
+        let blank = self.pending.is_none() && self.block.is_none() && line.trim().is_empty();
+        if self.options.diagnostics && blank && self.last_blank {
+            self.diagnostics.push(Diagnostic::new(Severity::Note, self.line_number + 1, String::from("Blank line following another one")));
+        }
+        self.last_blank = blank;
+
+        #[cfg(feature = "std")]
+        if self.pending.is_none() && self.block.is_none() {
+            if let Some(path) = self.include_path(line) {
+                self.line_number += 1;
+                return self.include(line, path);
+            }
+        }
+
+        let (entry, format, quote) = match self.read_line(line)? {
+            Some(val) => val,
+            None      => return Ok(()),
+        };
+
+        match entry {
+            Entry::KeyValue(ident, value)                         => {
+                let comments = core::mem::take(&mut self.comments);
+                let (value, format) = if self.appending {
+                    // The first assignment of the key is where a later redefinition is reported
+                    if self.options.duplicate_keys == DuplicateKeyPolicy::Error {
+                        self.assignment_lines.entry(ident.clone()).or_insert(self.line_number);
+                    }
+                    (self.append(&ident, value), None)
+                } else {
+                    match self.resolve_duplicate(&ident, value, format)? {
+                        Some(val) => val,
+                        None      => return Ok(()),
+                    }
+                };
+
+                if let Value::Raw(text) = &value {
+                    if self.options.diagnostics && !self.options.infer_types && matches!(Value::parse(text), Ok(Value::Int(_)) | Ok(Value::Float(_))) {
+                        let message = format!("Value {} of key {} read as a raw string since the types are not inferred", text, ident.name());
+                        self.diagnostics.push(Diagnostic::new(Severity::Warning, self.line_number, message));
+                    }
+                }
+
+                if let Some(max) = self.options.max_keys {
+                    let data = self.variables.get_data();
+                    if !data.contains_key(&ident) && data.len() >= max {
+                        return Err(Error::from(LimitExceeded::new(Limit::Keys, max)));
+                    }
+                }
+
+                self.variables.comment_key(&ident, comments);
+                if let Some(format) = format {
+                    self.variables.set_format(ident.clone(), format);
+                }
+                let quote = match value {
+                    Value::Str(_) => quote,
+                    _             => None,
+                };
+                self.variables.set_quote(ident.clone(), quote);
+                self.variables.insert(ident, value);
+            },
+            Entry::Section(section)                               => {
+                match self.section_lines.iter_mut().find(|(name, _)| *name == section) {
+                    Some((_, lines)) => {
+                        if self.options.diagnostics {
+                            let message = format!("Section {} already declared at line {}", section, lines[0]);
+                            self.diagnostics.push(Diagnostic::new(Severity::Warning, self.line_number, message));
+                        }
+                        lines.push(self.line_number);
+                    },
+                    None             => {
+                        if let Some(max) = self.options.max_sections {
+                            if self.section_lines.len() >= max {
+                                return Err(Error::from(LimitExceeded::new(Limit::Sections, max)));
+                            }
+                        }
+                        self.section_lines.push((section.clone(), vec![self.line_number]));
+                    },
+                }
+
+                if let Some(parent) = self.cur_parent.take() {
+                    self.inherit(line, &section, parent)?;
+                }
+
+                let comments = core::mem::take(&mut self.comments);
+                self.variables.comment_section(&section, comments);
+                if let Some(path) = self.cur_path.clone() {
+                    self.variables.set_section_path(section, path);
+                }
+            },
+            Entry::Comment(comment) if self.options.keep_comments => self.comments.push(comment),
+            Entry::Comment(comment) if comment.is_empty()         => self.comments.push(comment),
+            Entry::Comment(_)                                     => {},
+        }
+
+        Ok(())
+    }
+
+    /// Returns the path written in `line` if it is an include directive (see [`ParserOptions::include_directive`](struct.ParserOptions.html#structfield.include_directive "parse::ParserOptions::include_directive")). The path may be quoted
+    #[cfg(feature = "std")]
+    fn include_path<'a>(&self, line: &'a str) -> Option<&'a str> {
+        let directive = self.options.include_directive.as_deref()?;
+        let path = line.trim().strip_prefix(directive)?;
+        if !path.starts_with(char::is_whitespace) {
+            return None;
+        }
+
+        let path = path.trim_start();
+        let unquoted = path.strip_prefix('"').and_then(|i| i.strip_suffix('"'))
+                           .or_else(|| path.strip_prefix('\'').and_then(|i| i.strip_suffix('\'')));
+        Some(unquoted.unwrap_or(path))
+    }
+
+    /// Parses the lines of the file `path` included by the directive `line`, as if they were written instead of `line`
+    /// 
+    /// # Return value
+    /// `Ok(())` in case of success
+    /// 
+    /// `Err(error)` with `error` as an [`IncludeCycle`](../errors/error_kinds/struct.IncludeCycle.html "errors::error_kinds::IncludeCycle") error if `path` is already being parsed, as an I/O error naming `path` if it can not be read or as the first error of its lines
+    #[cfg(feature = "std")]
+    fn include(&mut self, line: &str, path: &str) -> Result<(), Error> {
+        let io_error = |err: io::Error| Error::from(io::Error::new(err.kind(), format!("{}: {}", path, err)));
+
+        let full_path = match self.files.last().and_then(|file| file.parent()) {
+            Some(dir) => dir.join(path),
+            None      => PathBuf::from(path),
+        };
+        let full_path = full_path.canonicalize().map_err(io_error)?;
+        if self.files.contains(&full_path) {
+            return Err(Error::from(IncludeCycle::new(String::from(line), String::from(path))));
+        }
+
+        let content = match read_all(File::open(&full_path).map_err(io_error)?, self.options.max_line_len, Err) {
+            Ok(content)                             => content,
+            Err(ParseFileError::IOError(err))       => return Err(io_error(err)),
+            Err(ParseFileError::ParseError(err))    => return Err(err),
+            Err(ParseFileError::UnsupportedEncoding(encoding)) => {
+                let err = io::Error::new(io::ErrorKind::InvalidData, format!("unsupported encoding {}", encoding));
+                return Err(io_error(err));
+            },
+        };
+
+        self.files.push(full_path);
+        let line_number = core::mem::replace(&mut self.line_number, 0);
+        let result = self.parse_included(&content);
+        self.line_number = line_number;
+        self.files.pop();
+
+        result
+    }
+
+    /// Parses `content`, the text of an included file, until its end
+    #[cfg(feature = "std")]
+    fn parse_included(&mut self, content: &str) -> Result<(), Error> {
+        for i in content.split_terminator('\n') {
+            self.parse_line(i)?;
+        }
+
+        self.finish()
+    }
+
+    /// Retains `path` as the file being parsed, from which the include directives are resolved
+    #[cfg(feature = "std")]
+    fn set_file(&mut self, path: &Path) -> io::Result<()> {
+        if self.options.include_directive.is_some() {
+            self.files.push(path.canonicalize()?);
+        }
+
+        Ok(())
+    }
+
+    /// Copies the keys of the section `parent` which are not in the section `section` yet into `section`, declared by `line` as inheriting from `parent` (see [`ParserOptions::section_inheritance`](struct.ParserOptions.html#structfield.section_inheritance "parse::ParserOptions::section_inheritance"))
+    /// 
+    /// # Return value
+    /// `Ok(())` in case of success
+    /// 
+    /// `Err(error)` with `error` as an `UnknownParent` error if `parent` is not declared yet, as an `InheritanceCycle` error if `parent` inherits from `section` or as a `LimitExceeded` error if copying the keys exceeds `ParserOptions::max_keys`
+    fn inherit(&mut self, line: &str, section: &str, parent: String) -> Result<(), Error> {
+        if !self.section_lines.iter().any(|(name, _)| *name == parent) {
+            return Err(Error::from(UnknownParent::new(String::from(line), parent)));
+        }
+
+        let mut ancestor = Some(parent.as_str());
+        while let Some(name) = ancestor {
+            if name == section {
+                return Err(Error::from(InheritanceCycle::new(String::from(line), parent)));
+            }
+            ancestor = self.variables.section_parent(name);
+        }
+
+        let keys: Vec<Identifier> = match self.variables.section(Some(&parent)) {
+            Some(keys) => keys.keys()
+                              .map(|i| Identifier::new_unchecked(Some(String::from(section)), String::from(i.name())))
+                              .filter(|i| !self.variables.get_data().contains_key(i))
+                              .collect(),
+            None       => Vec::new(),
+        };
+        if let Some(max) = self.options.max_keys {
+            if self.variables.len() + keys.len() > max {
+                return Err(Error::from(LimitExceeded::new(Limit::Keys, max)));
+            }
+        }
+
+        for ident in keys {
+            let value = self.variables.get_value(Some(&parent), ident.name())
+                                      .expect("The keys were just listed")
+                                      .clone();
+            let quote = self.variables.quote_of(Some(&parent), ident.name());
+            self.variables.set_quote(ident.clone(), quote);
+            self.variables.insert(ident.clone(), value);
+            self.inherited.insert(ident);
+        }
+
+        self.variables.set_section_parent(String::from(section), parent);
+        Ok(())
+    }
+
+    /// Applies [`ParserOptions::duplicate_keys`](struct.ParserOptions.html#structfield.duplicate_keys "parse::ParserOptions::duplicate_keys") to the assignment of `value` to `ident`
+    /// 
+    /// # Return value
+    /// `Ok(Some((value, format)))` with `value` as the value to store and `format` as its text to retain
+    /// 
+    /// `Ok(None)` if the assignment must be ignored
+    /// 
+    /// `Err(error)` with `error` as a `RedefinedKey` error if `ident` is already assigned and the policy is `DuplicateKeyPolicy::Error`
+    fn resolve_duplicate(&mut self, ident: &Identifier, value: Value, format: Option<String>) -> Result<Option<(Value, Option<String>)>, Error> {
+        // A key inherited is overridden as if it was not assigned yet
+        let inherited = self.inherited.remove(ident);
+        let existing = match self.variables.get_data().get(ident) {
+            Some(existing) if !inherited => existing,
+            _                            => {
+                if self.options.duplicate_keys == DuplicateKeyPolicy::Error {
+                    self.assignment_lines.insert(ident.clone(), self.line_number);
+                }
+                return Ok(Some((value, format)));
+            },
+        };
+
+        match self.options.duplicate_keys {
+            DuplicateKeyPolicy::LastWins  => Ok(Some((value, format))),
+            DuplicateKeyPolicy::FirstWins => Ok(None),
+            DuplicateKeyPolicy::Error     => {
+                let first_line = self.assignment_lines.get(ident)
+                                                      .copied()
+                                                      .unwrap_or(self.line_number);
+                let section = ident.section().map(String::from);
+                Err(Error::from(RedefinedKey::new(section, String::from(ident.name()), first_line, self.line_number)))
+            },
+            DuplicateKeyPolicy::Collect   => {
+                let mut values = match existing.clone() {
+                    Value::Array(values) => values,
+                    existing             => vec![existing],
+                };
+                match value {
+                    Value::Array(value) => values.extend(value),
+                    value               => values.push(value),
+                }
+
+                // The text of a single assignment does not denote the whole array
+                Ok(Some((Value::Array(values), None)))
+            },
+        }
+    }
+
+    /// Returns the array of `ident` once `value` is appended to it, for an assignment to `key[]` (see [`ParserOptions::array_keys`](struct.ParserOptions.html#structfield.array_keys "parse::ParserOptions::array_keys")). A value already assigned to `ident` which is not an array becomes its first element
+    fn append(&self, ident: &Identifier, value: Value) -> Value {
+        let mut values = match self.variables.get_data().get(ident).cloned() {
+            Some(Value::Array(values)) => values,
+            Some(existing)             => vec![existing],
+            None                       => Vec::new(),
+        };
+        values.push(value);
+
+        Value::Array(values)
+    }
+
+    /// Reads a line and returns the entry it declares without storing it
+    /// 
+    /// # Return value
+    /// `Ok(Some((entry, format, quote)))` with `entry` as the entry declared by `line`, `format` as the text of its value if it is an assignment and the formats are retained and `quote` as the quotes around this value if any
+    /// 
+    /// `Ok(None)` if `line` is empty or continued on the next line
+    /// 
+    /// `Err(error)` in case of error with `error` as the error code
+    pub(super) fn read_line(&mut self, line: &str) -> Result<Option<ReadEntry>, Error> {
+        self.line_number += 1;
+
+        if let Some(max) = self.options.max_line_len {
+            let pending = self.pending.as_ref().map_or(0, String::len);
+            let block = self.block.as_ref().map_or(0, |block| block.text.len());
+            if line.len() + pending + block > max {
+                self.pending = None;
+                self.block = None;
+                return Err(Error::from(LimitExceeded::new(Limit::LineLength, max)));
+            }
+        }
+
+        if let Some(mut block) = self.block.take() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            return match line.find(TRIPLE_QUOTE) {
+                Some(end) => {
+                    block.text.push_str(&line[..end]);
+                    self.check_block_end(line, end)?;
+                    Ok(Some((Entry::KeyValue(block.ident, Value::Str(block.text)), None, Some(Quote::Triple))))
+                },
+                None      => {
+                    block.text.push_str(line);
+                    block.text.push('\n');
+                    self.block = Some(block);
+                    Ok(None)
+                },
+            };
+        }
+
+        let line = match self.pending.take() {
+            Some(mut pending) => {
+                pending.pop(); // The backslash
+                pending.push_str(line.trim_start());
+                Cow::Owned(pending)
+            },
+            None => Cow::Borrowed(line),
+        };
+
+        if is_continued(&line, &self.options.comment_chars, self.options.spaced_comments) {
+            let line = line.strip_suffix('\r').unwrap_or(&line);
+            self.pending = Some(String::from(line));
+            return Ok(None);
+        }
+
+        self.read_logical_line(&line)
+    }
+
+    /// Replaces each `${VAR}` of `value`, which is the value of the assignment `line`, by the value of `VAR` escaped, the other escape sequences being left as is
+    /// 
+    /// # Return value
+    /// `Ok(text)` with `text` as the expanded value
+    /// 
+    /// `Err(error)` with `error` as an [`UndefinedVariable`](../errors/error_kinds/struct.UndefinedVariable.html "errors::error_kinds::UndefinedVariable") error if a variable is not defined and `UndefinedEnvPolicy::Error` is used or as an [`ExpectedToken`](../errors/error_kinds/struct.ExpectedToken.html "errors::error_kinds::ExpectedToken") error if a `${` is not closed
+    fn expand_env<'a>(&self, line: &str, value: &'a str) -> Result<Cow<'a, str>, Error> {
+        if !value.contains("${") {
+            return Ok(Cow::Borrowed(value));
+        }
+
+        let mut expanded = String::with_capacity(value.len());
+        let mut tokens = TokenIterator::from(value.chars()).peekable();
+        while let Some(i) = tokens.next() {
+            match i {
+                Token::Char('$') if tokens.peek() == Some(&Token::Char('{')) => {
+                    tokens.next();
+
+                    let mut name = String::new();
+                    loop {
+                        match tokens.next() {
+                            Some(Token::Char('}')) => break,
+                            Some(Token::Char(c))   => name.push(c),
+                            Some(Token::Escape(s)) => name.push_str(&s),
+                            None                   => {
+                                let end = line.trim_end().len();
+                                return Err(Error::from(ExpectedToken::new(String::from(line), end, String::from("}"))));
+                            },
+                        }
+                    }
+
+                    match (self.resolve_env(&name), self.options.undefined_env) {
+                        (Some(content), _)                => expanded.push_str(&dump_str(&content)),
+                        (None, UndefinedEnvPolicy::Empty) => {},
+                        (None, UndefinedEnvPolicy::Error) => return Err(Error::from(UndefinedVariable::new(String::from(line), name))),
+                    }
+                },
+                Token::Char(c)   => expanded.push(c),
+                Token::Escape(s) => expanded.push_str(&s),
+            }
+        }
+
+        Ok(Cow::Owned(expanded))
+    }
+
+    /// Returns the value of the variable `name` expanded with [`ParserOptions::expand_env`](struct.ParserOptions.html#structfield.expand_env "parse::ParserOptions::expand_env"), or `None` if it is not defined
+    fn resolve_env(&self, name: &str) -> Option<String> {
+        match &self.options.env_resolver {
+            Some(resolver) => resolver.resolve(name),
+            #[cfg(feature = "std")]
+            None           => std::env::var(name).ok(),
+            #[cfg(not(feature = "std"))]
+            None           => None,
+        }
+    }
+
+    /// Reads a line once the line continuations are resolved
+    fn read_logical_line(&mut self, line: &str) -> Result<Option<ReadEntry>, Error> {
         let effective_line = line.trim_start();
 
         match effective_line.chars().next() {
-            None | Some(';')    => Ok(()),
-            Some(c) if c == '[' => self.parse_section(line),
-            Some(_)             => self.parse_assignment(line),
+            None if self.options.keep_blank_lines                => Ok(Some((Entry::Comment(String::new()), None, None))),
+            None                                                 => Ok(None),
+            Some(c) if self.options.comment_chars.contains(&c) => Ok(Some((Entry::Comment(String::from(effective_line.trim_end())), None, None))),
+            Some('[')                                            => {
+                self.parse_section(line)?;
+                let section = match &self.cur_section {
+                    Some(section) => section.clone(),
+                    None          => self.options.global_section_name.clone()
+                                                                     .expect("Only the global section name declares the global section"),
+                };
+                Ok(Some((Entry::Section(section), None, None)))
+            },
+            Some(_)                                              => {
+                if let Some(entry) = self.open_block(line)? {
+                    return Ok(entry.map(|entry| (entry, None, Some(Quote::Triple))));
+                }
+
+                let (ident, value, format) = self.parse_assignment(line)?;
+                let quote = match format.trim_start().chars().next() {
+                    Some('\'') => Some(Quote::Single),
+                    Some('"')  => Some(Quote::Double),
+                    _          => None,
+                };
+                let format = if self.options.keep_formats {
+                    Some(String::from(format))
+                } else {
+                    None
+                };
+
+                Ok(Some((Entry::KeyValue(ident, value), format, quote)))
+            },
         }
     }
 
@@ -109,41 +663,252 @@ impl Parser {
     /// `line` the line to parse
     /// 
     /// # Return value
-    /// `Ok(())` in case of success
+    /// `Ok((identifier, value, format))` in case of success with `identifier` as the key declared in the current section, `value` as its value and `format` as the text of this value
     /// 
     /// `Err(error)` in case of error with `error` as the error code
-    fn parse_assignment(&mut self, line: &str) -> Result<(), Error> {
+    fn parse_assignment<'a>(&mut self, line: &'a str) -> Result<(Identifier, Value, &'a str), Error> {
         // Getting the expression of `identifier` in "`identifier` = `value`[;comment]"
-        let equal = match line.find('=') {
-            Some(index) => index,
-            None        => {
+        let (equal, separator) = match find_separator(line, &self.options.kv_separators) {
+            Some(val)                      => val,
+            None if self.options.bare_keys => {
+                let end_of_ident = ignore_comment(line, &self.options.comment_chars).trim_end().len();
+                let identifier = self.parse_key(line, end_of_ident)?;
+
+                return Ok((Identifier::new_unchecked(self.cur_section.clone(), identifier), Value::Null, ""));
+            },
+            None                           => {
                 let end_of_ident = line.trim_end().len();
+                let expected = self.options.kv_separators.first().copied().unwrap_or('=');
 
-                return Err(Error::from(ExpectedToken::new(String::from(line), end_of_ident, String::from("="))));
+                return Err(Error::from(ExpectedToken::new(String::from(line), end_of_ident, String::from(expected))));
             }
         };
 
-        let identifier = String::from(line[..equal].trim());
+        let identifier = self.parse_key(line, equal)?;
 
         // Getting the expression of `value` in "`identifier` = `value`[;comment]"
-        let value = if line.len() == equal + 1 {
+        let begin_of_value = equal + separator.len_utf8();
+        let value = if line.len() == begin_of_value {
             ""
         } else {
-            ignore_comment(&line[equal + 1..]).trim()
+            ignore_comment_with(&line[begin_of_value..], &self.options.comment_chars, self.options.spaced_comments)
+        };
+        let value = if self.options.trim_whitespace {
+            value.trim()
+        } else {
+            value.strip_suffix('\r').unwrap_or(value)
         };
 
+        let format = value;
+        let escaped = match &self.options.escapes {
+            Some(escapes) => {
+                // An escaped separator of array is not an escape sequence of the table
+                let kept = self.options.array_separator.map(|c| format!("\\{}", c));
+                to_default_escapes(line, value, escapes.table(), kept.as_deref())?
+            },
+            None          => Cow::Borrowed(value),
+        };
+        let escaped = if self.options.percent_escapes {
+            match from_percent_escapes(line, &escaped)? {
+                Cow::Owned(text) => Cow::Owned(text),
+                Cow::Borrowed(_) => escaped,
+            }
+        } else {
+            escaped
+        };
+        let escaped = if self.options.spaced_comments {
+            match escape_comment_chars(&escaped, &self.options.comment_chars) {
+                Cow::Owned(text) => Cow::Owned(text),
+                Cow::Borrowed(_) => escaped,
+            }
+        } else {
+            escaped
+        };
+        let text = if self.options.expand_env {
+            self.expand_env(line, &escaped)?
+        } else {
+            Cow::Borrowed(&*escaped)
+        };
+        let mut value = match self.options.array_separator {
+            Some(separator) => Value::parse_array_with(&text, separator, self.options.allow_trailing_comma, |i| self.parse_value(i))?,
+            None            => self.parse_value(&text)?,
+        };
+
+        // `Value::parse` trims the unquoted strings so the whitespaces are given back here
+        if !self.options.trim_whitespace && !is_quoted(format) {
+            if let Value::Raw(raw) = &mut value {
+                *raw = parse_str(&text)?;
+            }
+        }
+
+        Ok((Identifier::new_unchecked(self.cur_section.clone(), identifier), value, format))
+    }
+
+    /// Returns the key assigned by `line`, whose separator is at the index `equal`
+    /// 
+    /// With [`ParserOptions::array_keys`](struct.ParserOptions.html#structfield.array_keys "parse::ParserOptions::array_keys"), `key[]` is read as `key`, setting `appending`, and `key[name]` as `key.name`
+    fn parse_key(&mut self, line: &str, equal: usize) -> Result<String, Error> {
+        // The indentation is never part of the key
+        let identifier = if self.options.trim_whitespace {
+            line[..equal].trim()
+        } else {
+            line[..equal].trim_start()
+        };
+
+        self.appending = false;
+        let array_key = identifier.strip_suffix(']')
+                                  .and_then(|i| i.split_once('['))
+                                  .filter(|_| self.options.array_keys);
+        let identifier = match array_key {
+            Some((key, ""))   => {
+                self.appending = true;
+                String::from(key)
+            },
+            Some((key, name)) => format!("{}.{}", key, name),
+            None              => String::from(identifier),
+        };
+
+        if self.options.strict_escapes {
+            if let Some(escape) = find_unknown_escape(&identifier) {
+                return Err(Error::from(InvalidEscape::new(String::from(line), escape)));
+            }
+        }
         if !Identifier::is_valid(&identifier) {
             return Err(Error::from(InvalidIdentifier::new(String::from(line), identifier)));
         }
-        let value = Value::parse(value)?;
+        Ok(identifier)
+    }
+
+    /// Reads `line` if it assigns a triple-quoted value
+    /// 
+    /// # Return value
+    /// `Ok(Some(Some(entry)))` with `entry` as the assignment if the value is closed on `line`
+    /// 
+    /// `Ok(Some(None))` if the value goes on on the next lines, which are then read by `read_line` as its text
+    /// 
+    /// `Ok(None)` if `line` is not the assignment of a triple-quoted value
+    /// 
+    /// `Err(error)` in case of error with `error` as the error code
+    fn open_block(&mut self, line: &str) -> Result<Option<Option<Entry>>, Error> {
+        let (equal, separator) = match find_separator(line, &self.options.kv_separators) {
+            Some(val) => val,
+            None      => return Ok(None),
+        };
+        let value = &line[equal + separator.len_utf8()..];
+        let begin = match value.trim_start().strip_prefix(TRIPLE_QUOTE) {
+            Some(text) => line.len() - text.len(),
+            None       => return Ok(None),
+        };
+
+        let ident = Identifier::new_unchecked(self.cur_section.clone(), self.parse_key(line, equal)?);
+        let text = &line[begin..];
+        if let Some(end) = text.find(TRIPLE_QUOTE) {
+            self.check_block_end(line, begin + end)?;
+            return Ok(Some(Some(Entry::KeyValue(ident, Value::Str(String::from(&text[..end]))))));
+        }
+
+        let text = text.strip_suffix('\r').unwrap_or(text);
+        let text = if text.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n", text)
+        };
+        self.block = Some(Block {
+            ident,
+            line: String::from(line.strip_suffix('\r').unwrap_or(line)),
+            text,
+        });
+
+        Ok(Some(None))
+    }
+
+    /// Ensures only whitespaces and a comment follow the closing `"""` of a triple-quoted value, found at the index `end` of `line`
+    fn check_block_end(&self, line: &str, end: usize) -> Result<(), Error> {
+        let begin = end + TRIPLE_QUOTE.len();
+        for (n, i) in line[begin..].char_indices() {
+            if self.options.comment_chars.contains(&i) {
+                break;
+            } else if !i.is_whitespace() {
+                return Err(Error::from(UnexpectedToken::new(String::from(line), begin + n)));
+            }
+        }
 
-        self.variables.insert(
-            Identifier::new(self.cur_section.clone(), identifier),
-            value,
-        );
         Ok(())
     }
 
+    /// Parses a single value, inferring its type or reading its type annotation if asked by the options of `self`
+    fn parse_value(&self, content: &str) -> Result<Value, Error> {
+        if let Some(token) = &self.options.null_token {
+            if content.trim().eq_ignore_ascii_case(token) {
+                return Ok(Value::Null);
+            }
+        }
+
+        if self.options.type_annotations {
+            if let Some(colon) = find_annotation(content) {
+                return self.parse_annotated(content, colon);
+            }
+        }
+
+        if !self.options.infer_types {
+            return Value::parse_untyped(content);
+        }
+        self.infer_value(content)
+    }
+
+    /// Parses `content`, a value followed by a type annotation after the `:` at index `colon`
+    fn parse_annotated(&self, content: &str, colon: usize) -> Result<Value, Error> {
+        let literal = &content[..colon];
+        let annotation = content[colon + 1..].trim();
+        let keyword = if self.options.case_insensitive_keywords {
+            Cow::Owned(annotation.to_ascii_lowercase())
+        } else {
+            Cow::Borrowed(annotation)
+        };
+
+        let value = match keyword.as_ref() {
+            "raw" => Value::parse_untyped(literal)?,
+            "str" => match Value::parse_untyped(literal)? {
+                Value::Raw(string) => Value::Str(string),
+                value              => value,
+            },
+            _     => match (keyword.as_ref(), self.infer_value(literal)?) {
+                ("int", Value::Int(number))     => Value::Int(number),
+                ("float", Value::Float(number)) => Value::Float(number),
+                ("float", Value::Int(number))   => Value::Float(number as f64),
+                ("bool", Value::Bool(boolean))  => Value::Bool(boolean),
+                _                               => {
+                    let err = InvalidAnnotation::new(String::from(content), String::from(literal.trim()), String::from(annotation));
+                    return Err(Error::from(err));
+                },
+            },
+        };
+
+        Ok(value)
+    }
+
+    /// Parses a single value, inferring its type
+    fn infer_value(&self, content: &str) -> Result<Value, Error> {
+        let effective = content.trim();
+        if self.options.parse_colors && effective.starts_with('#') && !self.options.comment_chars.contains(&'#') {
+            return Ok(Value::parse_color(effective));
+        }
+
+        if let Some(tokens) = &self.options.bool_tokens {
+            if let Some(boolean) = tokens.parse(effective) {
+                return Ok(Value::Bool(boolean));
+            }
+        }
+
+        if self.options.case_insensitive_keywords {
+            if let Some(boolean) = parse_bool_keyword(&effective.to_ascii_lowercase()) {
+                return Ok(Value::Bool(boolean));
+            }
+        }
+
+        Value::parse(content)
+    }
+
     /// Parses a section declaration. A section declaration is of form
     /// 
     /// ```ini
@@ -181,40 +946,285 @@ impl Parser {
             }
         }
 
-        // end == 0 means that there isn't any ']' while end == 1 means that the section name is empty
+        // end == 0 means that there isn't any ']'
         if end == 0 {
             return Err(Error::from(ExpectedToken::new(String::from(line), leading_spaces, String::from("]"))));
-        } else if end == 1 {
-            return Err(Error::from(ExpectedIdentifier::new(String::from(line), leading_spaces + 1)));
         }
 
+        // `begin` is the index of the section name in `line`, once the whitespaces inside the brackets are trimmed as in `[ section ]`
         let section = &line[leading_spaces + 1..end];
-        if !Identifier::is_valid(section) {
-            return Err(Error::from(InvalidIdentifier::new(String::from(line), String::from(section))));
+        let (section, begin) = if self.options.trim_whitespace {
+            let trimmed = section.trim_start();
+            (trimmed.trim_end(), end - trimmed.len())
+        } else {
+            (section, leading_spaces + 1)
+        };
+        if section.is_empty() {
+            return Err(Error::from(ExpectedIdentifier::new(String::from(line), begin)));
         }
 
+        // A nested parent keeps its literal dots escaped like its name
+        let is_valid_parent = if self.options.nested_sections {
+            Identifier::is_valid_section
+        } else {
+            Identifier::is_valid
+        };
+        let (section, parent) = match section.split_once(':') {
+            Some((child, parent)) if self.options.section_inheritance => {
+                let parent = parent.trim();
+                if parent.is_empty() {
+                    return Err(Error::from(ExpectedIdentifier::new(String::from(line), end)));
+                } else if !is_valid_parent(parent) {
+                    return Err(Error::from(InvalidIdentifier::new(String::from(line), String::from(parent))));
+                }
+
+                (child.trim_end(), Some(String::from(parent)))
+            },
+            _                                                         => (section, None),
+        };
+        if section.is_empty() {
+            return Err(Error::from(ExpectedIdentifier::new(String::from(line), begin)));
+        }
+
+        let path = if self.options.nested_sections {
+            let path = split_unescaped(section, '.');
+            for i in &path {
+                if i.is_empty() {
+                    // An empty section name is either at the beginning, between two dots or at the end
+                    let index = if section.starts_with('.') {
+                        0
+                    } else {
+                        section.find("..").map(|n| n + 1).unwrap_or(section.len())
+                    };
+
+                    return Err(Error::from(ExpectedIdentifier::new(String::from(line), begin + index)));
+                } else if !Identifier::is_valid(i) {
+                    // An escaped dot makes the name differ from its text in `line`
+                    let ident = if line.contains(i.as_str()) { i.as_str() } else { section };
+                    return Err(Error::from(InvalidIdentifier::new(String::from(line), String::from(ident))));
+                }
+            }
+
+            Some(path)
+        } else if !Identifier::is_valid(section) {
+            return Err(Error::from(InvalidIdentifier::new(String::from(line), String::from(section))));
+        } else {
+            None
+        };
+
         // Checking integrity: I want to ensure there is no extra character after the section declaration
-        // The only ones allowed are the whitespaces and the semicolon (with all the following ones)
+        // The only ones allowed are the whitespaces and the comment characters (with all the following ones)
         for (n, i) in iter {
-            if i == ';' {
+            if self.options.comment_chars.contains(&i) {
                 break;
             } else if !i.is_whitespace() {
-                let line = String::from(line);
-                return Err(Error::from(UnexpectedToken::new(line, leading_spaces  // The leading spaces ignored
-                                                                  + 2             // The '[' and ']' characters
-                                                                  + section.len() // The identifier
-                                                                  + n)));         // The index after the ']' character
+                // `n` is the index of the offending character in the whole line
+                return Err(Error::from(UnexpectedToken::new(String::from(line), n)));
             }
         }
 
-        self.cur_section = Some(String::from(section));
+        // A literal dot stays escaped so that `[a\\.b]` and the nested `[a.b]` are two different sections
+        let section = match &path {
+            Some(path) => {
+                let path: Vec<String> = path.iter()
+                                            .map(|i| i.replace('.', "\\."))
+                                            .collect();
+                path.join(".")
+            },
+            None       => String::from(section),
+        };
+
+        self.cur_parent = parent;
+        if self.options.global_section_name.as_ref() == Some(&section) {
+            self.cur_section = None;
+            self.cur_path = None;
+        } else {
+            self.cur_section = Some(section);
+            self.cur_path = path;
+        }
         Ok(())
     }
 }
 
-/// Returns a subslice of the given slice which is comment-free (stopped at the first non-escaped semicolon ';'). `line` should be a single line
-fn ignore_comment(line: &str) -> &str { 
-    &line[..super::find_unescaped(line, ';').unwrap_or(line.len())]
+impl Default for Parser {
+    fn default() -> Self {
+        Parser::new()
+    }
+}
+
+/// Returns `true` if `line` ends with a non-escaped backslash which is not part of a comment, which means it continues on the next line. `comment_chars` and `spaced` tell where a comment starts, as in [`ignore_comment_with`](fn.ignore_comment_with.html "parse::ignore_comment_with")
+fn is_continued(line: &str, comment_chars: &[char], spaced: bool) -> bool {
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    // A backslash ending a comment, on its own line or after a value, is part of its text
+    if ignore_comment_with(line, comment_chars, spaced).len() < line.len() {
+        return false;
+    }
+
+    // An escape sequence ending with a backslash is necessarily `\\` so an odd number of backslashes means the last one is not escaped
+    let backslashes = line.bytes()
+                          .rev()
+                          .take_while(|&c| c == b'\\')
+                          .count();
+    backslashes % 2 == 1
+}
+
+/// Returns the index of the first character of `separators` in `line` which is outside of quotes, and this character
+pub(crate) fn find_separator(line: &str, separators: &[char]) -> Option<(usize, char)> {
+    if line.is_ascii() {
+        find_separator_in(ascii_indices(line), separators)
+    } else {
+        find_separator_in(line.char_indices(), separators)
+    }
+}
+
+/// Returns the characters of `line`, which is ASCII, with their index like `char_indices` but without decoding UTF-8
+fn ascii_indices(line: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    line.bytes()
+        .enumerate()
+        .map(|(n, c)| (n, char::from(c)))
+}
+
+/// Implements [`find_separator`](fn.find_separator.html "parse::find_separator") over the characters of a line with their index
+fn find_separator_in<T: Iterator<Item = (usize, char)>>(chars: T, separators: &[char]) -> Option<(usize, char)> {
+    let mut quote = None;
+    for (n, i) in chars {
+        if quote == Some(i) {
+            quote = None;
+        } else if quote.is_none() && (i == '\'' || i == '"') {
+            quote = Some(i);
+        } else if quote.is_none() && separators.contains(&i) {
+            return Some((n, i));
+        }
+    }
+
+    None
+}
+
+/// Returns the first escape sequence of `content` which is not a known one, such as `\q`
+fn find_unknown_escape(content: &str) -> Option<String> {
+    TokenIterator::from(content.chars()).find_map(|i| match i {
+        Token::Escape(escape) if parse_str(&escape).is_err() => Some(escape),
+        _                                                    => None,
+    })
+}
+
+/// Returns the index of the last `:` of `value` which is neither escaped nor inside quotes, which starts a type annotation
+fn find_annotation(value: &str) -> Option<usize> {
+    let mut found = None;
+    let mut quote = None;
+    let mut escaped = false;
+    for (n, i) in value.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if i == '\\' {
+            escaped = true;
+        } else if quote == Some(i) {
+            quote = None;
+        } else if quote.is_none() && (i == '\'' || i == '"') {
+            quote = Some(i);
+        } else if quote.is_none() && i == ':' {
+            found = Some(n);
+        }
+    }
+
+    found
+}
+
+/// Returns a subslice of the given slice which is comment-free (stopped at the first non-escaped comment character out of quotes). `line` should be a single line
+pub(crate) fn ignore_comment<'a>(line: &'a str, comment_chars: &[char]) -> &'a str {
+    ignore_comment_with(line, comment_chars, false)
+}
+
+/// Like [`ignore_comment`](fn.ignore_comment.html "parse::ignore_comment") but, if `spaced` is `true`, a comment character only starts a comment at the beginning of `line` or after a whitespace (see [`ParserOptions::spaced_comments`](struct.ParserOptions.html#structfield.spaced_comments "parse::ParserOptions::spaced_comments"))
+pub(crate) fn ignore_comment_with<'a>(line: &'a str, comment_chars: &[char], spaced: bool) -> &'a str {
+    let end = if line.is_ascii() {
+        comment_start(ascii_indices(line), comment_chars, spaced)
+    } else {
+        comment_start(line.char_indices(), comment_chars, spaced)
+    };
+
+    match end {
+        Some(end) => &line[..end],
+        None      => line,
+    }
+}
+
+/// Returns the index of the comment character starting a comment among the characters of a line with their index, as found by [`ignore_comment`](fn.ignore_comment.html "parse::ignore_comment")
+fn comment_start<T: Iterator<Item = (usize, char)>>(chars: T, comment_chars: &[char], spaced: bool) -> Option<usize> {
+    // possible values of `escape`
+    // -1   : the last character parsed is a '\\'
+    // 0    : this character must be read because it's unescaped
+    // 1..6 : this character must be ignored if it is an hexadecimal digit because it belongs to an escape sequence
+    let mut escape = 0;
+    let mut quote = None;
+    let mut previous = None;
+    for (n, i) in chars {
+        let before = previous.replace(i);
+        if escape == -1 {
+            escape = if i == 'x' {
+                6
+            } else {
+                0
+            };
+            continue;
+        }
+
+        if escape > 0 && i.is_ascii_hexdigit() {
+            escape -= 1;
+            continue;
+        }
+        escape = 0;
+
+        if i == '\\' {
+            escape = -1;
+        } else if quote == Some(i) {
+            quote = None;
+        } else if quote.is_none() && (i == '\'' || i == '"') {
+            quote = Some(i);
+        } else if quote.is_none() && comment_chars.contains(&i) && !(spaced && before.is_some_and(|c| !c.is_whitespace())) {
+            return Some(n);
+        }
+    }
+
+    None
+}
+
+/// Escapes the comment characters of `value`, which do not start a comment since they are not preceded by a whitespace, so that they are read as written
+fn escape_comment_chars<'a>(value: &'a str, comment_chars: &[char]) -> Cow<'a, str> {
+    if !value.contains(comment_chars) {
+        return Cow::Borrowed(value);
+    }
+
+    let mut text = String::with_capacity(value.len() + 1);
+    for i in TokenIterator::from(value.chars()) {
+        match i {
+            Token::Char(c) if comment_chars.contains(&c) => text.push_str(&dump_str(c.encode_utf8(&mut [0; 4]))),
+            Token::Char(c)                               => text.push(c),
+            Token::Escape(escape)                        => text.push_str(&escape),
+        }
+    }
+
+    Cow::Owned(text)
+}
+
+/// The delimiter of a value spanning several lines
+pub(crate) const TRIPLE_QUOTE: &str = "\"\"\"";
+
+/// A triple-quoted value spanning several lines, being read
+#[derive(Debug, Clone)]
+struct Block {
+    /// The key the value is assigned to
+    ident: Identifier,
+    /// The line opening the value
+    line: String,
+    /// The text of the value read so far
+    text: String,
+}
+
+/// Returns `true` if `value` is a quoted string, ignoring the whitespaces around
+fn is_quoted(value: &str) -> bool {
+    let value = value.trim_start();
+    value.starts_with('\'') || value.starts_with('"')
 }
 
 /// Reads in an INI file and returns the parsed data
@@ -226,33 +1236,299 @@ fn ignore_comment(line: &str) -> &str {
 /// `Ok(data)` in case of success with `data` as a `HashMap<Identifier, Value>` linking each identifier to its associated value
 /// 
 /// `Err(error)` in case of failure with `error` as an error code for either an I/O error or a parsing error (see [ParseFileError](../errors/enum.ParseFileError.html "errors::ParseFileError"))
+#[cfg(feature = "std")]
 pub fn parse_file<T: AsRef<Path>>(path: T) -> Result<HashMap<Identifier, Value>, ParseFileError> {
-    let mut file = File::open(path)?;
+    let tree = parse_file_with_options(path, ParserOptions::default())?;
 
-    let mut content = String::new();
-    file.read_to_string(&mut content)?;
-    let content = content;
+    Ok(tree.into_data())
+}
 
-    let mut parser = Parser::new();
+/// Reads in an INI file following `options` and returns the parsed data
+/// 
+/// # Parameters
+/// `path` the path of the file to open
+/// 
+/// `options` the options of the parser (see [`ParserOptions`](struct.ParserOptions.html "parse::ParserOptions"))
+/// 
+/// # Return value
+/// `Ok(tree)` in case of success with `tree` as a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") keeping the order of declaration of the keys
+/// 
+/// `Err(error)` in case of failure with `error` as an error code for either an I/O error or a parsing error (see [ParseFileError](../errors/enum.ParseFileError.html "errors::ParseFileError")). A parsing error knows the number of the line where it was detected (see [`Error::line_number`](../errors/enum.Error.html#method.line_number "errors::Error::line_number"))
+/// 
+/// # Encoding
+/// The file must be encoded in UTF-8. A leading UTF-8 byte-order mark is ignored while a UTF-16 one is reported as a `ParseFileError::UnsupportedEncoding`
+#[cfg(feature = "std")]
+pub fn parse_file_with_options<T: AsRef<Path>>(path: T, options: ParserOptions) -> Result<Tree, ParseFileError> {
+    let path = path.as_ref();
+    let content = read_all(File::open(path)?, options.max_line_len, Err)?;
 
-    let mut begin = 0;
-    while begin < content.len() {
-        let end = match &content[begin..].find('\n') {
-            Some(val) => val + begin,
-            None      => content.len(),
-        };
-        if begin == end {
-            begin += 1;
-            continue;
+    let mut parser = Parser::with_options(options);
+    parser.set_file(path)?;
+    parse_with(&mut parser, &content)?;
+
+    Ok(parser.into_tree())
+}
+
+/// Reads in an INI file following `options` like [`parse_file_with_options`](fn.parse_file_with_options.html "parse::parse_file_with_options") and also returns the notices about its valid lines
+/// 
+/// # Return value
+/// `Ok((tree, diagnostics))` with `tree` as the data of the file and `diagnostics` as the notices found, in their order in the file (see [`Diagnostic`](struct.Diagnostic.html "parse::Diagnostic"))
+/// 
+/// `Err(error)` in case of failure with `error` as an error code for either an I/O error or a parsing error, as returned by `parse_file_with_options`
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{parse_file_with_diagnostics, ParserOptions};
+/// 
+/// let (tree, diagnostics) = parse_file_with_diagnostics("good.ini", ParserOptions::default()).unwrap();
+/// for i in &diagnostics {
+///     eprintln!("good.ini: {}", i);
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_file_with_diagnostics<T: AsRef<Path>>(path: T, options: ParserOptions) -> Result<(Tree, Vec<Diagnostic>), ParseFileError> {
+    let path = path.as_ref();
+    let content = read_all(File::open(path)?, options.max_line_len, Err)?;
+
+    let mut parser = Parser::with_options(ParserOptions {
+        diagnostics: true,
+        ..options
+    });
+    parser.set_file(path)?;
+    parse_with(&mut parser, &content)?;
+    let diagnostics = core::mem::take(&mut parser.diagnostics);
+
+    Ok((parser.into_tree(), diagnostics))
+}
+
+/// Reads INI data from `reader` until its end and returns the parsed data
+/// 
+/// # Return value
+/// `Ok(tree)` in case of success with `tree` as a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") keeping the order of declaration of the keys
+/// 
+/// `Err(error)` in case of failure with `error` as an error code for either an I/O error or a parsing error (see [ParseFileError](../errors/enum.ParseFileError.html "errors::ParseFileError"))
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::parse_reader;
+/// use mininip::datas::Value;
+/// 
+/// let content: &[u8] = b"[server]\nport = 8080\n";
+/// let tree = parse_reader(content).unwrap();
+/// assert_eq!(tree.get_value(Some("server"), "port"), Some(&Value::Int(8080)));
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_reader<R: Read>(reader: R) -> Result<Tree, ParseFileError> {
+    parse_reader_with_options(reader, ParserOptions::default())
+}
+
+/// Reads INI data from `reader` until its end following `options` and returns the parsed data
+/// 
+/// # See
+/// [`parse_file_with_options`](fn.parse_file_with_options.html "parse::parse_file_with_options") for the return value and the encoding, which are the same
+#[cfg(feature = "std")]
+pub fn parse_reader_with_options<R: Read>(reader: R, options: ParserOptions) -> Result<Tree, ParseFileError> {
+    let content = read_all(reader, options.max_line_len, Err)?;
+
+    // `read_all` already stripped the byte-order mark
+    let mut parser = Parser::with_options(options);
+    parse_with(&mut parser, &content)?;
+
+    Ok(parser.into_tree())
+}
+
+/// Parses `content`, the whole text of an INI file, and returns the parsed data
+/// 
+/// # Return value
+/// `Ok(tree)` in case of success with `tree` as a [`Tree`](../datas/tree/struct.Tree.html "datas::tree::Tree") keeping the order of declaration of the keys
+/// 
+/// `Err(error)` in case of failure with `error` as the error code, knowing the number of its line (see [`Error::line_number`](../errors/enum.Error.html#method.line_number "errors::Error::line_number"))
+/// 
+/// # Note
+/// [`parse_str`](fn.parse_str.html "parse::parse_str") does not parse an INI file but a single string as written in a value
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::parse_content;
+/// use mininip::datas::Value;
+/// 
+/// let tree = parse_content("name = mininip\n[dependencies]\nserde = 1.0\n").unwrap();
+/// assert_eq!(tree.get_value(None, "name"), Some(&Value::Raw(String::from("mininip"))));
+/// assert_eq!(tree.get_value(Some("dependencies"), "serde"), Some(&Value::Float(1.0)));
+/// ```
+pub fn parse_content(content: &str) -> Result<Tree, Error> {
+    parse_content_with_options(content, ParserOptions::default())
+}
+
+/// Parses `content`, the whole text of an INI file, following `options` and returns the parsed data
+/// 
+/// # See
+/// [`parse_content`](fn.parse_content.html "parse::parse_content") for the return value. A leading byte-order mark is ignored
+pub fn parse_content_with_options(content: &str, options: ParserOptions) -> Result<Tree, Error> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut parser = Parser::with_options(options);
+    parse_with(&mut parser, content)?;
+
+    Ok(parser.into_tree())
+}
+
+/// Parses all the lines of `content` with `parser`. A byte-order mark must have been stripped before, once
+/// 
+/// # Return value
+/// `Ok(())` in case of success
+/// 
+/// `Err(error)` in case of failure with `error` as the error code, knowing the number of its line
+fn parse_with(parser: &mut Parser, content: &str) -> Result<(), Error> {
+    let mut line_number = 0;
+    for line in content.split_terminator('\n') {
+        line_number += 1;
+        parser.parse_line(line)
+              .map_err(|err| err.with_line_number(line_number))?;
+    }
+    parser.finish()
+          .map_err(|err| err.with_line_number(line_number))
+}
+
+/// Reads in an INI file following `options` like [`parse_file_with_options`](fn.parse_file_with_options.html "parse::parse_file_with_options") but goes on after an invalid line instead of stopping at the first error
+/// 
+/// An invalid line is skipped. After an invalid section declaration, such as `[bad name!]`, the keys are skipped until the next valid one since they would be put in a wrong section. Only a section declaration which never closes, such as `[section`, stops the parsing
+/// 
+/// # Return value
+/// `Ok((tree, errors))` with `tree` as the data of the valid lines and `errors` as the errors found, in their order in the file, each knowing the number of its line
+/// 
+/// `Err(error)` in case of failure with `error` as an error code for an I/O error
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{parse_file_lossy, ParserOptions};
+/// 
+/// let (tree, errors) = parse_file_lossy("bad.ini", ParserOptions::default()).unwrap();
+/// 
+/// // The only line of this file is not valid
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].line_number(), Some(1));
+/// assert!(tree.get_data().is_empty());
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_file_lossy<T: AsRef<Path>>(path: T, options: ParserOptions) -> Result<(Tree, Vec<Error>), ParseFileError> {
+    let (parser, errors) = parse_lossy(path.as_ref(), options)?;
+
+    Ok((parser.into_tree(), errors))
+}
+
+/// Reads in an INI file like [`parse_file_lossy`](fn.parse_file_lossy.html "parse::parse_file_lossy") and also reports the sections declared more than once
+/// 
+/// # Return value
+/// `Ok((tree, errors, duplicates))` with `tree` and `errors` as returned by `parse_file_lossy` and `duplicates` as the sections declared more than once with the numbers of the lines declaring them (see [`Parser::duplicate_sections`](struct.Parser.html#method.duplicate_sections "parse::Parser::duplicate_sections"))
+/// 
+/// `Err(error)` in case of failure with `error` as an error code for an I/O error
+#[cfg(feature = "std")]
+pub fn parse_file_lossy_with_duplicates<T: AsRef<Path>>(path: T, options: ParserOptions) -> Result<(Tree, Vec<Error>, DuplicateSections), ParseFileError> {
+    let (parser, errors) = parse_lossy(path.as_ref(), options)?;
+    let duplicates = parser.duplicate_sections();
+
+    Ok((parser.into_tree(), errors, duplicates))
+}
+
+/// Reads in the file `path` following `options`, skipping the invalid lines like [`parse_file_lossy`](fn.parse_file_lossy.html "parse::parse_file_lossy")
+/// 
+/// # Return value
+/// `Ok((parser, errors))` with `parser` as the parser which read the file and `errors` as the errors found
+/// 
+/// `Err(error)` in case of failure with `error` as an error code for an I/O error
+#[cfg(feature = "std")]
+fn parse_lossy(path: &Path, options: ParserOptions) -> Result<(Parser, Vec<Error>), ParseFileError> {
+    // The overlong lines are read as empty ones and their errors are put back in order below
+    let mut long_lines = Vec::new();
+    let content = read_all(File::open(path)?, options.max_line_len, |err| {
+        long_lines.push(err);
+        Ok(())
+    })?;
+    let mut long_lines = long_lines.into_iter().peekable();
+    let mut parser = Parser::with_options(options);
+    parser.set_file(path)?;
+    let mut errors = Vec::new();
+
+    // Set after an invalid section declaration so that its keys are not put in the previous section
+    let mut skipping = false;
+    let mut line_number = 0;
+    for line in content.split_terminator('\n') {
+        line_number += 1;
+        let header = line.trim_start().starts_with('[');
+        // A skipped line is still counted by the parser
+        let result = parser.parse_line(if skipping && !header { "" } else { line });
+        if long_lines.peek().is_some_and(|err| err.line_number() == Some(line_number)) {
+            errors.extend(long_lines.next());
+        } else if let Err(err) = result {
+            if header {
+                // A section declaration which never closes leaves the structure of the file unknown
+                if let Error::ExpectedToken(_) = err {
+                    errors.push(err.with_line_number(line_number));
+                    return Ok((parser, errors));
+                }
+                skipping = true;
+            }
+            errors.push(err.with_line_number(line_number));
+        } else if header {
+            skipping = false;
+        }
+    }
+    if let Err(err) = parser.finish() {
+        errors.push(err.with_line_number(line_number));
+    }
+
+    Ok((parser, errors))
+}
+
+/// Reads the content of `reader` until its end without storing a line longer than `max_line_len` bytes (see [`ParserOptions::max_line_len`](struct.ParserOptions.html#structfield.max_line_len "parse::ParserOptions::max_line_len"))
+/// 
+/// An overlong line is given to `on_long_line` as a `LimitExceeded` error knowing the number of its line. Returning this error stops the reading before the rest of the line is read while returning `Ok(())` skips the line, read as an empty one
+#[cfg(feature = "std")]
+fn read_all<R: Read, F: FnMut(Error) -> Result<(), Error>>(mut reader: R, max_line_len: Option<usize>, mut on_long_line: F) -> Result<String, ParseFileError> {
+    let mut content = Vec::new();
+    let max = match max_line_len {
+        Some(max) => max,
+        None      => {
+            reader.read_to_end(&mut content)?;
+            return decode(content);
+        },
+    };
+
+    let mut reader = BufReader::new(reader);
+    let mut line_number = 0;
+    loop {
+        line_number += 1;
+        match read_bounded_line(&mut reader, &mut content, Some(max))? {
+            Some(0) => break,
+            Some(_) => {},
+            None    => {
+                on_long_line(Error::from(LimitExceeded::new(Limit::LineLength, max)).with_line_number(line_number))?;
+                skip_line(&mut reader)?;
+                content.push(b'\n');
+            },
         }
+    }
 
-        let line = &content[begin..end];
-        parser.parse_line(line)?;
+    decode(content)
+}
 
-        begin = end + 1;
+/// Decodes the content of an INI file, stripping its UTF-8 byte-order mark if any
+/// 
+/// # Return value
+/// `Ok(content)` with `content` as the text of the file
+/// 
+/// `Err(error)` with `error` as a `ParseFileError::UnsupportedEncoding` if `bytes` starts with a UTF-16 byte-order mark or as a `ParseFileError::IOError` if `bytes` is not valid UTF-8
+#[cfg(feature = "std")]
+fn decode(mut bytes: Vec<u8>) -> Result<String, ParseFileError> {
+    if bytes.starts_with(&[0xFF, 0xFE]) {
+        return Err(ParseFileError::UnsupportedEncoding("UTF-16 LE"));
+    } else if bytes.starts_with(&[0xFE, 0xFF]) {
+        return Err(ParseFileError::UnsupportedEncoding("UTF-16 BE"));
+    } else if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        bytes.drain(..3);
     }
 
-    Ok(parser.data())
+    String::from_utf8(bytes).map_err(|err| ParseFileError::from(io::Error::new(io::ErrorKind::InvalidData, err)))
 }
 
 
@@ -7,7 +7,7 @@ fn parser_parse_assignment_simplest() {
     let expr = "ident=val";
     let mut parser = Parser::new();
 
-    parser.parse_assignment(expr)
+    parser.parse_line(expr)
         .expect("This code should be accepted because it's a valid INI assignment");
 
     let data = parser.data();
@@ -21,7 +21,7 @@ fn parser_parse_assignment_commented() {
     let expr = "ident=val;This is a comment";
     let mut parser = Parser::new();
 
-    parser.parse_assignment(expr)
+    parser.parse_line(expr)
         .expect("This code should be accepted because it's a valid INI assignment");
 
     let data = parser.data();
@@ -35,7 +35,7 @@ fn parser_parse_assignment_with_spaces() {
     let expr = "ident = val";
     let mut parser = Parser::new();
 
-    parser.parse_assignment(expr)
+    parser.parse_line(expr)
         .expect("This code should be accepted because it's a valid INI assignment");
 
     let data = parser.data();
@@ -49,7 +49,7 @@ fn parser_parse_assignment_with_comment_and_spaces() {
     let expr = "ident=val ; This is a comment";
     let mut parser = Parser::new();
 
-    parser.parse_assignment(expr)
+    parser.parse_line(expr)
         .expect("This code should be accepted because it's a valid INI assignment");
 
     let data = parser.data();
@@ -63,7 +63,7 @@ fn parser_parse_assignment_with_leading_spaces() {
     let expr = "    ident=val";
     let mut parser = Parser::new();
 
-    parser.parse_assignment(expr)
+    parser.parse_line(expr)
         .expect("This code should be accepted because it's a valid INI assignment");
 
     let data = parser.data();
@@ -77,7 +77,7 @@ fn parser_parse_assignment_unicode_value() {
     let expr = r"latin_small_letter_e_with_acute=\x0000e9";
     let mut parser = Parser::new();
 
-    parser.parse_assignment(expr)
+    parser.parse_line(expr)
         .expect("This code should be accepted because it's a valid INI assignment");
 
     let data = parser.data();
@@ -91,7 +91,7 @@ fn parser_parse_assignment_unicode_comment() {
     let expr = "ident=val; C'est un cas tout à fait valid"; // Notice the 'à' in the comment
     let mut parser = Parser::new();
 
-    parser.parse_assignment(expr)
+    parser.parse_line(expr)
         .expect("This code should be accepted because it's a valid INI assignment");
 
     let data = parser.data();
@@ -105,7 +105,7 @@ fn parser_parse_assignment_str() {
     let expr = "ident='Hello world!'";
     let mut parser = Parser::new();
 
-    parser.parse_assignment(expr)
+    parser.parse_line(expr)
         .expect("This code should be accepted because it's a valid INI assignment");
 
     let data = parser.data();
@@ -119,7 +119,7 @@ fn parser_parse_assignment_int() {
     let expr = "ident=0";
     let mut parser = Parser::new();
 
-    parser.parse_assignment(expr)
+    parser.parse_line(expr)
         .expect("This code should be accepted because it's a valid INI assignment");
 
     let data = parser.data();
@@ -133,7 +133,7 @@ fn parser_parse_assignment_float() {
     let expr = "ident=0.0";
     let mut parser = Parser::new();
 
-    parser.parse_assignment(expr)
+    parser.parse_line(expr)
         .expect("This code should be accepted because it's a valid INI assignment");
 
     let data = parser.data();
@@ -147,7 +147,7 @@ fn parser_parse_assignment_bool() {
     let expr = "ident=on";
     let mut parser = Parser::new();
 
-    parser.parse_assignment(expr)
+    parser.parse_line(expr)
         .expect("This code should be accepted because it's a valid INI assignment");
 
     let data = parser.data();
@@ -161,7 +161,7 @@ fn parser_parse_assignment_unicode_identifier() {
     let expr = r"é=\x0000e9";
     let mut parser = Parser::new();
 
-    match parser.parse_assignment(expr) {
+    match parser.parse_line(expr) {
         Ok(())                           => panic!("This code is wrong and shouldn't be accepted"),
         Err(Error::InvalidIdentifier(_)) => {},
         Err(err)                         => panic!("Wrong return value for this error: {:?}", err),
@@ -173,7 +173,7 @@ fn parser_parse_assignment_bad_ident() {
     let expr = "my*identifier=val";
     let mut parser = Parser::new();
 
-    match parser.parse_assignment(expr) {
+    match parser.parse_line(expr) {
         Ok(())                           => panic!("This code is wrong and shouldn't be accepted"),
         Err(Error::InvalidIdentifier(_)) => {},
         Err(err)                         => panic!("Wrong return value for this error: {:?}", err),
@@ -185,7 +185,7 @@ fn parser_parse_assignment_bad_value() {
     let expr = "ident=abc=123";
     let mut parser = Parser::new();
 
-    match parser.parse_assignment(expr) {
+    match parser.parse_line(expr) {
         Ok(())                        => panic!("This code is wrong and shouldn't be accepted"),
         Err(Error::ExpectedEscape(_)) => {},
         Err(err)                      => panic!("Wrong return value for this error: {:?}", err),
@@ -197,7 +197,7 @@ fn parser_parse_assignment_no_value() {
     let expr = "ident=";
     let mut parser = Parser::new();
 
-    parser.parse_assignment(expr)
+    parser.parse_line(expr)
         .expect("This code should be accepted because it's a valid INI assignment");
 
     let data = parser.data();
@@ -216,7 +216,7 @@ fn parser_parse_section_simplest() {
     
     assert_eq!(parser.cur_section, Some(String::from("section")));
 
-    parser.parse_assignment("ident=val").unwrap();
+    parser.parse_line("ident=val").unwrap();
 
     let data = parser.data();
     let key = Identifier::new(Some(String::from("section")), String::from("ident"));
@@ -234,7 +234,7 @@ fn parser_parse_section_with_comment() {
     
     assert_eq!(parser.cur_section, Some(String::from("section")));
 
-    parser.parse_assignment("ident=val").unwrap();
+    parser.parse_line("ident=val").unwrap();
 
     let data = parser.data();
     let key = Identifier::new(Some(String::from("section")), String::from("ident"));
@@ -252,7 +252,7 @@ fn parser_parse_section_with_comment_and_whitespaces() {
     
     assert_eq!(parser.cur_section, Some(String::from("section")));
 
-    parser.parse_assignment("ident=val").unwrap();
+    parser.parse_line("ident=val").unwrap();
 
     let data = parser.data();
     let key = Identifier::new(Some(String::from("section")), String::from("ident"));
@@ -270,7 +270,7 @@ fn parser_parse_section_with_leading_spaces() {
 
     assert_eq!(parser.cur_section, Some(String::from("section")));
 
-    parser.parse_assignment("ident=val").unwrap();
+    parser.parse_line("ident=val").unwrap();
 
     let data = parser.data();
     let key = Identifier::new(Some(String::from("section")), String::from("ident"));
@@ -299,6 +299,23 @@ fn parser_parse_section_ending_extra_token() {
     }
 }
 
+#[test]
+fn parser_parse_section_trailing_garbage_position() {
+    let mut parser = Parser::new();
+
+    for &(line, column) in &[("[section]]", 10), ("  [section] extra", 13)] {
+        match parser.parse_section(line) {
+            Err(Error::UnexpectedToken(err)) => {
+                assert_eq!(err.column(), column);
+                assert_eq!(format!("{}", err), format!("Unexpected token {} {}{{here}}", &line[column - 1..column], &line[..column - 1]));
+            },
+            other                            => panic!("Wrong return value for {}: {:?}", line, other),
+        }
+    }
+
+    parser.parse_section("[section] \t;comment").unwrap();
+}
+
 #[test]
 fn parser_parse_section_invalid_identifier() {
     let expr = "[hello there!]";
@@ -323,6 +340,73 @@ fn parser_parse_section_empty() {
     }
 }
 
+#[test]
+fn parser_section_inheritance() {
+    let options = ParserOptions {
+        section_inheritance: true,
+        duplicate_keys: DuplicateKeyPolicy::Error,
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options.clone());
+
+    parser.parse_line("[default]").unwrap();
+    parser.parse_line("host = localhost").unwrap();
+    parser.parse_line("port = 80").unwrap();
+    parser.parse_line("[dev : default]").unwrap();
+    parser.parse_line("port = 8080").unwrap();
+    parser.parse_line("[test:dev]").unwrap();
+
+    let tree = parser.into_tree();
+    assert_eq!(tree.get_value(Some("dev"), "host"), Some(&Value::Raw(String::from("localhost"))));
+    assert_eq!(tree.get_value(Some("dev"), "port"), Some(&Value::Int(8080)));
+    assert_eq!(tree.get_value(Some("test"), "port"), Some(&Value::Int(8080)));
+    assert_eq!(tree.get_value(Some("default"), "port"), Some(&Value::Int(80)));
+    assert_eq!(tree.parent_of("test"), Some("dev"));
+
+    let mut parser = Parser::with_options(options.clone());
+    match parser.parse_line("[dev : default]") {
+        Err(Error::UnknownParent(err)) => {
+            assert_eq!(err.parent(), "default");
+            assert_eq!(err.column(), 8);
+            assert_eq!(format!("{}", err), "Unknown parent section default in [dev : default]");
+        },
+        other                          => panic!("Wrong return value: {:?}", other),
+    }
+
+    let mut parser = Parser::with_options(options);
+    parser.parse_line("[a]").unwrap();
+    parser.parse_line("[b : a]").unwrap();
+    match parser.parse_line("[a : b]") {
+        Err(Error::InheritanceCycle(err)) => assert_eq!(err.parent(), "b"),
+        other                             => panic!("Wrong return value: {:?}", other),
+    }
+
+    // A colon is part of the name by default
+    let mut parser = Parser::new();
+    parser.parse_line("[a:b]").unwrap();
+    parser.parse_line("key = 1").unwrap();
+    assert!(parser.into_tree().contains_section(Some("a:b")));
+}
+
+#[test]
+fn parser_parse_section_inner_whitespaces() {
+    let mut parser = Parser::new();
+    parser.parse_section("[ database ]").unwrap();
+    assert_eq!(parser.cur_section.as_deref(), Some("database"));
+
+    match parser.parse_section("[ ]") {
+        Err(Error::ExpectedIdentifier(err)) => assert_eq!(err.column(), 3),
+        other                               => panic!("Wrong return value: {:?}", other),
+    }
+
+    let options = ParserOptions {
+        trim_whitespace: false,
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+    assert!(parser.parse_section("[ database ]").is_err());
+}
+
 #[test]
 fn parser_parse_section_unterminated() {
     let expr = "[EOF";
@@ -377,3 +461,1017 @@ fn parser_parse_line_empty() {
     parser.parse_line(expr)
         .expect("This line should be accepted because it's a valid INI empty line");
 }
+
+#[test]
+fn parser_parse_line_continuation() {
+    let mut parser = Parser::new();
+
+    parser.parse_line("path = /very/long/\\").unwrap();
+    parser.parse_line("    continuation").unwrap();
+    parser.finish()
+        .expect("The continuation is complete");
+
+    let data = parser.data();
+    let key = Identifier::new(None, String::from("path"));
+    let val = Value::Raw(String::from("/very/long/continuation"));
+    assert_eq!(data[&key], val);
+}
+
+#[test]
+fn parser_parse_line_several_continuations() {
+    let mut parser = Parser::new();
+
+    parser.parse_line("list = a\\").unwrap();
+    parser.parse_line("\tb\\").unwrap();
+    parser.parse_line("  c").unwrap();
+
+    let data = parser.data();
+    let key = Identifier::new(None, String::from("list"));
+    let val = Value::Raw(String::from("abc"));
+    assert_eq!(data[&key], val);
+}
+
+#[test]
+fn parser_parse_line_escaped_backslash_is_not_a_continuation() {
+    let mut parser = Parser::new();
+
+    parser.parse_line("path = C\\:\\\\").unwrap();
+    parser.parse_line("other = value").unwrap();
+    parser.finish()
+        .expect("There is no continuation");
+
+    let data = parser.data();
+    let path = Identifier::new(None, String::from("path"));
+    assert_eq!(data[&path], Value::Raw(String::from("C:\\")));
+    let other = Identifier::new(None, String::from("other"));
+    assert_eq!(data[&other], Value::Raw(String::from("value")));
+}
+
+#[test]
+fn parser_parse_line_continued_comment() {
+    let mut parser = Parser::new();
+
+    parser.parse_line("; A comment is never continued \\").unwrap();
+    parser.parse_line("ident = val").unwrap();
+
+    let data = parser.data();
+    let key = Identifier::new(None, String::from("ident"));
+    assert_eq!(data[&key], Value::Raw(String::from("val")));
+}
+
+#[test]
+fn parser_parse_line_inline_comment_is_not_continued() {
+    let tree = parse_content("key = a ; note \\\nother = b\nlong = c\\\n  d ; end\n").unwrap();
+
+    assert_eq!(tree.get_value(None, "key"), Some(&Value::Raw(String::from("a"))));
+    assert_eq!(tree.get_value(None, "other"), Some(&Value::Raw(String::from("b"))));
+    assert_eq!(tree.get_value(None, "long"), Some(&Value::Raw(String::from("cd"))));
+}
+
+#[test]
+fn parser_finish_unterminated_continuation() {
+    let mut parser = Parser::new();
+
+    parser.parse_line("path = /very/long/\\").unwrap();
+    match parser.finish() {
+        Ok(())                       => panic!("A continuation at the end of the input should not be accepted"),
+        Err(Error::ExpectedToken(_)) => {},
+        Err(err)                     => panic!("Wrong return value: {:?}", err),
+    }
+}
+
+#[test]
+fn parser_triple_quoted_value() {
+    let mut parser = Parser::new();
+
+    parser.parse_line("[scripts]").unwrap();
+    parser.parse_line("build = \"\"\"").unwrap();
+    parser.parse_line("  cargo build \\").unwrap();
+    parser.parse_line("; still the value").unwrap();
+    parser.parse_line("").unwrap();
+    parser.parse_line("echo 'done'\"\"\" ; a comment\r").unwrap();
+    parser.parse_line("inline = \"\"\"a = 'b'\"\"\"").unwrap();
+    parser.parse_line("first = \"\"\"on the first line").unwrap();
+    parser.parse_line("\"\"\"").unwrap();
+    parser.finish().unwrap();
+
+    let tree = parser.into_tree();
+    let build = "  cargo build \\\n; still the value\n\necho 'done'";
+    assert_eq!(tree.get_value(Some("scripts"), "build"), Some(&Value::Str(String::from(build))));
+    assert_eq!(tree.get_value(Some("scripts"), "inline"), Some(&Value::Str(String::from("a = 'b'"))));
+    assert_eq!(tree.get_value(Some("scripts"), "first"), Some(&Value::Str(String::from("on the first line\n"))));
+}
+
+#[test]
+fn parser_triple_quoted_value_errors() {
+    let mut parser = Parser::new();
+    parser.parse_line("key = \"\"\"").unwrap();
+    match parser.parse_line("text\"\"\" garbage") {
+        Err(Error::UnexpectedToken(err)) => assert_eq!(err.column(), 9),
+        other                            => panic!("Expected an UnexpectedToken, found {:?}", other),
+    }
+
+    let mut parser = Parser::new();
+    parser.parse_line("key = \"\"\"").unwrap();
+    parser.parse_line("never closed").unwrap();
+    match parser.finish() {
+        Err(Error::ExpectedToken(err)) => assert_eq!(format!("{}", err), "Expected \"\"\" key = \"\"\"{here}"),
+        other                          => panic!("Expected an ExpectedToken, found {:?}", other),
+    }
+
+    let err = crate::parse::parse_content("key = \"\"\"\nnever closed\n").unwrap_err();
+    assert_eq!(err.line_number(), Some(2));
+}
+
+#[test]
+fn parser_strict_escapes_in_keys() {
+    match Parser::new().parse_line("key\\q = 1") {
+        Err(Error::InvalidIdentifier(_)) => {},
+        other                            => panic!("Expected an InvalidIdentifier, found {:?}", other),
+    }
+
+    let mut parser = Parser::with_options(ParserOptions {
+        strict_escapes: true,
+        ..ParserOptions::default()
+    });
+    match parser.parse_line("key\\q = 1") {
+        Err(Error::InvalidEscape(err)) => assert_eq!(format!("{}", err), "Invalid escape sequence \\q in key{here}\\q = 1"),
+        other                          => panic!("Expected an InvalidEscape, found {:?}", other),
+    }
+
+    // A known escape is still not allowed in a key
+    assert!(matches!(parser.parse_line("key\\; = 1"), Err(Error::InvalidIdentifier(_))));
+    parser.parse_line("key = 1").unwrap();
+}
+
+#[test]
+fn parser_ignores_comments_by_default() {
+    let mut parser = Parser::new();
+
+    parser.parse_line("; comment").unwrap();
+    parser.parse_line("ident = val").unwrap();
+
+    let tree = parser.into_tree();
+    assert_eq!(tree.comments_for(None, Some("ident")), None);
+    assert!(tree.trailing_comments().is_empty());
+}
+
+#[test]
+fn parser_keeps_comments() {
+    let options = ParserOptions {
+        keep_comments: true,
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    parser.parse_line("; key comment").unwrap();
+    parser.parse_line("ident = val ; inline comment").unwrap();
+    parser.parse_line("  ; section comment").unwrap();
+    parser.parse_line("[section]").unwrap();
+    parser.parse_line("key = val").unwrap();
+    parser.parse_line("; trailing comment").unwrap();
+
+    let tree = parser.into_tree();
+    assert_eq!(tree.comments_for(None, Some("ident")), Some(&[String::from("; key comment")][..]));
+    assert_eq!(tree.comments_for(Some("section"), None), Some(&[String::from("; section comment")][..]));
+    assert_eq!(tree.comments_for(Some("section"), Some("key")), None);
+    assert_eq!(tree.trailing_comments(), &[String::from("; trailing comment")][..]);
+}
+
+/// Returns the options of a parser accepting both `;` and `#` as comment characters
+fn hash_comments() -> ParserOptions {
+    ParserOptions {
+        comment_chars: vec![';', '#'],
+        ..ParserOptions::default()
+    }
+}
+
+#[test]
+fn parser_hash_comment_line() {
+    let mut parser = Parser::with_options(hash_comments());
+
+    parser.parse_line("# A comment")
+        .expect("This line should be accepted because `#` is a comment character");
+    parser.parse_line("; Another comment")
+        .expect("This line should be accepted because `;` is a comment character");
+
+    assert!(parser.data().is_empty());
+}
+
+#[test]
+fn parser_hash_inline_comment() {
+    let mut parser = Parser::with_options(hash_comments());
+
+    parser.parse_line("ident = val # comment").unwrap();
+    parser.parse_line("[section] # comment").unwrap();
+    parser.parse_line("key = 'a # b' # comment").unwrap();
+
+    let data = parser.data();
+    let ident = Identifier::new(None, String::from("ident"));
+    assert_eq!(data[&ident], Value::Raw(String::from("val")));
+    let key = Identifier::new(Some(String::from("section")), String::from("key"));
+    assert_eq!(data[&key], Value::Str(String::from("a # b")));
+}
+
+#[test]
+fn parser_section_trailing_comment() {
+    let mut parser = Parser::with_options(hash_comments());
+
+    parser.parse_line("[section];note").unwrap();
+    assert_eq!(parser.cur_section, Some(String::from("section")));
+    parser.parse_line("[other]  # note").unwrap();
+    assert_eq!(parser.cur_section, Some(String::from("other")));
+
+    // Only a comment may follow the `]`
+    match parser.parse_line("[third] text ; note") {
+        Err(Error::UnexpectedToken(err)) => assert_eq!(err.column(), 9),
+        other                            => panic!("Expected an UnexpectedToken, found {:?}", other),
+    }
+
+    // `#` only starts a comment if it is a comment character
+    assert!(Parser::new().parse_line("[section]  # note").is_err());
+}
+
+#[test]
+fn parser_hash_is_not_a_comment_by_default() {
+    let mut parser = Parser::new();
+
+    match parser.parse_line("ident = val # not a comment") {
+        Ok(())                        => panic!("`#` must be escaped when it is not a comment character"),
+        Err(Error::ExpectedEscape(_)) => {},
+        Err(err)                      => panic!("Wrong return value: {:?}", err),
+    }
+}
+
+#[test]
+fn parser_only_hash_comments() {
+    let options = ParserOptions {
+        comment_chars: vec!['#'],
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    match parser.parse_line("; not a comment") {
+        Ok(())                       => panic!("`;` is not a comment character here"),
+        Err(Error::ExpectedToken(_)) => {},
+        Err(err)                     => panic!("Wrong return value: {:?}", err),
+    }
+}
+
+#[test]
+fn parser_semicolon_in_quotes() {
+    let mut parser = Parser::new();
+
+    parser.parse_line("ident = \"a ; b\" ; comment").unwrap();
+
+    let data = parser.data();
+    let key = Identifier::new(None, String::from("ident"));
+    assert_eq!(data[&key], Value::Str(String::from("a ; b")));
+}
+
+#[test]
+fn parser_arrays() {
+    let options = ParserOptions {
+        array_separator: Some(','),
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    parser.parse_line("servers = a.com, b.com ; comment").unwrap();
+    parser.parse_line("single = a.com").unwrap();
+
+    let data = parser.data();
+    let servers = Identifier::new(None, String::from("servers"));
+    let single = Identifier::new(None, String::from("single"));
+    assert_eq!(data[&servers], Value::Array(vec![
+        Value::Raw(String::from("a.com")),
+        Value::Raw(String::from("b.com")),
+    ]));
+    assert_eq!(data[&single], Value::Raw(String::from("a.com")));
+}
+
+#[test]
+fn parser_arrays_trailing_comma() {
+    let options = ParserOptions {
+        array_separator: Some(','),
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options.clone());
+
+    parser.parse_line("single = a.com,").unwrap();
+    parser.parse_line("servers = a.com, b.com, ").unwrap();
+    parser.parse_line("holes = a.com,, b.com").unwrap();
+    parser.parse_line("leading = , a.com").unwrap();
+
+    let data = parser.data();
+    let a = Value::Raw(String::from("a.com"));
+    let b = Value::Raw(String::from("b.com"));
+    let empty = Value::Str(String::new());
+    assert_eq!(data[&Identifier::new(None, String::from("single"))], Value::Array(vec![a.clone()]));
+    assert_eq!(data[&Identifier::new(None, String::from("servers"))], Value::Array(vec![a.clone(), b.clone()]));
+    assert_eq!(data[&Identifier::new(None, String::from("holes"))], Value::Array(vec![a.clone(), empty.clone(), b]));
+    assert_eq!(data[&Identifier::new(None, String::from("leading"))], Value::Array(vec![empty.clone(), a.clone()]));
+
+    let options = ParserOptions {
+        allow_trailing_comma: false,
+        ..options
+    };
+    let mut parser = Parser::with_options(options);
+    parser.parse_line("single = a.com,").unwrap();
+
+    let data = parser.data();
+    assert_eq!(data[&Identifier::new(None, String::from("single"))], Value::Array(vec![a, empty]));
+}
+
+#[test]
+fn parser_array_keys() {
+    let options = ParserOptions {
+        array_keys: true,
+        duplicate_keys: DuplicateKeyPolicy::Error,
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    parser.parse_line("hosts[] = a.com").unwrap();
+    parser.parse_line("hosts[] = 'b.com'").unwrap();
+    parser.parse_line("[ports]").unwrap();
+    parser.parse_line("ports[http] = 80").unwrap();
+    parser.parse_line("ports[https] = 443").unwrap();
+    parser.parse_line("single = 1").unwrap();
+
+    let tree = parser.into_tree();
+    assert_eq!(tree.get_value(None, "hosts"), Some(&Value::Array(vec![
+        Value::Raw(String::from("a.com")),
+        Value::Str(String::from("b.com")),
+    ])));
+    assert_eq!(tree.get_value(Some("ports"), "ports.http"), Some(&Value::Int(80)));
+    assert_eq!(tree.get_value(Some("ports"), "ports.https"), Some(&Value::Int(443)));
+    assert_eq!(tree.get_value(Some("ports"), "single"), Some(&Value::Int(1)));
+
+    // The brackets are not part of the syntax by default
+    let mut parser = Parser::new();
+    match parser.parse_line("hosts[] = a.com") {
+        Err(Error::InvalidIdentifier(err)) => assert_eq!(format!("{}", err), "Invalid identifier hosts[] in hosts[] = a.com"),
+        other                              => panic!("Wrong return value: {:?}", other),
+    }
+}
+
+#[test]
+fn parser_no_arrays_by_default() {
+    let mut parser = Parser::new();
+
+    parser.parse_line("servers = a.com, b.com").unwrap();
+
+    let data = parser.data();
+    let servers = Identifier::new(None, String::from("servers"));
+    assert_eq!(data[&servers], Value::Raw(String::from("a.com, b.com")));
+}
+
+#[test]
+fn parser_quoted_whitespaces() {
+    let mut parser = Parser::new();
+
+    parser.parse_line("key = \"  spaced ; out  \" ; comment").unwrap();
+
+    let data = parser.data();
+    let key = Identifier::new(None, String::from("key"));
+    assert_eq!(data[&key], Value::Str(String::from("  spaced ; out  ")));
+}
+
+#[test]
+fn parser_unterminated_quote() {
+    let mut parser = Parser::new();
+
+    match parser.parse_line("key = \"unterminated ; comment") {
+        Err(Error::ExpectedToken(err)) => assert_eq!(format!("{}", err), "Expected \" \"unterminated ; comment{here}"),
+        other                          => panic!("Wrong return value: {:?}", other),
+    }
+}
+
+#[test]
+fn parser_nested_sections() {
+    let options = ParserOptions {
+        nested_sections: true,
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    parser.parse_line("[a.b]").unwrap();
+    parser.parse_line("key = 1").unwrap();
+    match parser.parse_line("[a..b]") {
+        Err(Error::ExpectedIdentifier(err)) => assert_eq!(format!("{}", err), "Expected identifier [a.{here}.b]"),
+        other                               => panic!("Wrong return value: {:?}", other),
+    }
+    match parser.parse_line("[a.?]") {
+        Err(Error::InvalidIdentifier(err)) => assert_eq!(format!("{}", err), "Invalid identifier ? in [a.?]"),
+        other                              => panic!("Wrong return value: {:?}", other),
+    }
+
+    let data = parser.data();
+    let key = Identifier::new(Some(String::from("a.b")), String::from("key"));
+    assert_eq!(data[&key], Value::Int(1));
+}
+
+#[test]
+fn parser_nested_sections_escaped_dot() {
+    let options = ParserOptions {
+        nested_sections: true,
+        ..ParserOptions::default()
+    };
+    let content = "[a\\.b]\nkey = 1\n[a.b]\nkey = 2\n";
+    let tree = parse_content_with_options(content, options.clone()).unwrap();
+
+    // The escaped dot is kept in the name so that both sections exist
+    assert_eq!(tree.get_value(Some("a\\.b"), "key"), Some(&Value::Int(1)));
+    assert_eq!(tree.get_value(Some("a.b"), "key"), Some(&Value::Int(2)));
+    assert_eq!(tree.get_subsection(&["a.b"]).unwrap().name(), Some("a\\.b"));
+    assert_eq!(tree.get_subsection(&["a", "b"]).unwrap().name(), Some("a.b"));
+
+    let dumped = crate::dump::dump_tree(&tree);
+    assert_eq!(dumped, "[a\\.b]\nkey=1\n\n[a.b]\nkey=2\n");
+    assert_eq!(parse_content_with_options(&dumped, options).unwrap().get_data(), tree.get_data());
+}
+
+#[test]
+fn parser_escaped_dot_without_nested_sections() {
+    let mut parser = Parser::new();
+
+    assert!(parser.parse_line("[a.b]").is_ok());
+    match parser.parse_line("[a\\.b]") {
+        Err(Error::InvalidIdentifier(_)) => {},
+        other                            => panic!("Wrong return value: {:?}", other),
+    }
+}
+
+fn parse_duplicates(policy: DuplicateKeyPolicy) -> Result<Parser, Error> {
+    let options = ParserOptions {
+        duplicate_keys: policy,
+        array_separator: Some(','),
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+    for i in &["[servers]", "host = a.com", "port = 80", "host = b.com, c.com"] {
+        parser.parse_line(i)?;
+    }
+
+    Ok(parser)
+}
+
+#[test]
+fn parser_duplicate_keys_last_wins() {
+    let data = parse_duplicates(DuplicateKeyPolicy::LastWins).unwrap().data();
+    let host = Identifier::new(Some(String::from("servers")), String::from("host"));
+
+    assert_eq!(data[&host], Value::Array(vec![Value::Raw(String::from("b.com")), Value::Raw(String::from("c.com"))]));
+}
+
+#[test]
+fn parser_duplicate_keys_first_wins() {
+    let data = parse_duplicates(DuplicateKeyPolicy::FirstWins).unwrap().data();
+    let host = Identifier::new(Some(String::from("servers")), String::from("host"));
+
+    assert_eq!(data[&host], Value::Raw(String::from("a.com")));
+}
+
+#[test]
+fn parser_duplicate_keys_collect() {
+    let data = parse_duplicates(DuplicateKeyPolicy::Collect).unwrap().data();
+    let host = Identifier::new(Some(String::from("servers")), String::from("host"));
+
+    assert_eq!(data[&host], Value::Array(vec![
+        Value::Raw(String::from("a.com")),
+        Value::Raw(String::from("b.com")),
+        Value::Raw(String::from("c.com")),
+    ]));
+}
+
+#[test]
+fn parser_duplicate_keys_error() {
+    match parse_duplicates(DuplicateKeyPolicy::Error) {
+        Err(Error::RedefinedKey(err)) => {
+            assert_eq!(err.key(), "host");
+            assert_eq!(err.first_line(), 2);
+            assert_eq!(err.line_number(), Some(4));
+            assert_eq!(format!("{}", err), "Key host redefined in section servers (first defined at line 2) at line 4");
+        },
+        Err(err)                      => panic!("Wrong error returned: {:?}", err),
+        Ok(_)                         => panic!("A duplicate key must be an error"),
+    }
+}
+
+#[test]
+fn parser_duplicate_keys_error_after_array_key() {
+    let options = ParserOptions {
+        array_keys: true,
+        duplicate_keys: DuplicateKeyPolicy::Error,
+        ..ParserOptions::default()
+    };
+
+    match parse_content_with_options("k[]=1\nk[]=2\nk=3\n", options) {
+        Err(Error::RedefinedKey(err)) => {
+            assert_eq!(err.key(), "k");
+            assert_eq!(err.first_line(), 1);
+            assert_eq!(err.line_number(), Some(3));
+        },
+        Err(err)                      => panic!("Wrong error returned: {:?}", err),
+        Ok(_)                         => panic!("A duplicate key must be an error"),
+    }
+}
+
+#[test]
+fn parser_byte_escape_before_comment() {
+    let mut parser = Parser::new();
+
+    parser.parse_line("key = \\x41\\u{1F600};comment").unwrap();
+
+    let data = parser.data();
+    let key = Identifier::new(None, String::from("key"));
+    assert_eq!(data[&key], Value::Raw(String::from("A\u{1f600}")));
+}
+
+#[test]
+fn parser_without_type_inference() {
+    let options = ParserOptions {
+        infer_types: false,
+        array_separator: Some(','),
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    parser.parse_line("number = 0x2A").unwrap();
+    parser.parse_line("quoted = ' on '").unwrap();
+    parser.parse_line("list = 1, yes,,").unwrap();
+
+    let data = parser.data();
+    let raw = |s: &str| Value::Raw(String::from(s));
+    assert_eq!(data[&Identifier::new(None, String::from("number"))], raw("0x2A"));
+    assert_eq!(data[&Identifier::new(None, String::from("quoted"))], raw(" on "));
+    assert_eq!(data[&Identifier::new(None, String::from("list"))], Value::Array(vec![raw("1"), raw("yes"), raw("")]));
+}
+
+#[test]
+fn parser_colon_separator() {
+    let options = ParserOptions {
+        kv_separators: vec!['=', ':'],
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    parser.parse_line("host: localhost").unwrap();
+    parser.parse_line("url = 'http://a.com'").unwrap();
+    parser.parse_line("path: \"a=b\"").unwrap();
+
+    let data = parser.data();
+    assert_eq!(data[&Identifier::new(None, String::from("host"))], Value::Raw(String::from("localhost")));
+    assert_eq!(data[&Identifier::new(None, String::from("url"))], Value::Str(String::from("http://a.com")));
+    assert_eq!(data[&Identifier::new(None, String::from("path"))], Value::Str(String::from("a=b")));
+}
+
+#[test]
+fn parser_missing_separator() {
+    let options = ParserOptions {
+        kv_separators: vec![':'],
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    match parser.parse_line("key = value") {
+        Err(Error::ExpectedToken(err)) => assert_eq!(format!("{}", err), "Expected : key = value{here}"),
+        other                          => panic!("Wrong return value: {:?}", other),
+    }
+}
+
+#[test]
+fn parser_without_trimming() {
+    let options = ParserOptions {
+        trim_whitespace: false,
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    parser.parse_line("  key = value ;comment").unwrap();
+    parser.parse_line("key=value\r").unwrap();
+    parser.parse_line("number = 42 ").unwrap();
+    parser.parse_line("quoted = ' a ' ").unwrap();
+
+    let data = parser.data();
+    let raw = |s: &str| Value::Raw(String::from(s));
+    assert_eq!(data[&Identifier::new(None, String::from("key "))], raw(" value "));
+    assert_eq!(data[&Identifier::new(None, String::from("key"))], raw("value"));
+    assert_eq!(data[&Identifier::new(None, String::from("number "))], Value::Int(42));
+    assert_eq!(data[&Identifier::new(None, String::from("quoted "))], Value::Str(String::from(" a ")));
+}
+
+#[test]
+fn parse_content_reports_line_number() {
+    let tree = parse_content("\u{feff}a = 1\n[s]\nb = 2\n").unwrap();
+    assert_eq!(tree.get_value(None, "a"), Some(&Value::Int(1)));
+    assert_eq!(tree.get_value(Some("s"), "b"), Some(&Value::Int(2)));
+
+    let err = parse_content("a = 1\nb\n").unwrap_err();
+    assert_eq!(err.line_number(), Some(2));
+}
+
+#[test]
+fn parse_strips_a_single_bom() {
+    let content = "\u{feff}\u{feff}a = 1\n";
+    assert!(parse_content(content).is_err());
+    assert!(parse_reader(content.as_bytes()).is_err());
+
+    let tree = parse_reader("\u{feff}a = 1\n".as_bytes()).unwrap();
+    assert_eq!(tree.get_value(None, "a"), Some(&Value::Int(1)));
+}
+
+#[test]
+fn parse_reader_io_error() {
+    struct FailingReader;
+
+    impl std::io::Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("broken reader"))
+        }
+    }
+
+    match parse_reader(FailingReader) {
+        Err(crate::errors::ParseFileError::IOError(_)) => {},
+        other                                          => panic!("Wrong return value: {:?}", other),
+    }
+}
+
+#[test]
+fn parse_reader_max_line_len() {
+    // An endless line, which must not be read further than the limit
+    struct EndlessReader {
+        read: usize,
+    }
+
+    impl std::io::Read for EndlessReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let content = b"a = 1\nb = ";
+            let len = buf.len().min(64);
+            for (n, c) in buf[..len].iter_mut().enumerate() {
+                *c = content.get(self.read + n).copied().unwrap_or(b'x');
+            }
+            self.read += len;
+            Ok(len)
+        }
+    }
+
+    let options = ParserOptions {
+        max_line_len: Some(16),
+        ..ParserOptions::default()
+    };
+    let mut reader = EndlessReader { read: 0 };
+    match parse_reader_with_options(&mut reader, options.clone()) {
+        Err(crate::errors::ParseFileError::ParseError(Error::LimitExceeded(err))) => assert_eq!(err.line_number(), Some(2)),
+        other                                                                     => panic!("Wrong return value: {:?}", other),
+    }
+    assert!(reader.read < 16 * 1024);
+
+    let tree = parse_reader_with_options("a = 1\nb = 1234567890\n".as_bytes(), options).unwrap();
+    assert_eq!(tree.get_value(None, "b"), Some(&Value::Int(1234567890)));
+}
+
+#[test]
+fn parser_bool_tokens() {
+    let options = ParserOptions {
+        bool_tokens: Some(BoolTokens {
+            truthy: vec![String::from("si")],
+            falsy: vec![String::from("non")],
+        }),
+        array_separator: Some(','),
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    parser.parse_line("a = SI").unwrap();
+    parser.parse_line("b = 'si'").unwrap();
+    parser.parse_line("c = non, yes, 1").unwrap();
+
+    let data = parser.data();
+    assert_eq!(data[&Identifier::new(None, String::from("a"))], Value::Bool(true));
+    assert_eq!(data[&Identifier::new(None, String::from("b"))], Value::Str(String::from("si")));
+    assert_eq!(data[&Identifier::new(None, String::from("c"))], Value::Array(vec![Value::Bool(false), Value::Bool(true), Value::Int(1)]));
+}
+
+#[test]
+fn parser_colors() {
+    let options = ParserOptions {
+        parse_colors: true,
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    parser.parse_line("background = #1A2B3C ; dark").unwrap();
+    parser.parse_line("overlay = #0008").unwrap();
+    parser.parse_line("quoted = '#fff'").unwrap();
+
+    let data = parser.data();
+    assert_eq!(data[&Identifier::new(None, String::from("background"))], Value::Color(0x1a, 0x2b, 0x3c, None));
+    assert_eq!(data[&Identifier::new(None, String::from("overlay"))], Value::Str(String::from("#0008")));
+    assert_eq!(data[&Identifier::new(None, String::from("quoted"))], Value::Str(String::from("#fff")));
+}
+
+#[test]
+fn parser_colors_with_hash_comments() {
+    let options = ParserOptions {
+        parse_colors: true,
+        comment_chars: vec!['#'],
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    parser.parse_line("color = #fff").unwrap();
+
+    let data = parser.data();
+    assert_eq!(data[&Identifier::new(None, String::from("color"))], Value::Raw(String::new()));
+}
+
+#[test]
+fn parser_type_annotations() {
+    let options = ParserOptions {
+        type_annotations: true,
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    parser.parse_line("port = 8080:int").unwrap();
+    parser.parse_line("ratio = 1 : float").unwrap();
+    parser.parse_line("version = 1.0:str").unwrap();
+    parser.parse_line("url = 'http://a.com'").unwrap();
+    parser.parse_line("escaped = a\\:b").unwrap();
+
+    match parser.parse_line("name = abc:int") {
+        Err(Error::InvalidAnnotation(err)) => {
+            assert_eq!(err.value(), "abc");
+            assert_eq!(err.annotation(), "int");
+            assert_eq!(format!("{}", err), "Value abc does not match its type annotation int in abc:int");
+        },
+        other                              => panic!("Wrong return value: {:?}", other),
+    }
+    assert!(parser.parse_line("name = abc:text").is_err());
+
+    let data = parser.data();
+    assert_eq!(data[&Identifier::new(None, String::from("port"))], Value::Int(8080));
+    assert_eq!(data[&Identifier::new(None, String::from("ratio"))], Value::Float(1.0));
+    assert_eq!(data[&Identifier::new(None, String::from("version"))], Value::Str(String::from("1.0")));
+    assert_eq!(data[&Identifier::new(None, String::from("url"))], Value::Str(String::from("http://a.com")));
+    assert_eq!(data[&Identifier::new(None, String::from("escaped"))], Value::Raw(String::from("a:b")));
+}
+
+#[test]
+fn parser_type_annotations_disabled() {
+    let mut parser = Parser::new();
+
+    assert!(parser.parse_line("port = 8080:int").is_err());
+}
+
+#[test]
+fn parser_case_insensitive_keywords() {
+    let options = ParserOptions {
+        case_insensitive_keywords: true,
+        type_annotations: true,
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    parser.parse_line("A = YES").unwrap();
+    parser.parse_line("B = Off").unwrap();
+    parser.parse_line("C = 42:INT").unwrap();
+    parser.parse_line("D = Hello").unwrap();
+    parser.parse_line("E = TRUE").unwrap();
+    parser.parse_line("F = False").unwrap();
+
+    let data = parser.data();
+    assert_eq!(data[&Identifier::new(None, String::from("E"))], Value::Bool(true));
+    assert_eq!(data[&Identifier::new(None, String::from("F"))], Value::Bool(false));
+    assert_eq!(data[&Identifier::new(None, String::from("A"))], Value::Bool(true));
+    assert_eq!(data[&Identifier::new(None, String::from("B"))], Value::Bool(false));
+    assert_eq!(data[&Identifier::new(None, String::from("C"))], Value::Int(42));
+    assert_eq!(data[&Identifier::new(None, String::from("D"))], Value::Raw(String::from("Hello")));
+}
+
+#[test]
+fn parser_case_sensitive_keywords_by_default() {
+    let mut parser = Parser::new();
+
+    parser.parse_line("a = YES").unwrap();
+
+    let data = parser.data();
+    assert_eq!(data[&Identifier::new(None, String::from("a"))], Value::Raw(String::from("YES")));
+}
+
+#[test]
+fn parser_tab_indentation() {
+    let mut parser = Parser::new();
+
+    parser.parse_line("\t[section]").unwrap();
+    parser.parse_line("\t  key = value").unwrap();
+    parser.parse_line(" \tcolumns = a\tb ;\tcomment").unwrap();
+    parser.parse_line("\t; comment").unwrap();
+
+    let data = parser.data();
+    let section = Some(String::from("section"));
+    assert_eq!(data[&Identifier::new(section.clone(), String::from("key"))], Value::Raw(String::from("value")));
+    assert_eq!(data[&Identifier::new(section, String::from("columns"))], Value::Raw(String::from("a\tb")));
+}
+
+#[test]
+fn parser_expand_env() {
+    let resolver = EnvResolver::new(|name| match name {
+        "HOME" => Some(String::from("/home/user")),
+        "PORT" => Some(String::from("8080")),
+        "NOTE" => Some(String::from("a;b")),
+        _      => None,
+    });
+    let options = ParserOptions {
+        expand_env: true,
+        env_resolver: Some(resolver),
+        ..ParserOptions::default()
+    };
+
+    let mut parser = Parser::with_options(options.clone());
+    parser.parse_line("path = ${HOME}/.config").unwrap();
+    parser.parse_line("port = ${PORT}").unwrap();
+    parser.parse_line("note = '${NOTE}' ; a comment").unwrap();
+    parser.parse_line(r"price = \${HOME}").unwrap();
+    let tree = parser.into_tree();
+    assert_eq!(tree.get_value(None, "path"), Some(&Value::Raw(String::from("/home/user/.config"))));
+    assert_eq!(tree.get_value(None, "port"), Some(&Value::Int(8080)));
+    assert_eq!(tree.get_value(None, "note"), Some(&Value::Str(String::from("a;b"))));
+    assert_eq!(tree.get_value(None, "price"), Some(&Value::Raw(String::from("${HOME}"))));
+
+    let mut parser = Parser::with_options(options.clone());
+    match parser.parse_line("user = ${USER}") {
+        Err(Error::UndefinedVariable(err)) => {
+            assert_eq!(err.variable(), "USER");
+            assert_eq!(format!("{}", err), "Undefined variable USER in user = ${USER}");
+            assert_eq!(Error::from(err).column(), Some(8));
+        },
+        other                              => panic!("Expected an UndefinedVariable error, got {:?}", other),
+    }
+    assert!(parser.parse_line("user = ${USER").is_err());
+
+    let mut parser = Parser::with_options(ParserOptions {
+        undefined_env: UndefinedEnvPolicy::Empty,
+        ..options
+    });
+    parser.parse_line("user = '${USER}'").unwrap();
+    assert_eq!(parser.into_tree().get_value(None, "user"), Some(&Value::Str(String::new())));
+}
+
+#[test]
+fn parser_files_without_keys() {
+    let options = ParserOptions {
+        keep_comments: true,
+        keep_blank_lines: true,
+        ..ParserOptions::default()
+    };
+
+    for content in &["", "\n", "  \n\t\n", "; only\n; comments\n", "\u{feff}", "; no line break"] {
+        for tree in [parse_content(content).unwrap(), parse_content_with_options(content, options.clone()).unwrap()] {
+            assert!(tree.is_empty());
+            assert_eq!(tree.section_count(), 0);
+            assert_eq!(tree.sections().count(), 0);
+            assert!(!tree.contains_section(None));
+        }
+
+        let tree = parse_reader(content.as_bytes()).unwrap();
+        assert!(tree.is_empty());
+        assert_eq!(tree.sections().count(), 0);
+    }
+
+    let tree = parse_content_with_options("; only\n; comments\n", options).unwrap();
+    assert_eq!(tree.trailing_comments(), ["; only", "; comments"]);
+}
+
+#[test]
+fn parser_null_values() {
+    let mut parser = Parser::new();
+    assert!(matches!(parser.parse_line("verbose"), Err(Error::ExpectedToken(_))));
+    parser.parse_line("key = null").unwrap();
+    assert_eq!(parser.into_tree().get_value(None, "key"), Some(&Value::Raw(String::from("null"))));
+
+    let options = ParserOptions {
+        bare_keys: true,
+        null_token: Some(String::from("null")),
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+    parser.parse_line("  verbose ; a bare key").unwrap();
+    parser.parse_line("a = NULL").unwrap();
+    parser.parse_line("b = 'null'").unwrap();
+    parser.parse_line("c =").unwrap();
+    assert!(matches!(parser.parse_line("1st"), Err(Error::InvalidIdentifier(_))));
+
+    let tree = parser.into_tree();
+    assert!(tree.get_value(None, "verbose").unwrap().is_null());
+    assert_eq!(tree.get_value(None, "a"), Some(&Value::Null));
+    assert_eq!(tree.get_value(None, "b"), Some(&Value::Str(String::from("null"))));
+    assert_eq!(tree.get_value(None, "c"), Some(&Value::Raw(String::new())));
+}
+
+#[test]
+fn parser_limits() {
+    use crate::errors::error_kinds::Limit;
+
+    let options = ParserOptions {
+        max_line_len: Some(16),
+        max_sections: Some(1),
+        max_keys: Some(2),
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+
+    match parser.parse_line("key = a very long value") {
+        Err(Error::LimitExceeded(err)) => assert_eq!(err.limit(), Limit::LineLength),
+        other                          => panic!("Expected a LimitExceeded error, got {:?}", other),
+    }
+    parser.parse_line("a = 1 \\").unwrap();
+    assert!(parser.parse_line("+ 2 + 3 + 4 + 5").is_err());
+
+    parser.parse_line("a = 1").unwrap();
+    parser.parse_line("b = 2").unwrap();
+    parser.parse_line("b = 3").unwrap();
+    match parser.parse_line("c = 4") {
+        Err(Error::LimitExceeded(err)) => assert_eq!(err.limit(), Limit::Keys),
+        other                          => panic!("Expected a LimitExceeded error, got {:?}", other),
+    }
+
+    parser.parse_line("[a]").unwrap();
+    parser.parse_line("[a]").unwrap();
+    match parser.parse_line("[b]") {
+        Err(Error::LimitExceeded(err)) => assert_eq!(format!("{}", err), "More than 1 sections"),
+        other                          => panic!("Expected a LimitExceeded error, got {:?}", other),
+    }
+}
+
+#[test]
+fn ascii_scanning_matches_unicode_scanning() {
+    use super::{find_separator_in, comment_start, ascii_indices};
+
+    let lines = ["key = value ; comment", "'a=b' = \"c;d\" ; e", "path = C\\:\\\\dir\\; x", "esc = \\x00003b; y", "no separator", "", "[s] ; c = d"];
+    for line in &lines {
+        assert_eq!(find_separator_in(ascii_indices(line), &['=', ':']), find_separator_in(line.char_indices(), &['=', ':']));
+        assert_eq!(comment_start(ascii_indices(line), &[';', '#'], false), comment_start(line.char_indices(), &[';', '#'], false));
+    }
+
+    assert_eq!(find_separator("caf\u{e9} = x", &['=']), Some((6, '=')));
+    assert_eq!(ignore_comment("caf\u{e9} ; x", &[';']), "caf\u{e9} ");
+    assert_eq!(ignore_comment("key = 'a;b' ; x", &[';']), "key = 'a;b' ");
+}
+
+#[test]
+fn parser_inline_comments() {
+    let tree = parse_content("a = a;b\nb = a ; b\nc = \"a ; b\"\nd = value   ; comment\n").unwrap();
+    assert_eq!(tree.get_value(None, "a"), Some(&Value::Raw(String::from("a"))));
+    assert_eq!(tree.get_value(None, "b"), Some(&Value::Raw(String::from("a"))));
+    assert_eq!(tree.get_value(None, "c"), Some(&Value::Str(String::from("a ; b"))));
+    assert_eq!(tree.get_value(None, "d"), Some(&Value::Raw(String::from("value"))));
+}
+
+#[test]
+fn parser_spaced_comments() {
+    let options = ParserOptions {
+        spaced_comments: true,
+        comment_chars: vec![';', '#'],
+        ..ParserOptions::default()
+    };
+    let content = "a = a;b\nb = a ; b\nc = \"a ; b\"\nd = value   ; comment\ne = x#y\tz\t# comment\nf =; empty\n";
+    let tree = parse_content_with_options(content, options.clone()).unwrap();
+
+    assert_eq!(tree.get_value(None, "a"), Some(&Value::Raw(String::from("a;b"))));
+    assert_eq!(tree.get_value(None, "b"), Some(&Value::Raw(String::from("a"))));
+    assert_eq!(tree.get_value(None, "c"), Some(&Value::Str(String::from("a ; b"))));
+    assert_eq!(tree.get_value(None, "d"), Some(&Value::Raw(String::from("value"))));
+    assert_eq!(tree.get_value(None, "e"), Some(&Value::Raw(String::from("x#y\tz"))));
+    assert_eq!(tree.get_value(None, "f"), Some(&Value::Raw(String::new())));
+
+    assert_eq!(tokenize_line_with_options("a = a;b ; c", &options).unwrap(), [
+        LineToken::Identifier("a"),
+        LineToken::Separator('='),
+        LineToken::Value("a;b"),
+        LineToken::Comment("; c"),
+    ]);
+}
+
+#[test]
+fn parser_diagnostics_only_collected_when_asked() {
+    let content = ["[a]", "", "", "[a]"];
+
+    let mut parser = Parser::new();
+    for i in &content {
+        parser.parse_line(i).unwrap();
+    }
+    assert!(parser.diagnostics().is_empty());
+
+    let mut parser = Parser::with_options(ParserOptions {
+        diagnostics: true,
+        ..ParserOptions::default()
+    });
+    for i in &content {
+        parser.parse_line(i).unwrap();
+    }
+    let found: Vec<_> = parser.diagnostics().iter().map(|i| (i.severity(), i.line_number())).collect();
+    assert_eq!(found, [(Severity::Note, 3), (Severity::Warning, 4)]);
+}
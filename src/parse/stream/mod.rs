@@ -0,0 +1,211 @@
+//! Contains the definition of [`StreamParser`](struct.StreamParser.html "parse::StreamParser")
+
+use crate::prelude::*;
+use crate::datas::{Identifier, Value};
+#[cfg(feature = "std")]
+use crate::errors::{Error, ParseFileError, error_kinds::{LimitExceeded, Limit}};
+#[cfg(feature = "std")]
+use super::{Parser, ParserOptions};
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, Read};
+
+/// An entry of an INI file as yielded by a [`StreamParser`](struct.StreamParser.html "parse::StreamParser")
+#[derive(Debug, Clone, PartialEq)]
+pub enum Entry {
+    /// A section declaration with the name of the section. The following keys belong to this section
+    Section(String),
+    /// An assignment with the identifier of the key and its value
+    KeyValue(Identifier, Value),
+    /// A comment on its own line, as written in the file (starting with its comment character). The comments following an assignment or a section declaration on the same line are not yielded
+    Comment(String),
+}
+
+/// A parser reading its lines from a reader and yielding the [`Entry`](enum.Entry.html "parse::Entry")s it finds one after the other
+/// 
+/// Unlike [`parse_file`](fn.parse_file.html "parse::parse_file"), a `StreamParser` never stores the data it parses so it can read huge files with a constant memory usage
+/// 
+/// # Errors
+/// A parsing error is yielded as a [`ParseFileError::ParseError`](../errors/enum.ParseFileError.html "errors::ParseFileError"), knowing the number of its line, and the parser goes on at the next line. An I/O error is yielded as a `ParseFileError::IOError` and ends the iteration
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{StreamParser, Entry};
+/// use mininip::datas::{Identifier, Value};
+/// 
+/// let content = "\
+/// ; A comment\n\
+/// [section]\n\
+/// answer = 42\n";
+/// 
+/// let mut parser = StreamParser::new(content.as_bytes());
+/// 
+/// assert_eq!(parser.next().unwrap().unwrap(), Entry::Comment(String::from("; A comment")));
+/// assert_eq!(parser.next().unwrap().unwrap(), Entry::Section(String::from("section")));
+/// 
+/// let answer = Identifier::new(Some(String::from("section")), String::from("answer"));
+/// assert_eq!(parser.next().unwrap().unwrap(), Entry::KeyValue(answer, Value::Int(42)));
+/// 
+/// assert!(parser.next().is_none());
+/// ```
+/// 
+/// # Note
+/// Requires the `std` feature
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct StreamParser<R: BufRead> {
+    reader: R,
+    parser: Parser,
+    /// The buffer of the line currently parsed, kept to avoid an allocation per line
+    line: Vec<u8>,
+    /// Set to `true` when the end of the input is reached or an I/O error occurs
+    finished: bool,
+    /// The number of lines read
+    line_number: usize,
+    /// A copy of `ParserOptions::max_line_len`
+    max_line_len: Option<usize>,
+}
+
+#[cfg(feature = "std")]
+impl<R: BufRead> StreamParser<R> {
+    /// Creates a new `StreamParser` reading its lines from `reader`
+    pub fn new(reader: R) -> StreamParser<R> {
+        StreamParser::with_options(reader, ParserOptions::default())
+    }
+
+    /// Creates a new `StreamParser` reading its lines from `reader` and following `options`
+    /// 
+    /// # Note
+    /// [`ParserOptions::keep_comments`](struct.ParserOptions.html#structfield.keep_comments "parse::ParserOptions::keep_comments") is meaningless here since a `StreamParser` always yields the comments
+    pub fn with_options(reader: R, options: ParserOptions) -> StreamParser<R> {
+        StreamParser {
+            reader,
+            max_line_len: options.max_line_len,
+            parser: Parser::with_options(options),
+            line: Vec::new(),
+            finished: false,
+            line_number: 0,
+        }
+    }
+
+    /// Consumes `self` and returns the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads the next line into `self.line` like `read_bounded_line`, skipping the rest of a line longer than the limit
+    fn read_line(&mut self) -> io::Result<Option<usize>> {
+        let read = read_bounded_line(&mut self.reader, &mut self.line, self.max_line_len)?;
+        if read.is_none() {
+            skip_line(&mut self.reader)?;
+        }
+
+        Ok(read)
+    }
+}
+
+/// Reads the next line of `reader` into `line`, storing at most `max_line_len` bytes of it (see [`ParserOptions::max_line_len`](struct.ParserOptions.html#structfield.max_line_len "parse::ParserOptions::max_line_len"))
+/// 
+/// # Return value
+/// `Ok(Some(n))` with `n` as the number of bytes read, `0` at the end of the input
+/// 
+/// `Ok(None)` if the line is longer than the limit. `line` is left as it was and the rest of the line is left in `reader` (see [`skip_line`](fn.skip_line.html "parse::stream::skip_line"))
+/// 
+/// `Err(error)` with `error` as the I/O error which occured
+#[cfg(feature = "std")]
+pub(crate) fn read_bounded_line<R: BufRead>(reader: &mut R, line: &mut Vec<u8>, max_line_len: Option<usize>) -> io::Result<Option<usize>> {
+    let max = match max_line_len {
+        Some(max) => max,
+        None      => return reader.read_until(b'\n', line).map(Some),
+    };
+
+    // One more byte for either the line ending or the first byte beyond the limit, so that the line ending of an overlong line is never read
+    let start = line.len();
+    let read = reader.by_ref()
+                     .take(max as u64 + 1)
+                     .read_until(b'\n', line)?;
+    if read <= max || line.ends_with(b"\n") {
+        return Ok(Some(read));
+    }
+
+    line.truncate(start);
+    Ok(None)
+}
+
+/// Skips the rest of the current line of `reader` without storing it, after [`read_bounded_line`](fn.read_bounded_line.html "parse::stream::read_bounded_line") found it too long
+#[cfg(feature = "std")]
+pub(crate) fn skip_line<R: BufRead>(reader: &mut R) -> io::Result<()> {
+    loop {
+        let buffer = reader.fill_buf()?;
+        match buffer.iter().position(|&c| c == b'\n') {
+            Some(n)                    => {
+                reader.consume(n + 1);
+                return Ok(());
+            },
+            None if buffer.is_empty() => return Ok(()),
+            None                      => {
+                let len = buffer.len();
+                reader.consume(len);
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: BufRead> Iterator for StreamParser<R> {
+    type Item = Result<Entry, ParseFileError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            self.line.clear();
+
+            match self.read_line() {
+                Ok(Some(0)) => {
+                    self.finished = true;
+
+                    if let Err(err) = self.parser.finish() {
+                        return Some(Err(ParseFileError::from(err.with_line_number(self.line_number))));
+                    }
+                },
+                Ok(None)    => {
+                    self.line_number += 1;
+                    let max = self.max_line_len.unwrap_or(0);
+                    let err = Error::from(LimitExceeded::new(Limit::LineLength, max));
+                    return Some(Err(ParseFileError::from(err.with_line_number(self.line_number))));
+                },
+                Ok(Some(_)) => {
+                    self.line_number += 1;
+                    let line = match core::str::from_utf8(&self.line) {
+                        Ok(line) => line,
+                        Err(err) => {
+                            self.finished = true;
+                            return Some(Err(ParseFileError::from(io::Error::new(io::ErrorKind::InvalidData, err))));
+                        },
+                    };
+                    let line = line.strip_suffix('\n').unwrap_or(line);
+                    let line = if self.line_number == 1 {
+                        // A UTF-8 byte-order mark
+                        line.strip_prefix('\u{feff}').unwrap_or(line)
+                    } else {
+                        line
+                    };
+
+                    match self.parser.read_line(line) {
+                        Ok(Some((entry, ..))) => return Some(Ok(entry)),
+                        Ok(None)             => {},
+                        Err(err)             => return Some(Err(ParseFileError::from(err.with_line_number(self.line_number)))),
+                    }
+                },
+                Err(err)    => {
+                    self.finished = true;
+                    return Some(Err(ParseFileError::from(err)));
+                },
+            }
+        }
+
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests;
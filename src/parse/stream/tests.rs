@@ -0,0 +1,150 @@
+use crate::parse::stream::*;
+use crate::errors::{Error, ParseFileError};
+use std::io::{self, Read, BufReader};
+
+#[test]
+fn stream_parser_yields_entries() {
+    let content = "\
+    abc = 123\n\
+    \n\
+    ; comment\n\
+    [section]\n\
+    def = 'Hello' ; inline comments are not yielded\n\
+    ";
+
+    let found = StreamParser::new(content.as_bytes())
+                .collect::<Result<Vec<Entry>, _>>()
+                .expect("This code is valid");
+
+    let section = Some(String::from("section"));
+    let expected = vec![
+        Entry::KeyValue(Identifier::new(None, String::from("abc")), Value::Int(123)),
+        Entry::Comment(String::from("; comment")),
+        Entry::Section(String::from("section")),
+        Entry::KeyValue(Identifier::new(section, String::from("def")), Value::Str(String::from("Hello"))),
+    ];
+
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn stream_parser_continuation() {
+    let content = "path = /very/long/\\\n    continuation";
+
+    let found = StreamParser::new(content.as_bytes())
+                .collect::<Result<Vec<Entry>, _>>()
+                .expect("This code is valid");
+
+    let path = Identifier::new(None, String::from("path"));
+    assert_eq!(found, vec![Entry::KeyValue(path, Value::Raw(String::from("/very/long/continuation")))]);
+}
+
+#[test]
+fn stream_parser_goes_on_after_error() {
+    let content = "how to greet? = hello\nident = val\n";
+    let mut parser = StreamParser::new(content.as_bytes());
+
+    match parser.next() {
+        Some(Err(ParseFileError::ParseError(Error::InvalidIdentifier(err)))) => {
+            assert_eq!(format!("{}", err), "Invalid identifier how to greet? in how to greet? = hello at line 1");
+        },
+        other => panic!("Wrong return value: {:?}", other),
+    }
+
+    let ident = Identifier::new(None, String::from("ident"));
+    assert_eq!(parser.next().unwrap().unwrap(), Entry::KeyValue(ident, Value::Raw(String::from("val"))));
+    assert!(parser.next().is_none());
+}
+
+#[test]
+fn stream_parser_line_numbers() {
+    let content = "a = 1\n\nlong = \\\n  \\\nb = \\x\n";
+    let mut parser = StreamParser::new(content.as_bytes());
+
+    parser.next().unwrap().unwrap();
+    match parser.next() {
+        Some(Err(ParseFileError::ParseError(err))) => assert_eq!(err.line_number(), Some(5)),
+        other                                      => panic!("Wrong return value: {:?}", other),
+    }
+}
+
+#[test]
+fn stream_parser_unterminated_continuation() {
+    let content = "path = /very/long/\\\n";
+    let mut parser = StreamParser::new(content.as_bytes());
+
+    match parser.next() {
+        Some(Err(ParseFileError::ParseError(Error::ExpectedToken(_)))) => {},
+        other                                                          => panic!("Wrong return value: {:?}", other),
+    }
+    assert!(parser.next().is_none());
+}
+
+/// A reader failing at the first read
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("broken reader"))
+    }
+}
+
+#[test]
+fn stream_parser_io_error() {
+    let mut parser = StreamParser::new(BufReader::new(FailingReader));
+
+    match parser.next() {
+        Some(Err(ParseFileError::IOError(_))) => {},
+        other                                 => panic!("Wrong return value: {:?}", other),
+    }
+    assert!(parser.next().is_none());
+}
+
+#[test]
+fn stream_parser_with_options() {
+    let content = "# comment\nident = val # inline\n";
+    let options = crate::parse::ParserOptions {
+        comment_chars: vec!['#'],
+        ..crate::parse::ParserOptions::default()
+    };
+
+    let found = StreamParser::with_options(content.as_bytes(), options)
+                .collect::<Result<Vec<Entry>, _>>()
+                .expect("This code is valid");
+
+    let ident = Identifier::new(None, String::from("ident"));
+    assert_eq!(found, vec![
+        Entry::Comment(String::from("# comment")),
+        Entry::KeyValue(ident, Value::Raw(String::from("val"))),
+    ]);
+}
+
+#[test]
+fn stream_parser_strips_bom() {
+    let content = "\u{feff}[section]\n";
+
+    let found = StreamParser::new(content.as_bytes())
+                .collect::<Result<Vec<Entry>, _>>()
+                .expect("The byte-order mark is ignored");
+
+    assert_eq!(found, vec![Entry::Section(String::from("section"))]);
+}
+
+#[test]
+fn stream_parser_skips_long_lines() {
+    let content = format!("a = 1\nb = {}\nc = 3\n", "x".repeat(4096));
+    let options = crate::parse::ParserOptions {
+        max_line_len: Some(16),
+        ..crate::parse::ParserOptions::default()
+    };
+
+    let found: Vec<_> = StreamParser::with_options(content.as_bytes(), options).collect();
+    assert_eq!(found.len(), 3);
+    match &found[1] {
+        Err(ParseFileError::ParseError(Error::LimitExceeded(err))) => assert_eq!(err.line_number(), Some(2)),
+        other                                                      => panic!("Expected a LimitExceeded error, got {:?}", other),
+    }
+
+    let c = Identifier::new(None, String::from("c"));
+    assert_eq!(found[2].as_ref().unwrap(), &Entry::KeyValue(c, Value::Int(3)));
+}
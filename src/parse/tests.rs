@@ -8,7 +8,7 @@ fn token_iterator_no_escapes() {
                 .collect::<Vec<Token>>();
 
     let expected = message.chars()
-                          .map(|c| Token::Char(c))
+                          .map(Token::Char)
                           .collect::<Vec<Token>>();
 
     assert_eq!(found, expected);
@@ -50,7 +50,7 @@ fn token_iterator_unfinished_escape() {
 
     let mut expected = message.chars()
                               .take(message.len() - 1)
-                              .map(|c| Token::Char(c))
+                              .map(Token::Char)
                               .collect::<Vec<Token>>();
     expected.push(Token::Escape(String::from("\\")));
 
@@ -140,3 +140,35 @@ fn find_unescaped_not_found() {
 
     assert_eq!(None, find_unescaped(sequence, 'e'));
 }
+
+#[test]
+fn parse_str_byte_escapes() {
+    assert_eq!(parse_str("\\x41\\x42").unwrap(), "AB");
+    assert_eq!(parse_str("\\xf0\\x9f\\x98\\x80!").unwrap(), "\u{1f600}!");
+    // Only 2 digits belong to the escape when 6 are not available
+    assert_eq!(parse_str("\\x4142").unwrap(), "A42");
+}
+
+#[test]
+fn parse_str_code_point_escapes() {
+    assert_eq!(parse_str("\\u{1F600}").unwrap(), "\u{1f600}");
+    assert_eq!(parse_str("\\u{7}\\u{0}").unwrap(), "\x07\0");
+    assert_eq!(parse_str("\\x01f600").unwrap(), "\u{1f600}");
+}
+
+#[test]
+fn parse_str_invalid_escapes() {
+    for i in &["\\xc0\\x80", "\\xed\\xa0\\x80", "\\xc3", "\\x4", "\\xzz", "\\u{d800}", "\\u{110000}", "\\u{}", "\\u{41", "\\u41", "\\x110000"] {
+        match parse_str(i) {
+            Err(Error::InvalidEscape(_)) => {},
+            other                        => panic!("Wrong return value for {}: {:?}", i, other),
+        }
+    }
+}
+
+#[test]
+fn find_unescaped_after_byte_escape() {
+    let sequence = "\\x41;";
+
+    assert_eq!(Some(4), find_unescaped(sequence, ';'));
+}
@@ -0,0 +1,154 @@
+//! Contains the definition of [`ParseVisitor`](trait.ParseVisitor.html "parse::ParseVisitor")
+
+use crate::datas::{Identifier, Value};
+use crate::errors::Error;
+#[cfg(feature = "std")]
+use crate::errors::ParseFileError;
+use super::{Entry, Parser, ParserOptions};
+#[cfg(feature = "std")]
+use super::StreamParser;
+use core::ops::ControlFlow;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+/// The callbacks called by [`visit_content`](fn.visit_content.html "parse::visit_content") and [`visit_reader`](fn.visit_reader.html "parse::visit_reader") for each entry parsed, in their order
+/// 
+/// Each callback returns `ControlFlow::Continue(())` to go on parsing or `ControlFlow::Break(())` to stop right after this entry. The default callbacks ignore their entry, except [`on_error`](trait.ParseVisitor.html#method.on_error "parse::ParseVisitor::on_error") which stops at the first error
+/// 
+/// Like a [`StreamParser`](struct.StreamParser.html "parse::StreamParser"), the entries are given as they are declared, so a key assigned twice is visited twice
+/// 
+/// # Example
+/// ```
+/// use mininip::parse::{visit_content, ParseVisitor, ParserOptions};
+/// use mininip::datas::{Identifier, Value};
+/// use std::ops::ControlFlow;
+/// 
+/// /// Finds the value of the first key named `port`
+/// struct FindPort(Option<i64>);
+/// 
+/// impl ParseVisitor for FindPort {
+///     fn on_key_value(&mut self, ident: &Identifier, value: &Value) -> ControlFlow<()> {
+///         match value {
+///             Value::Int(port) if ident.name() == "port" => {
+///                 self.0 = Some(*port);
+///                 ControlFlow::Break(())
+///             },
+///             _                                          => ControlFlow::Continue(()),
+///         }
+///     }
+/// }
+/// 
+/// let mut visitor = FindPort(None);
+/// let content = "[server]\nport = 8080\n[client]\nport = 80\n";
+/// let flow = visit_content(content, ParserOptions::default(), &mut visitor).unwrap();
+/// 
+/// assert_eq!(flow, ControlFlow::Break(()));
+/// assert_eq!(visitor.0, Some(8080));
+/// ```
+pub trait ParseVisitor {
+    /// Called for each section declaration with the name of the section. The following keys belong to this section
+    fn on_section(&mut self, _section: &str) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called for each assignment with the identifier of the key and its value
+    fn on_key_value(&mut self, _ident: &Identifier, _value: &Value) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called for each comment on its own line, as written in the file (starting with its comment character)
+    fn on_comment(&mut self, _comment: &str) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called for each parsing error, knowing the number of its line. Parsing goes on at the next line on `ControlFlow::Continue(())` while `ControlFlow::Break(())` stops it and returns this error. Defaults to `ControlFlow::Break(())`
+    fn on_error(&mut self, _error: &Error) -> ControlFlow<()> {
+        ControlFlow::Break(())
+    }
+}
+
+/// Parses `content`, the whole text of an INI file, following `options` and calls the callbacks of `visitor` for each entry
+/// 
+/// # Return value
+/// `Ok(ControlFlow::Continue(()))` if the whole content was parsed
+/// 
+/// `Ok(ControlFlow::Break(()))` if `visitor` stopped the parsing
+/// 
+/// `Err(error)` with `error` as the parsing error `visitor` stopped at, knowing its line number
+/// 
+/// # See
+/// [`ParseVisitor`](trait.ParseVisitor.html "parse::ParseVisitor")
+pub fn visit_content<V: ParseVisitor + ?Sized>(content: &str, options: ParserOptions, visitor: &mut V) -> Result<ControlFlow<()>, Error> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut parser = Parser::with_options(options);
+
+    let mut line_number = 0;
+    for line in content.split_terminator('\n') {
+        line_number += 1;
+
+        let flow = match parser.read_line(line) {
+            Ok(Some((entry, ..))) => visit_entry(visitor, &entry),
+            Ok(None)             => ControlFlow::Continue(()),
+            Err(err)             => visit_error(visitor, err.with_line_number(line_number))?,
+        };
+        if flow.is_break() {
+            return Ok(flow);
+        }
+    }
+
+    match parser.finish() {
+        Ok(())   => Ok(ControlFlow::Continue(())),
+        Err(err) => visit_error(visitor, err.with_line_number(line_number)),
+    }
+}
+
+/// Parses the content of `reader` line after line following `options` and calls the callbacks of `visitor` for each entry, without storing the data parsed
+/// 
+/// # Return value
+/// `Ok(ControlFlow::Continue(()))` if the whole content was parsed
+/// 
+/// `Ok(ControlFlow::Break(()))` if `visitor` stopped the parsing. The lines following the one stopping the parsing are not read, although `reader` may have buffered them
+/// 
+/// `Err(error)` with `error` as either an I/O error or the parsing error `visitor` stopped at, knowing its line number
+/// 
+/// # Note
+/// Requires the `std` feature
+/// 
+/// # See
+/// [`ParseVisitor`](trait.ParseVisitor.html "parse::ParseVisitor")
+#[cfg(feature = "std")]
+pub fn visit_reader<R: BufRead, V: ParseVisitor + ?Sized>(reader: R, options: ParserOptions, visitor: &mut V) -> Result<ControlFlow<()>, ParseFileError> {
+    for i in StreamParser::with_options(reader, options) {
+        let flow = match i {
+            Ok(entry)                            => visit_entry(visitor, &entry),
+            Err(ParseFileError::ParseError(err)) => visit_error(visitor, err)?,
+            Err(err)                             => return Err(err),
+        };
+        if flow.is_break() {
+            return Ok(flow);
+        }
+    }
+
+    Ok(ControlFlow::Continue(()))
+}
+
+/// Calls the callback of `visitor` matching `entry`
+fn visit_entry<V: ParseVisitor + ?Sized>(visitor: &mut V, entry: &Entry) -> ControlFlow<()> {
+    match entry {
+        Entry::Section(section)       => visitor.on_section(section),
+        Entry::KeyValue(ident, value) => visitor.on_key_value(ident, value),
+        Entry::Comment(comment)       => visitor.on_comment(comment),
+    }
+}
+
+/// Gives `error` to `visitor`, returning it if `visitor` stops the parsing
+fn visit_error<V: ParseVisitor + ?Sized>(visitor: &mut V, error: Error) -> Result<ControlFlow<()>, Error> {
+    match visitor.on_error(&error) {
+        ControlFlow::Continue(()) => Ok(ControlFlow::Continue(())),
+        ControlFlow::Break(())    => Err(error),
+    }
+}
+
+
+#[cfg(test)]
+mod tests;
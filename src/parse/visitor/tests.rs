@@ -0,0 +1,86 @@
+use crate::parse::visitor::*;
+
+/// Records the entries visited, stopping at the key `stop` and going on after the errors if `lenient`
+#[derive(Default)]
+struct Recorder {
+    events: Vec<String>,
+    lenient: bool,
+}
+
+impl ParseVisitor for Recorder {
+    fn on_section(&mut self, section: &str) -> ControlFlow<()> {
+        self.events.push(format!("[{}]", section));
+        ControlFlow::Continue(())
+    }
+
+    fn on_key_value(&mut self, ident: &Identifier, value: &Value) -> ControlFlow<()> {
+        self.events.push(format!("{}={}", ident, value));
+        if ident.name() == "stop" {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+
+    fn on_comment(&mut self, comment: &str) -> ControlFlow<()> {
+        self.events.push(String::from(comment));
+        ControlFlow::Continue(())
+    }
+
+    fn on_error(&mut self, error: &Error) -> ControlFlow<()> {
+        self.events.push(format!("error at line {:?}", error.line_number()));
+        if self.lenient {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    }
+}
+
+const CONTENT: &str = "\
+; comment\n\
+a = 1\n\
+[section]\n\
+b = 2\n\
+stop = 3\n\
+c = 4\n";
+
+#[test]
+fn visit_content_calls_back() {
+    let mut visitor = Recorder::default();
+    let flow = visit_content(CONTENT, ParserOptions::default(), &mut visitor).unwrap();
+    assert_eq!(flow, ControlFlow::Break(()));
+    assert_eq!(visitor.events, ["; comment", "a=1", "[section]", "section.b=2", "section.stop=3"]);
+
+    // Stopping leaves nothing behind so the same visitor may be used again
+    visitor.events.clear();
+    let flow = visit_content("x = 1\n[s]\ny = 2", ParserOptions::default(), &mut visitor).unwrap();
+    assert_eq!(flow, ControlFlow::Continue(()));
+    assert_eq!(visitor.events, ["x=1", "[s]", "s.y=2"]);
+
+    let mut reader_visitor = Recorder::default();
+    let flow = visit_reader(CONTENT.as_bytes(), ParserOptions::default(), &mut reader_visitor).unwrap();
+    assert_eq!(flow, ControlFlow::Break(()));
+    assert_eq!(reader_visitor.events, ["; comment", "a=1", "[section]", "section.b=2", "section.stop=3"]);
+}
+
+#[test]
+fn visit_content_errors() {
+    let content = "a = 1\nbad\nb = 2\nc = \\\n";
+
+    let mut visitor = Recorder::default();
+    let err = visit_content(content, ParserOptions::default(), &mut visitor).unwrap_err();
+    assert_eq!(err.line_number(), Some(2));
+    assert_eq!(visitor.events, ["a=1", "error at line Some(2)"]);
+
+    let mut visitor = Recorder { lenient: true, ..Recorder::default() };
+    let flow = visit_content(content, ParserOptions::default(), &mut visitor).unwrap();
+    assert_eq!(flow, ControlFlow::Continue(()));
+    assert_eq!(visitor.events, ["a=1", "error at line Some(2)", "b=2", "error at line Some(4)"]);
+
+    let mut visitor = Recorder::default();
+    match visit_reader(content.as_bytes(), ParserOptions::default(), &mut visitor) {
+        Err(ParseFileError::ParseError(err)) => assert_eq!(err.line_number(), Some(2)),
+        other                                => panic!("Expected a parsing error, got {:?}", other),
+    }
+}
@@ -0,0 +1,479 @@
+//! Serializes any type implementing `serde::Serialize` into an INI string. Requires the `serde` feature
+//! 
+//! The value serialized must be a struct or a map. Its fields are mapped as follows
+//! - a boolean, an integer, a float, a string or a unit enum variant is a key of the global section with a typed [`Value`](../datas/enum.Value.html "datas::Value"). A string or a unit variant is a `Str`
+//! - a sequence or a tuple of such values is a `Value::Array` whose elements are separated by `,`. It is parsed back when [`ParserOptions::array_separator`](../parse/struct.ParserOptions.html#structfield.array_separator "parse::ParserOptions::array_separator") is `Some(',')`
+//! - a struct or a map with string keys is a section named after the field, whose keys are its own fields mapped as above. A section can not contain another section
+//! - `None` and `()` are omitted
+//! 
+//! Any other shape, like a nested sequence, a section inside a section, bytes or an enum variant holding data, is an error
+//! 
+//! # Example
+//! ```
+//! use mininip::ser::to_string;
+//! use serde::Serialize;
+//! 
+//! #[derive(Serialize)]
+//! struct Config {
+//!     name: String,
+//!     debug: Option<bool>,
+//!     server: Server,
+//! }
+//! 
+//! #[derive(Serialize)]
+//! struct Server {
+//!     port: u16,
+//!     aliases: Vec<&'static str>,
+//! }
+//! 
+//! let config = Config {
+//!     name: String::from("my service"),
+//!     debug: None,
+//!     server: Server {
+//!         port: 8080,
+//!         aliases: vec!["a.com", "b.com"],
+//!     },
+//! };
+//! 
+//! let expected = "\
+//! name='my service'\n\
+//! \n\
+//! [server]\n\
+//! port=8080\n\
+//! aliases='a.com','b.com'\n";
+//! assert_eq!(to_string(&config).unwrap(), expected);
+//! ```
+
+use crate::datas::{Identifier, Value, tree::Tree};
+use crate::errors::SerdeError;
+use serde::ser::{self, Serialize, Impossible};
+use std::convert::TryFrom;
+
+/// Serializes `value` into a `String` formatted as INI, escaped like [`dump_tree`](../dump/fn.dump_tree.html "dump::dump_tree") does
+/// 
+/// # Return value
+/// `Ok(content)` with `content` as the INI code of `value`
+/// 
+/// `Err(error)` with `error` as a [`SerdeError`](../errors/struct.SerdeError.html "errors::SerdeError") if `value` can not be represented as INI (see the [module documentation](index.html "ser"))
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, SerdeError> {
+    let mut tree = Tree::new();
+    value.serialize(TreeSerializer { tree: &mut tree })?;
+
+    Ok(crate::dump::dump_tree(&tree))
+}
+
+/// Returns an error telling `shape` can not be serialized
+fn unsupported(shape: &str) -> SerdeError {
+    SerdeError::new(format!("Can not serialize {} as INI", shape))
+}
+
+/// Returns `name` if it is a valid identifier or an error otherwise
+fn identifier(name: &str) -> Result<String, SerdeError> {
+    if Identifier::is_valid(name) {
+        Ok(String::from(name))
+    } else {
+        Err(SerdeError::new(format!("Invalid identifier {}", name)))
+    }
+}
+
+
+/// Implements the methods of `serde::Serializer` for the scalar types by serializing them with a `ValueSerializer` and passing the result to `self.insert`
+macro_rules! serialize_scalars {
+    ($($method:ident($type:ty)),*) => {
+        $(
+            fn $method(self, value: $type) -> Result<(), SerdeError> {
+                let value = ser::Serializer::$method(ValueSerializer, value);
+                self.insert(value)
+            }
+        )*
+    };
+}
+
+/// Implements the methods of `serde::Serializer` returning an error for the shapes which can not be serialized
+macro_rules! unsupported_shapes {
+    ($ok:ty) => {
+        fn serialize_bytes(self, _value: &[u8]) -> Result<$ok, SerdeError> {
+            Err(unsupported("bytes"))
+        }
+
+        fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, _variant: &'static str, _value: &T) -> Result<$ok, SerdeError> {
+            Err(unsupported("an enum variant holding data"))
+        }
+
+        fn serialize_tuple_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, SerdeError> {
+            Err(unsupported("an enum variant holding data"))
+        }
+
+        fn serialize_struct_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, SerdeError> {
+            Err(unsupported("an enum variant holding data"))
+        }
+    };
+}
+
+
+/// The serializer of the whole value, which must be a struct or a map
+struct TreeSerializer<'a> {
+    tree: &'a mut Tree,
+}
+
+impl<'a> TreeSerializer<'a> {
+    fn top_level_error() -> SerdeError {
+        unsupported("a value which is not a struct or a map at the top level")
+    }
+}
+
+impl<'a> ser::Serializer for TreeSerializer<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+    type SerializeSeq = Impossible<(), SerdeError>;
+    type SerializeTuple = Impossible<(), SerdeError>;
+    type SerializeTupleStruct = Impossible<(), SerdeError>;
+    type SerializeTupleVariant = Impossible<(), SerdeError>;
+    type SerializeMap = SectionSerializer<'a>;
+    type SerializeStruct = SectionSerializer<'a>;
+    type SerializeStructVariant = Impossible<(), SerdeError>;
+
+    fn serialize_bool(self, _value: bool) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_i8(self, _value: i8) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_i16(self, _value: i16) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_i32(self, _value: i32) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_i64(self, _value: i64) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_u8(self, _value: u8) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_u16(self, _value: u16) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_u32(self, _value: u32) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_u64(self, _value: u64) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_f32(self, _value: f32) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_f64(self, _value: f64) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_char(self, _value: char) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_str(self, _value: &str) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_none(self) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_unit(self) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerdeError> { Err(TreeSerializer::top_level_error()) }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, _variant: &'static str) -> Result<(), SerdeError> {
+        Err(TreeSerializer::top_level_error())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Err(TreeSerializer::top_level_error())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        Err(TreeSerializer::top_level_error())
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(TreeSerializer::top_level_error())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Ok(SectionSerializer::new(self.tree, None))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, SerdeError> {
+        Ok(SectionSerializer::new(self.tree, None))
+    }
+
+    unsupported_shapes!(());
+}
+
+
+/// The serializer of the fields of a section, `None` for the global section
+struct SectionSerializer<'a> {
+    tree: &'a mut Tree,
+    section: Option<String>,
+    /// The key given by `serialize_key` waiting for its value
+    key: Option<String>,
+}
+
+impl<'a> SectionSerializer<'a> {
+    fn new(tree: &'a mut Tree, section: Option<String>) -> SectionSerializer<'a> {
+        SectionSerializer {
+            tree,
+            section,
+            key: None,
+        }
+    }
+
+    /// Serializes `value` as the value of `key`
+    fn serialize_entry_value<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), SerdeError> {
+        let serializer = EntrySerializer {
+            tree: self.tree,
+            section: self.section.as_deref(),
+            key,
+        };
+
+        value.serialize(serializer)
+             .map_err(|err| err.locate(self.section.as_deref(), Some(key)))
+    }
+}
+
+impl<'a> ser::SerializeStruct for SectionSerializer<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError> {
+        self.serialize_entry_value(key, value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeMap for SectionSerializer<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerdeError> {
+        match key.serialize(ValueSerializer)? {
+            Value::Str(key) => {
+                self.key = Some(key);
+                Ok(())
+            },
+            _               => Err(unsupported("a map whose keys are not strings")),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let key = self.key.take()
+                          .expect("`serialize_value` is called after `serialize_key`");
+        self.serialize_entry_value(&key, value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+
+/// The serializer of the value of a key, which may also be a section if the key is in the global section
+struct EntrySerializer<'a, 'b> {
+    tree: &'a mut Tree,
+    section: Option<&'b str>,
+    key: &'b str,
+}
+
+impl<'a, 'b> EntrySerializer<'a, 'b> {
+    /// Inserts `value` into the tree
+    fn insert(self, value: Result<Value, SerdeError>) -> Result<(), SerdeError> {
+        let ident = Identifier::new(self.section.map(String::from), identifier(self.key)?);
+        self.tree.insert(ident, value?);
+
+        Ok(())
+    }
+
+    /// Returns a serializer for the section named after the key
+    fn section(self) -> Result<SectionSerializer<'a>, SerdeError> {
+        if self.section.is_some() {
+            return Err(unsupported("a section inside a section"));
+        }
+
+        Ok(SectionSerializer::new(self.tree, Some(identifier(self.key)?)))
+    }
+}
+
+impl<'a, 'b> ser::Serializer for EntrySerializer<'a, 'b> {
+    type Ok = ();
+    type Error = SerdeError;
+    type SerializeSeq = ArraySerializer<'a, 'b>;
+    type SerializeTuple = ArraySerializer<'a, 'b>;
+    type SerializeTupleStruct = ArraySerializer<'a, 'b>;
+    type SerializeTupleVariant = Impossible<(), SerdeError>;
+    type SerializeMap = SectionSerializer<'a>;
+    type SerializeStruct = SectionSerializer<'a>;
+    type SerializeStructVariant = Impossible<(), SerdeError>;
+
+    serialize_scalars!(serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32), serialize_i64(i64),
+                       serialize_u8(u8), serialize_u16(u16), serialize_u32(u32), serialize_u64(u64),
+                       serialize_f32(f32), serialize_f64(f64), serialize_char(char), serialize_str(&str));
+
+    fn serialize_none(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+
+    fn serialize_unit(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerdeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, name: &'static str, index: u32, variant: &'static str) -> Result<(), SerdeError> {
+        let value = ser::Serializer::serialize_unit_variant(ValueSerializer, name, index, variant);
+        self.insert(value)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Ok(ArraySerializer {
+            entry: self,
+            values: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        self.section()
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, SerdeError> {
+        self.section()
+    }
+
+    unsupported_shapes!(());
+}
+
+
+/// The serializer of a sequence, which is a `Value::Array`
+struct ArraySerializer<'a, 'b> {
+    entry: EntrySerializer<'a, 'b>,
+    values: Vec<Value>,
+}
+
+impl<'a, 'b> ser::SerializeSeq for ArraySerializer<'a, 'b> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.values.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        self.entry.insert(Ok(Value::Array(self.values)))
+    }
+}
+
+impl<'a, 'b> ser::SerializeTuple for ArraySerializer<'a, 'b> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl<'a, 'b> ser::SerializeTupleStruct for ArraySerializer<'a, 'b> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+
+/// The serializer of a scalar value
+struct ValueSerializer;
+
+impl ValueSerializer {
+    fn scalar_error() -> SerdeError {
+        unsupported("a value which is not a scalar inside a sequence")
+    }
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = SerdeError;
+    type SerializeSeq = Impossible<Value, SerdeError>;
+    type SerializeTuple = Impossible<Value, SerdeError>;
+    type SerializeTupleStruct = Impossible<Value, SerdeError>;
+    type SerializeTupleVariant = Impossible<Value, SerdeError>;
+    type SerializeMap = Impossible<Value, SerdeError>;
+    type SerializeStruct = Impossible<Value, SerdeError>;
+    type SerializeStructVariant = Impossible<Value, SerdeError>;
+
+    fn serialize_bool(self, value: bool) -> Result<Value, SerdeError> { Ok(Value::Bool(value)) }
+    fn serialize_i8(self, value: i8) -> Result<Value, SerdeError> { Ok(Value::Int(i64::from(value))) }
+    fn serialize_i16(self, value: i16) -> Result<Value, SerdeError> { Ok(Value::Int(i64::from(value))) }
+    fn serialize_i32(self, value: i32) -> Result<Value, SerdeError> { Ok(Value::Int(i64::from(value))) }
+    fn serialize_i64(self, value: i64) -> Result<Value, SerdeError> { Ok(Value::Int(value)) }
+    fn serialize_u8(self, value: u8) -> Result<Value, SerdeError> { Ok(Value::Int(i64::from(value))) }
+    fn serialize_u16(self, value: u16) -> Result<Value, SerdeError> { Ok(Value::Int(i64::from(value))) }
+    fn serialize_u32(self, value: u32) -> Result<Value, SerdeError> { Ok(Value::Int(i64::from(value))) }
+    fn serialize_f32(self, value: f32) -> Result<Value, SerdeError> { Ok(Value::Float(f64::from(value))) }
+    fn serialize_f64(self, value: f64) -> Result<Value, SerdeError> { Ok(Value::Float(value)) }
+    fn serialize_char(self, value: char) -> Result<Value, SerdeError> { Ok(Value::Str(value.to_string())) }
+    fn serialize_str(self, value: &str) -> Result<Value, SerdeError> { Ok(Value::Str(String::from(value))) }
+
+    fn serialize_u64(self, value: u64) -> Result<Value, SerdeError> {
+        match i64::try_from(value) {
+            Ok(value) => Ok(Value::Int(value)),
+            Err(_)    => Err(SerdeError::new(format!("{} does not fit in a 64 bits signed integer", value))),
+        }
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<Value, SerdeError> {
+        Ok(Value::Str(String::from(variant)))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<Value, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_none(self) -> Result<Value, SerdeError> { Err(ValueSerializer::scalar_error()) }
+    fn serialize_unit(self) -> Result<Value, SerdeError> { Err(ValueSerializer::scalar_error()) }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, SerdeError> { Err(ValueSerializer::scalar_error()) }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<Value, SerdeError> {
+        Err(ValueSerializer::scalar_error())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Err(ValueSerializer::scalar_error())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        Err(ValueSerializer::scalar_error())
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(ValueSerializer::scalar_error())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Err(ValueSerializer::scalar_error())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, SerdeError> {
+        Err(ValueSerializer::scalar_error())
+    }
+
+    unsupported_shapes!(Value);
+}
+
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,131 @@
+use crate::ser::*;
+use crate::parse::{Parser, ParserOptions};
+use serde::{Serialize, Deserialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Level {
+    Debug,
+    Info,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Server {
+    host: String,
+    port: u16,
+    ratio: f64,
+    level: Level,
+    aliases: Vec<String>,
+    timeout: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    name: String,
+    verbose: bool,
+    server: Server,
+}
+
+fn config() -> Config {
+    Config {
+        name: String::from("a = b ; c"),
+        verbose: true,
+        server: Server {
+            host: String::from("localhost"),
+            port: 8080,
+            ratio: 0.5,
+            level: Level::Info,
+            aliases: vec![String::from("a.com"), String::from("b,c.com")],
+            timeout: None,
+        },
+    }
+}
+
+#[test]
+fn to_string_struct() {
+    let expected = "\
+    name='a \\= b \\; c'\n\
+    verbose=true\n\
+    \n\
+    [server]\n\
+    host='localhost'\n\
+    port=8080\n\
+    ratio=0.5\n\
+    level='Info'\n\
+    aliases='a.com','b,c.com'\n";
+
+    assert_eq!(to_string(&config()).unwrap(), expected);
+}
+
+#[test]
+fn to_string_round_trip() {
+    let content = to_string(&config()).unwrap();
+
+    let options = ParserOptions {
+        array_separator: Some(','),
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(options);
+    for i in content.lines() {
+        parser.parse_line(i).unwrap();
+    }
+    let tree = parser.into_tree();
+
+    assert_eq!(crate::de::from_tree::<Config>(&tree).unwrap(), config());
+}
+
+#[test]
+fn to_string_map() {
+    let mut section = BTreeMap::new();
+    section.insert("b", 2);
+    section.insert("a", 1);
+    let mut data = BTreeMap::new();
+    data.insert("section", section);
+
+    assert_eq!(to_string(&data).unwrap(), "[section]\na=1\nb=2\n");
+}
+
+#[test]
+fn to_string_unsupported() {
+    #[derive(Serialize)]
+    struct Nested {
+        section: BTreeMap<&'static str, BTreeMap<&'static str, i32>>,
+    }
+
+    #[derive(Serialize)]
+    enum Shape {
+        Circle(f64),
+    }
+
+    #[derive(Serialize)]
+    struct WithEnum {
+        shape: Shape,
+    }
+
+    #[derive(Serialize)]
+    struct WithNestedSeq {
+        matrix: Vec<Vec<i32>>,
+    }
+
+    let mut inner = BTreeMap::new();
+    inner.insert("sub", BTreeMap::new());
+    let err = to_string(&Nested { section: inner }).unwrap_err();
+    assert_eq!(err.section(), Some("section"));
+    assert_eq!(err.key(), Some("sub"));
+    assert_eq!(format!("{}", err), "Can not serialize a section inside a section as INI for key sub in section section");
+
+    let err = to_string(&WithEnum { shape: Shape::Circle(1.0) }).unwrap_err();
+    assert_eq!(err.key(), Some("shape"));
+
+    assert!(to_string(&WithNestedSeq { matrix: vec![vec![1]] }).is_err());
+    assert!(to_string(&42).is_err());
+    assert!(to_string(&u64::MAX).is_err());
+}
+
+#[test]
+fn to_string_invalid_identifier() {
+    let mut data = BTreeMap::new();
+    data.insert("not valid!", 1);
+
+    assert!(to_string(&data).is_err());
+}
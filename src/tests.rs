@@ -1,9 +1,9 @@
 use crate::{parse, dump, errors};
-use parse::parse_file;
+use parse::{parse_file, parse_file_with_options, ParserOptions};
 use errors::ParseFileError;
 use crate::datas::{Identifier, Value};
 use std::collections::HashMap;
-use dump::dump_into_file;
+use dump::{dump_into_file, dump_tree};
 use std::fs::{self, File};
 use std::io::Read;
 
@@ -56,9 +56,9 @@ fn parse_good_file() {
 fn parse_bad_file() {
     let err = parse_file("bad.ini");
     match err {
-        Ok(_)                              => panic!("This file contains wrong code and shouldn't be allowed"),
-        Err(ParseFileError::ParseError(_)) => {},
-        Err(err)                           => panic!("Wrong error value returned: {:?}", err),
+        Ok(_)                                => panic!("This file contains wrong code and shouldn't be allowed"),
+        Err(ParseFileError::ParseError(err)) => assert_eq!(err.line_number(), Some(1)),
+        Err(err)                             => panic!("Wrong error value returned: {:?}", err),
     }
 }
 
@@ -116,3 +116,203 @@ fn test_dump_into_file() {
 
     assert_eq!(content, expected);
 }
+
+#[test]
+fn dump_tree_keeps_file_order() {
+    let options = ParserOptions {
+        keep_formats: true,
+        ..ParserOptions::default()
+    };
+    let tree = parse_file_with_options("good.ini", options).unwrap();
+
+    let expected = "\
+    author=\"Boris DRYKONINGEN\"\n\
+    version_major=0\n\
+    \n\
+    [numbers]\n\
+    one=1\n\
+    two=2\n\
+    three=3\n\
+    \n\
+    [symbols]\n\
+    smiley=\\x00263a\n\
+    semicolon=\\;\n\
+    \n\
+    [valid since 1.2.0]\n\
+    contains spaces=on\n\
+    $starts-with-$=on\n\
+    contains:=on\n";
+
+    assert_eq!(dump_tree(&tree), expected);
+}
+
+#[test]
+fn parse_file_with_bom() {
+    let path = "test bom.ini";
+    fs::write(path, "\u{feff}[section]\nkey = value\n").unwrap();
+
+    let data = parse_file(path);
+    if let Err(err) = fs::remove_file(path) {
+        eprintln!("Error while removing the file: {}", err);
+    }
+
+    let key = Identifier::new(Some(String::from("section")), String::from("key"));
+    assert_eq!(data.unwrap()[&key], Value::Raw(String::from("value")));
+}
+
+#[test]
+fn parse_file_utf16() {
+    let path = "test utf16.ini";
+    fs::write(path, b"\xFF\xFE[\x00a\x00]\x00").unwrap();
+
+    let err = parse_file(path);
+    if let Err(err) = fs::remove_file(path) {
+        eprintln!("Error while removing the file: {}", err);
+    }
+
+    match err {
+        Err(ParseFileError::UnsupportedEncoding(name)) => assert_eq!(name, "UTF-16 LE"),
+        other                                          => panic!("Wrong return value: {:?}", other),
+    }
+}
+
+#[test]
+fn parse_file_lossy_collects_errors() {
+    let path = "test lossy.ini";
+    fs::write(path, "a = 1\nbad key! = 2\nb = 'unterminated\n[section]\nc = 3\n[bad section!]\nd = 4\n[other]\ne = 5\n[unclosed\nf = 6\n").unwrap();
+
+    let result = parse::parse_file_lossy(path, ParserOptions::default());
+    if let Err(err) = fs::remove_file(path) {
+        eprintln!("Error while removing the file: {}", err);
+    }
+
+    let (tree, errors) = result.unwrap();
+    let lines: Vec<_> = errors.iter().map(errors::Error::line_number).collect();
+    assert_eq!(lines, vec![Some(2), Some(3), Some(6), Some(10)]);
+    match &errors[0] {
+        errors::Error::InvalidIdentifier(err) => assert_eq!(format!("{}", err), "Invalid identifier bad key! in bad key! = 2 at line 2"),
+        other                                 => panic!("Wrong error returned: {:?}", other),
+    }
+
+    assert_eq!(tree.get_value(None, "a"), Some(&Value::Int(1)));
+    assert_eq!(tree.get_value(Some("section"), "c"), Some(&Value::Int(3)));
+    // The keys of the invalid section are skipped until the next valid one
+    assert_eq!(tree.get_value(Some("section"), "d"), None);
+    assert_eq!(tree.get_value(Some("other"), "e"), Some(&Value::Int(5)));
+    // The parsing stops at the section which never closes
+    assert_eq!(tree.get_value(Some("other"), "f"), None);
+    assert!(matches!(errors[3], errors::Error::ExpectedToken(_)));
+}
+
+#[test]
+fn parse_file_lossy_goes_on_after_a_bad_section_name() {
+    let path = "test lossy bad section.ini";
+    fs::write(path, "a=1\n[b@d]\nb=2\n[c]\nc=3\n").unwrap();
+
+    let result = parse::parse_file_lossy(path, ParserOptions::default());
+    if let Err(err) = fs::remove_file(path) {
+        eprintln!("Error while removing the file: {}", err);
+    }
+
+    let (tree, errors) = result.unwrap();
+    let lines: Vec<_> = errors.iter().map(errors::Error::line_number).collect();
+    assert_eq!(lines, vec![Some(2)]);
+    assert_eq!(tree.get_value(None, "a"), Some(&Value::Int(1)));
+    assert_eq!(tree.get_value(None, "b"), None);
+    assert_eq!(tree.get_value(Some("c"), "c"), Some(&Value::Int(3)));
+}
+
+#[test]
+fn parse_file_lossy_skips_long_lines() {
+    let path = "test lossy long lines.ini";
+    fs::write(path, "a = 1\nlong = a very long value\nbad key! = 2\nb = 2\n").unwrap();
+
+    let options = ParserOptions {
+        max_line_len: Some(16),
+        ..ParserOptions::default()
+    };
+    let result = parse::parse_file_lossy(path, options);
+    if let Err(err) = fs::remove_file(path) {
+        eprintln!("Error while removing the file: {}", err);
+    }
+
+    let (tree, errors) = result.unwrap();
+    let lines: Vec<_> = errors.iter().map(errors::Error::line_number).collect();
+    assert_eq!(lines, vec![Some(2), Some(3)]);
+    assert!(matches!(errors[0], errors::Error::LimitExceeded(_)));
+    assert_eq!(tree.get_value(None, "long"), None);
+    assert_eq!(tree.get_value(None, "b"), Some(&Value::Int(2)));
+}
+
+#[test]
+fn parse_file_lossy_reports_duplicate_sections() {
+    let path = "test lossy duplicates.ini";
+    fs::write(path, "[a]\nx = 1\n[b]\ny = 2\nbad key! = 3\n[a]\nz = 4\n[a]\n").unwrap();
+
+    let result = parse::parse_file_lossy_with_duplicates(path, ParserOptions::default());
+    if let Err(err) = fs::remove_file(path) {
+        eprintln!("Error while removing the file: {}", err);
+    }
+
+    let (tree, errors, duplicates) = result.unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(duplicates, vec![(String::from("a"), vec![1, 6, 8])]);
+    assert_eq!(tree.get_value(Some("a"), "x"), Some(&Value::Int(1)));
+    assert_eq!(tree.get_value(Some("a"), "z"), Some(&Value::Int(4)));
+}
+
+#[test]
+fn parse_file_includes() {
+    fs::create_dir_all("test include").unwrap();
+    fs::write("test include/main.ini", "name = main\n@include tls.ini\nport = 443\n").unwrap();
+    fs::write("test include/tls.ini", "[tls]\ncert = a.pem\n").unwrap();
+    fs::write("test include/loop.ini", "a = 1\n@include 'loop.ini'\n").unwrap();
+
+    let options = ParserOptions {
+        include_directive: Some(String::from("@include")),
+        ..ParserOptions::default()
+    };
+    let tree = parse_file_with_options("test include/main.ini", options.clone());
+    let cycle = parse_file_with_options("test include/loop.ini", options);
+    if let Err(err) = fs::remove_dir_all("test include") {
+        eprintln!("Error while removing the directory: {}", err);
+    }
+
+    let tree = tree.unwrap();
+    assert_eq!(tree.get_value(None, "name"), Some(&Value::Raw(String::from("main"))));
+    assert_eq!(tree.get_value(Some("tls"), "cert"), Some(&Value::Raw(String::from("a.pem"))));
+    // The lines of the included file are parsed as if they were written instead of the directive
+    assert_eq!(tree.get_value(Some("tls"), "port"), Some(&Value::Int(443)));
+
+    match cycle {
+        Err(ParseFileError::ParseError(errors::Error::IncludeCycle(err))) => {
+            assert_eq!(err.path(), "loop.ini");
+            assert_eq!(err.line_number(), Some(2));
+        },
+        other                                                            => panic!("Wrong return value: {:?}", other),
+    }
+}
+
+#[test]
+fn parse_file_reports_diagnostics() {
+    use parse::Severity;
+
+    let path = "test diagnostics.ini";
+    fs::write(path, "port = 8080\nname = service\n\n\n[a]\n[a]\n").unwrap();
+
+    let options = ParserOptions {
+        infer_types: false,
+        ..ParserOptions::default()
+    };
+    let result = parse::parse_file_with_diagnostics(path, options);
+    if let Err(err) = fs::remove_file(path) {
+        eprintln!("Error while removing the file: {}", err);
+    }
+
+    let (tree, diagnostics) = result.unwrap();
+    assert_eq!(tree.get_value(None, "port"), Some(&Value::Raw(String::from("8080"))));
+
+    let found: Vec<_> = diagnostics.iter().map(|i| (i.severity(), i.line_number())).collect();
+    assert_eq!(found, vec![(Severity::Warning, 1), (Severity::Note, 4), (Severity::Warning, 6)]);
+    assert_eq!(diagnostics[0].message(), "Value 8080 of key port read as a raw string since the types are not inferred");
+}